@@ -1,19 +1,165 @@
 use pbc_contract_common::{address::Address, avl_tree_map::AvlTreeMap};
+use utils::merkle::{validate_merkle_root, verify_merkle_proof};
 
-use crate::state::AirdropState;
+use crate::{
+    msg::{AirdropCampaignMsg, AirdropMerkleClaimMsg},
+    state::{merkle_leaf, AirdropAllowance, AirdropCampaign, AirdropState, MerkleAirdrop},
+};
 
 pub fn execute_init() -> AirdropState {
     AirdropState {
         inventory: AvlTreeMap::new(),
+        merkle_airdrops: AvlTreeMap::new(),
+        allowances: AvlTreeMap::new(),
+        campaigns: AvlTreeMap::new(),
     }
 }
 
-pub fn execute_airdrop(state: &mut AirdropState, address: &Address) {
-    assert!(state.has_airdrop(address), "No airdrop for the address");
+/// Uses the best airdrop of the address for the given mint
+/// Allowances of the best eligible campaign are used first, then the inventory
+/// Returns the name of the used campaign, if any
+pub fn execute_airdrop(
+    state: &mut AirdropState,
+    address: &Address,
+    domain_length: u32,
+    subscription_years: u32,
+    unix_millis_now: i64,
+) -> Option<String> {
+    match state.get_best_allowance(address, domain_length, subscription_years, unix_millis_now) {
+        Some(allowance) => {
+            state._use_allowance(&allowance);
 
-    state._use_airdrop(address);
+            Some(allowance.campaign)
+        }
+        None => {
+            assert!(state.has_airdrop(address), "No airdrop for the address");
+
+            state._use_airdrop(address);
+
+            None
+        }
+    }
 }
 
 pub fn execute_add_airdrop(state: &mut AirdropState, address: &Address) {
     state._add_airdrop(address);
 }
+
+/// Adds an allowance of the campaign to the address
+pub fn execute_add_campaign_airdrop(state: &mut AirdropState, campaign: &str, address: &Address) {
+    assert_campaign_not_revoked(state, campaign);
+
+    state._add_allowance(
+        &AirdropAllowance {
+            address: *address,
+            campaign: campaign.to_string(),
+            min_domain_length: 0,
+        },
+        1,
+    );
+}
+
+pub fn execute_create_campaign(state: &mut AirdropState, msg: &AirdropCampaignMsg) {
+    assert!(
+        !state.campaigns.contains_key(&msg.name),
+        "Airdrop campaign already exists"
+    );
+
+    state.campaigns.insert(
+        msg.name.clone(),
+        AirdropCampaign {
+            expires_at: msg.expires_at,
+            min_domain_length: msg.min_domain_length,
+            max_subscription_years: msg.max_subscription_years,
+            revoked: false,
+        },
+    );
+}
+
+/// Revokes the campaign, remaining allowances cannot be used anymore
+pub fn execute_revoke_campaign(state: &mut AirdropState, campaign: &str) {
+    assert_campaign_not_revoked(state, campaign);
+
+    let mut airdrop_campaign = state.campaigns.get(&campaign.to_string()).unwrap();
+    airdrop_campaign.revoked = true;
+    state
+        .campaigns
+        .insert(campaign.to_string(), airdrop_campaign);
+}
+
+/// Publishes the merkle root of the allowances of an airdrop campaign
+pub fn execute_add_merkle_airdrop(
+    state: &mut AirdropState,
+    campaign: &str,
+    merkle_root: &str,
+    leaves_count: u32,
+) {
+    assert_campaign_not_revoked(state, campaign);
+    assert!(
+        !state.merkle_airdrops.contains_key(&campaign.to_string()),
+        "Merkle airdrop already exists"
+    );
+    validate_merkle_root(merkle_root);
+
+    state.merkle_airdrops.insert(
+        campaign.to_string(),
+        MerkleAirdrop::new(merkle_root, leaves_count),
+    );
+}
+
+/// Claims a merkle airdrop leaf of the campaign for the address
+/// The claimed amount can then be used for domains of at least `min_domain_length` chars
+pub fn execute_claim_merkle_airdrop(
+    state: &mut AirdropState,
+    address: &Address,
+    msg: &AirdropMerkleClaimMsg,
+    unix_millis_now: i64,
+) {
+    assert_campaign_not_revoked(state, &msg.campaign);
+    assert!(
+        state
+            .campaigns
+            .get(&msg.campaign)
+            .unwrap()
+            .is_active(unix_millis_now),
+        "Airdrop campaign is expired"
+    );
+
+    let airdrop = state.merkle_airdrops.get(&msg.campaign);
+    assert!(airdrop.is_some(), "Merkle airdrop not found");
+
+    let mut airdrop = airdrop.unwrap();
+    assert!(
+        msg.index < airdrop.leaves_count,
+        "Invalid merkle leaf index"
+    );
+    assert!(
+        !airdrop.is_claimed(msg.index),
+        "Merkle airdrop already claimed"
+    );
+    assert!(msg.amount > 0, "Invalid merkle airdrop amount");
+
+    let leaf = merkle_leaf(msg.index, address, msg.amount, msg.min_domain_length);
+    verify_merkle_proof(&airdrop.merkle_root, &leaf, &msg.proof);
+
+    airdrop._set_claimed(msg.index);
+    state.merkle_airdrops.insert(msg.campaign.clone(), airdrop);
+
+    state._add_allowance(
+        &AirdropAllowance {
+            address: *address,
+            campaign: msg.campaign.clone(),
+            min_domain_length: msg.min_domain_length,
+        },
+        msg.amount,
+    );
+}
+
+fn assert_campaign_not_revoked(state: &AirdropState, campaign: &str) {
+    let airdrop_campaign = state.campaigns.get(&campaign.to_string());
+    assert!(airdrop_campaign.is_some(), "Airdrop campaign not found");
+    assert!(
+        !airdrop_campaign.unwrap().revoked,
+        "Airdrop campaign is revoked"
+    );
+}
@@ -1,4 +1,5 @@
 pub mod actions;
+pub mod msg;
 pub mod state;
 
 #[cfg(test)]
@@ -0,0 +1,25 @@
+use create_type_spec_derive::CreateTypeSpec;
+use read_write_rpc_derive::ReadWriteRPC;
+
+/// This structure describes fields for the airdrop campaign creation msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct AirdropCampaignMsg {
+    pub name: String,
+    /// Unix millis timestamp
+    pub expires_at: Option<i64>,
+    pub min_domain_length: u32,
+    pub max_subscription_years: u32,
+}
+
+/// This structure describes fields for the merkle airdrop claim msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct AirdropMerkleClaimMsg {
+    pub campaign: String,
+    /// Index of the leaf in the merkle tree
+    pub index: u32,
+    /// Amount of domains that can be minted with the airdrop
+    pub amount: u128,
+    pub min_domain_length: u32,
+    /// Hex encoded merkle proof
+    pub proof: Vec<String>,
+}
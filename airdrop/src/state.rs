@@ -1,11 +1,115 @@
 use create_type_spec_derive::CreateTypeSpec;
-use pbc_contract_common::{address::Address, avl_tree_map::AvlTreeMap};
+use pbc_contract_common::{
+    address::Address, avl_tree_map::AvlTreeMap, sorted_vec_map::SortedVecMap,
+};
 use read_write_state_derive::ReadWriteState;
 
 #[repr(C)]
 #[derive(ReadWriteState, CreateTypeSpec, Default, Debug)]
 pub struct AirdropState {
     pub inventory: AvlTreeMap<Address, u128>,
+    pub merkle_airdrops: AvlTreeMap<String, MerkleAirdrop>,
+    /// Amounts of the campaign allowances of an address, by min domain length
+    pub allowances: AvlTreeMap<AirdropAllowanceKey, SortedVecMap<u32, u128>>,
+    pub campaigns: AvlTreeMap<String, AirdropCampaign>,
+}
+
+/// Named airdrop campaign, each with its own eligibility rules
+#[repr(C)]
+#[derive(ReadWriteState, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct AirdropCampaign {
+    /// Unix millis timestamp
+    pub expires_at: Option<i64>,
+    pub min_domain_length: u32,
+    pub max_subscription_years: u32,
+    pub revoked: bool,
+}
+
+/// Airdrop campaign published as a merkle root of `(index, address, amount, min_domain_length)` leaves
+#[repr(C)]
+#[derive(ReadWriteState, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct MerkleAirdrop {
+    /// Hex encoded merkle root
+    pub merkle_root: String,
+    pub leaves_count: u32,
+    /// Bitmap of the claimed leaves, one bit per leaf index
+    pub claimed: Vec<u8>,
+}
+
+/// Amount of domains an address can mint for free within a campaign,
+/// usable for domains of at least `min_domain_length` chars
+#[repr(C)]
+#[derive(ReadWriteState, CreateTypeSpec, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct AirdropAllowance {
+    pub address: Address,
+    pub campaign: String,
+    pub min_domain_length: u32,
+}
+
+/// Allowances of an address within a campaign
+#[repr(C)]
+#[derive(ReadWriteState, CreateTypeSpec, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct AirdropAllowanceKey {
+    pub address: Address,
+    pub campaign: String,
+}
+
+impl AirdropAllowance {
+    pub fn key(&self) -> AirdropAllowanceKey {
+        AirdropAllowanceKey {
+            address: self.address,
+            campaign: self.campaign.clone(),
+        }
+    }
+}
+
+impl AirdropCampaign {
+    /// Checks if the campaign can be used for the given mint
+    pub fn is_eligible(
+        &self,
+        domain_length: u32,
+        subscription_years: u32,
+        unix_millis_now: i64,
+    ) -> bool {
+        !self.revoked
+            && self.is_active(unix_millis_now)
+            && domain_length >= self.min_domain_length
+            && subscription_years <= self.max_subscription_years
+    }
+
+    /// Checks if the campaign is not expired
+    pub fn is_active(&self, unix_millis_now: i64) -> bool {
+        match self.expires_at {
+            Some(expires_at) => expires_at >= unix_millis_now,
+            None => true,
+        }
+    }
+}
+
+impl MerkleAirdrop {
+    pub fn new(merkle_root: &str, leaves_count: u32) -> Self {
+        Self {
+            merkle_root: merkle_root.to_string(),
+            leaves_count,
+            claimed: vec![0; (leaves_count as usize).div_ceil(8)],
+        }
+    }
+
+    /// Check if the leaf has already been claimed
+    pub fn is_claimed(&self, index: u32) -> bool {
+        let (byte, bit) = Self::bitmap_position(index);
+        self.claimed[byte] & bit != 0
+    }
+
+    /// Mark the leaf as claimed
+    pub fn _set_claimed(&mut self, index: u32) {
+        let (byte, bit) = Self::bitmap_position(index);
+        self.claimed[byte] |= bit;
+    }
+
+    fn bitmap_position(index: u32) -> (usize, u8) {
+        ((index / 8) as usize, 1 << (index % 8))
+    }
 }
 
 impl AirdropState {
@@ -14,6 +118,67 @@ impl AirdropState {
         self.inventory.contains_key(address)
     }
 
+    /// Check if the address has an allowance in the campaign
+    pub fn has_campaign_airdrop(&self, address: &Address, campaign: &str) -> bool {
+        self.allowances.contains_key(&AirdropAllowanceKey {
+            address: *address,
+            campaign: campaign.to_string(),
+        })
+    }
+
+    /// Returns the allowance of the best eligible campaign for the given mint
+    /// Campaigns expiring first are preferred, then the most restrictive ones,
+    /// so that the most flexible allowances are kept for later
+    pub fn get_best_allowance(
+        &self,
+        address: &Address,
+        domain_length: u32,
+        subscription_years: u32,
+        unix_millis_now: i64,
+    ) -> Option<AirdropAllowance> {
+        self.campaigns
+            .iter()
+            .filter(|(_, campaign)| {
+                campaign.is_eligible(domain_length, subscription_years, unix_millis_now)
+            })
+            .filter_map(|(name, campaign)| {
+                self.get_campaign_allowance(address, &name, domain_length)
+                    .map(|allowance| (allowance, campaign))
+            })
+            .min_by_key(|(allowance, campaign)| {
+                (
+                    campaign.expires_at.unwrap_or(i64::MAX),
+                    u32::MAX - allowance.min_domain_length.max(campaign.min_domain_length),
+                    campaign.max_subscription_years,
+                )
+            })
+            .map(|(allowance, _)| allowance)
+    }
+
+    /// Returns the most restrictive allowance of the campaign usable for the domain length
+    pub fn get_campaign_allowance(
+        &self,
+        address: &Address,
+        campaign: &str,
+        domain_length: u32,
+    ) -> Option<AirdropAllowance> {
+        let amounts = self.allowances.get(&AirdropAllowanceKey {
+            address: *address,
+            campaign: campaign.to_string(),
+        })?;
+
+        amounts
+            .iter()
+            .map(|(min_domain_length, _)| *min_domain_length)
+            .filter(|min_domain_length| *min_domain_length <= domain_length)
+            .max()
+            .map(|min_domain_length| AirdropAllowance {
+                address: *address,
+                campaign: campaign.to_string(),
+                min_domain_length,
+            })
+    }
+
     /// Use airdrop from the address
     pub fn _use_airdrop(&mut self, address: &Address) {
         if let Some(airdrop) = self.inventory.get(address) {
@@ -31,4 +196,54 @@ impl AirdropState {
         let airdrop = self.inventory.get(address).unwrap_or(0);
         self.inventory.insert(*address, airdrop + 1);
     }
+
+    /// Use a campaign allowance
+    pub fn _use_allowance(&mut self, allowance: &AirdropAllowance) {
+        let key = allowance.key();
+        if let Some(mut amounts) = self.allowances.get(&key) {
+            let remaining = amounts
+                .get(&allowance.min_domain_length)
+                .and_then(|airdrop| airdrop.checked_sub(1));
+            assert!(remaining.is_some(), "No airdrop for the address");
+
+            match remaining.unwrap() {
+                0 => {
+                    amounts.remove(&allowance.min_domain_length);
+                }
+                remaining => {
+                    amounts.insert(allowance.min_domain_length, remaining);
+                }
+            }
+
+            if amounts.is_empty() {
+                self.allowances.remove(&key);
+            } else {
+                self.allowances.insert(key, amounts);
+            }
+        }
+    }
+
+    /// Add campaign allowances
+    pub fn _add_allowance(&mut self, allowance: &AirdropAllowance, amount: u128) {
+        let key = allowance.key();
+        let mut amounts = self.allowances.get(&key).unwrap_or_default();
+        let airdrop = amounts
+            .get(&allowance.min_domain_length)
+            .copied()
+            .unwrap_or(0);
+        amounts.insert(allowance.min_domain_length, airdrop + amount);
+        self.allowances.insert(key, amounts);
+    }
+}
+
+/// Builds the merkle leaf of a claim
+/// The leaf is the utf8 string `{index}:{address_hex}:{amount}:{min_domain_length}`
+/// where `address_hex` is the hex encoded address type byte followed by the identifier
+pub fn merkle_leaf(index: u32, address: &Address, amount: u128, min_domain_length: u32) -> Vec<u8> {
+    let mut address_hex = format!("{:02x}", address.address_type as u8);
+    for byte in address.identifier {
+        address_hex.push_str(&format!("{:02x}", byte));
+    }
+
+    format!("{}:{}:{}:{}", index, address_hex, amount, min_domain_length).into_bytes()
 }
@@ -1,19 +1,27 @@
 // Setup tests
 
-use utils::tests::mock_address;
+use utils::tests::{mock_address, tomorrow_timestamp, unix_epoch_now, yesterday_timestamp};
 
-use crate::actions::{execute_add_airdrop, execute_airdrop, execute_init};
+use crate::{
+    actions::{
+        execute_add_airdrop, execute_add_campaign_airdrop, execute_add_merkle_airdrop,
+        execute_airdrop, execute_claim_merkle_airdrop, execute_create_campaign, execute_init,
+        execute_revoke_campaign,
+    },
+    msg::{AirdropCampaignMsg, AirdropMerkleClaimMsg},
+    state::AirdropState,
+};
 
 #[test]
 fn proper_has_airdrop() {
     let address = mock_address(0);
     let mut state = execute_init();
 
-    assert_eq!(state.has_airdrop(&address), false);
+    assert!(!state.has_airdrop(&address));
 
     execute_add_airdrop(&mut state, &address);
 
-    assert_eq!(state.has_airdrop(&address), true);
+    assert!(state.has_airdrop(&address));
 }
 
 #[test]
@@ -22,9 +30,9 @@ fn proper_execute_airdrop() {
     let mut state = execute_init();
 
     execute_add_airdrop(&mut state, &address);
-    execute_airdrop(&mut state, &address);
+    execute_airdrop(&mut state, &address, 5, 1, unix_epoch_now());
 
-    assert_eq!(state.has_airdrop(&address), false);
+    assert!(!state.has_airdrop(&address));
 }
 
 #[test]
@@ -35,7 +43,7 @@ fn proper_execute_add_airdrop() {
     execute_add_airdrop(&mut state, &address);
     execute_add_airdrop(&mut state, &address);
 
-    assert_eq!(state.has_airdrop(&address), true);
+    assert!(state.has_airdrop(&address));
     assert_eq!(state.inventory.get(&address).unwrap(), 2);
 }
 
@@ -45,5 +53,308 @@ fn proper_execute_airdrop_no_airdrop() {
     let address = mock_address(0);
     let mut state = execute_init();
 
-    execute_airdrop(&mut state, &address);
+    execute_airdrop(&mut state, &address, 5, 1, unix_epoch_now());
+}
+
+const MERKLE_ROOT: &str = "638905b99e916595bcd551c56bea0a59cdf7b831630e05b028af9744776521ef";
+const ALICE_PROOF: &str = "360af899bd822eba4926c93574adfae06abef9d6cb5e1155e154bc950e829c27";
+const BOB_PROOF: &str = "a90b31ae850fa740e290eade1d33e388d7e86bdeb9a43de090b1ef66e5de3d3e";
+
+fn campaign_msg(name: &str, min_domain_length: u32) -> AirdropCampaignMsg {
+    AirdropCampaignMsg {
+        name: name.to_string(),
+        expires_at: Some(tomorrow_timestamp()),
+        min_domain_length,
+        max_subscription_years: 1,
+    }
+}
+
+fn init_with_campaign(name: &str, min_domain_length: u32) -> AirdropState {
+    let mut state = execute_init();
+    execute_create_campaign(&mut state, &campaign_msg(name, min_domain_length));
+
+    state
+}
+
+// Leaves: (0, alice, 2, 5) and (1, bob, 1, 0)
+fn init_with_merkle_campaign() -> AirdropState {
+    let mut state = init_with_campaign("merkle", 0);
+    execute_add_merkle_airdrop(&mut state, "merkle", MERKLE_ROOT, 2);
+
+    state
+}
+
+fn alice_claim_msg() -> AirdropMerkleClaimMsg {
+    AirdropMerkleClaimMsg {
+        campaign: "merkle".to_string(),
+        index: 0,
+        amount: 2,
+        min_domain_length: 5,
+        proof: vec![ALICE_PROOF.to_string()],
+    }
+}
+
+#[test]
+fn proper_execute_claim_merkle_airdrop() {
+    let alice = mock_address(1);
+    let bob = mock_address(2);
+    let mut state = init_with_merkle_campaign();
+    execute_claim_merkle_airdrop(&mut state, &alice, &alice_claim_msg(), unix_epoch_now());
+    execute_claim_merkle_airdrop(
+        &mut state,
+        &bob,
+        &AirdropMerkleClaimMsg {
+            campaign: "merkle".to_string(),
+            index: 1,
+            amount: 1,
+            min_domain_length: 0,
+            proof: vec![BOB_PROOF.to_string()],
+        },
+        unix_epoch_now(),
+    );
+
+    let airdrop = state.merkle_airdrops.get(&"merkle".to_string()).unwrap();
+    assert!(airdrop.is_claimed(0));
+    assert!(airdrop.is_claimed(1));
+
+    assert!(state.has_campaign_airdrop(&alice, "merkle"));
+    assert!(state
+        .get_best_allowance(&alice, 4, 1, unix_epoch_now())
+        .is_none());
+    assert!(state
+        .get_best_allowance(&alice, 5, 1, unix_epoch_now())
+        .is_some());
+    assert!(state
+        .get_best_allowance(&bob, 1, 1, unix_epoch_now())
+        .is_some());
+}
+
+#[test]
+fn proper_execute_airdrop_with_allowance() {
+    let alice = mock_address(1);
+    let mut state = init_with_merkle_campaign();
+
+    execute_add_airdrop(&mut state, &alice);
+    execute_claim_merkle_airdrop(&mut state, &alice, &alice_claim_msg(), unix_epoch_now());
+
+    // Domains too short for the allowance fall back to the inventory
+    execute_airdrop(&mut state, &alice, 4, 1, unix_epoch_now());
+    assert!(!state.has_airdrop(&alice));
+
+    execute_airdrop(&mut state, &alice, 5, 1, unix_epoch_now());
+    execute_airdrop(&mut state, &alice, 10, 1, unix_epoch_now());
+    assert!(!state.has_campaign_airdrop(&alice, "merkle"));
+}
+
+#[test]
+#[should_panic(expected = "Merkle airdrop already claimed")]
+fn proper_execute_claim_merkle_airdrop_twice() {
+    let alice = mock_address(1);
+    let mut state = init_with_merkle_campaign();
+    execute_claim_merkle_airdrop(&mut state, &alice, &alice_claim_msg(), unix_epoch_now());
+    execute_claim_merkle_airdrop(&mut state, &alice, &alice_claim_msg(), unix_epoch_now());
+}
+
+#[test]
+#[should_panic(expected = "Invalid merkle airdrop amount")]
+fn proper_execute_claim_merkle_airdrop_zero_amount() {
+    let alice = mock_address(1);
+    let mut state = init_with_merkle_campaign();
+    let mut msg = alice_claim_msg();
+    msg.amount = 0;
+
+    execute_claim_merkle_airdrop(&mut state, &alice, &msg, unix_epoch_now());
+}
+
+#[test]
+fn proper_execute_airdrop_with_allowances_of_many_lengths() {
+    let alice = mock_address(1);
+    let mut state = init_with_merkle_campaign();
+    execute_add_campaign_airdrop(&mut state, "merkle", &alice);
+    execute_claim_merkle_airdrop(&mut state, &alice, &alice_claim_msg(), unix_epoch_now());
+
+    // The most restrictive allowance is used first
+    let allowance = state.get_campaign_allowance(&alice, "merkle", 10).unwrap();
+    assert_eq!(allowance.min_domain_length, 5);
+
+    execute_airdrop(&mut state, &alice, 10, 1, unix_epoch_now());
+    execute_airdrop(&mut state, &alice, 10, 1, unix_epoch_now());
+    let allowance = state.get_campaign_allowance(&alice, "merkle", 10).unwrap();
+    assert_eq!(allowance.min_domain_length, 0);
+
+    execute_airdrop(&mut state, &alice, 10, 1, unix_epoch_now());
+    assert!(!state.has_campaign_airdrop(&alice, "merkle"));
+}
+
+#[test]
+#[should_panic(expected = "Merkle verification failed")]
+fn proper_execute_claim_merkle_airdrop_wrong_address() {
+    let bob = mock_address(2);
+    let mut state = init_with_merkle_campaign();
+    execute_claim_merkle_airdrop(&mut state, &bob, &alice_claim_msg(), unix_epoch_now());
+}
+
+#[test]
+#[should_panic(expected = "Merkle verification failed")]
+fn proper_execute_claim_merkle_airdrop_wrong_amount() {
+    let alice = mock_address(1);
+    let mut state = init_with_merkle_campaign();
+    let mut msg = alice_claim_msg();
+    msg.amount = 10;
+
+    execute_claim_merkle_airdrop(&mut state, &alice, &msg, unix_epoch_now());
+}
+
+#[test]
+#[should_panic(expected = "Merkle airdrop not found")]
+fn proper_execute_claim_merkle_airdrop_not_found() {
+    let alice = mock_address(1);
+    let mut state = init_with_campaign("merkle", 0);
+
+    execute_claim_merkle_airdrop(&mut state, &alice, &alice_claim_msg(), unix_epoch_now());
+}
+
+#[test]
+#[should_panic(expected = "Merkle airdrop already exists")]
+fn proper_execute_add_merkle_airdrop_twice() {
+    let mut state = init_with_merkle_campaign();
+    execute_add_merkle_airdrop(&mut state, "merkle", MERKLE_ROOT, 2);
+}
+
+#[test]
+#[should_panic(expected = "No airdrop for the address")]
+fn proper_execute_airdrop_domain_too_short() {
+    let alice = mock_address(1);
+    let mut state = init_with_merkle_campaign();
+    execute_claim_merkle_airdrop(&mut state, &alice, &alice_claim_msg(), unix_epoch_now());
+
+    execute_airdrop(&mut state, &alice, 4, 1, unix_epoch_now());
+}
+
+#[test]
+#[should_panic(expected = "Airdrop campaign not found")]
+fn proper_execute_add_campaign_airdrop_campaign_not_found() {
+    let mut state = execute_init();
+
+    execute_add_campaign_airdrop(&mut state, "launch", &mock_address(0));
+}
+
+#[test]
+#[should_panic(expected = "Airdrop campaign already exists")]
+fn proper_execute_create_campaign_already_exists() {
+    let mut state = init_with_campaign("launch", 0);
+
+    execute_create_campaign(&mut state, &campaign_msg("launch", 5));
+}
+
+#[test]
+fn proper_execute_airdrop_picks_best_campaign() {
+    let address = mock_address(0);
+    let mut state = init_with_campaign("any", 0);
+    execute_create_campaign(&mut state, &campaign_msg("long", 6));
+    execute_create_campaign(
+        &mut state,
+        &AirdropCampaignMsg {
+            expires_at: None,
+            ..campaign_msg("permanent", 6)
+        },
+    );
+
+    for campaign in ["any", "long", "permanent"] {
+        execute_add_campaign_airdrop(&mut state, campaign, &address);
+    }
+    execute_add_airdrop(&mut state, &address);
+
+    let now = unix_epoch_now();
+    let used_campaign = execute_airdrop(&mut state, &address, 5, 1, now);
+    assert_eq!(used_campaign, Some("any".to_string()));
+    let used_campaign = execute_airdrop(&mut state, &address, 8, 1, now);
+    assert_eq!(used_campaign, Some("long".to_string()));
+    let used_campaign = execute_airdrop(&mut state, &address, 8, 1, now);
+    assert_eq!(used_campaign, Some("permanent".to_string()));
+
+    // The inventory is used once the campaign allowances are exhausted
+    assert_eq!(execute_airdrop(&mut state, &address, 8, 1, now), None);
+    assert!(!state.has_airdrop(&address));
+}
+
+#[test]
+fn proper_get_best_allowance_eligibility() {
+    let address = mock_address(0);
+    let mut state = init_with_campaign("launch", 5);
+    execute_add_campaign_airdrop(&mut state, "launch", &address);
+
+    let now = unix_epoch_now();
+    let allowance = state.get_best_allowance(&address, 5, 1, now);
+    assert_eq!(allowance.unwrap().campaign, "launch");
+
+    assert_eq!(state.get_best_allowance(&address, 4, 1, now), None);
+    assert_eq!(state.get_best_allowance(&address, 5, 2, now), None);
+    assert_eq!(state.get_best_allowance(&mock_address(1), 5, 1, now), None);
+}
+
+#[test]
+fn proper_get_best_allowance_expired() {
+    let address = mock_address(0);
+    let mut state = execute_init();
+    execute_create_campaign(
+        &mut state,
+        &AirdropCampaignMsg {
+            expires_at: Some(yesterday_timestamp()),
+            ..campaign_msg("launch", 0)
+        },
+    );
+    execute_add_campaign_airdrop(&mut state, "launch", &address);
+
+    assert_eq!(
+        state.get_best_allowance(&address, 5, 1, unix_epoch_now()),
+        None
+    );
+}
+
+#[test]
+fn proper_execute_revoke_campaign() {
+    let address = mock_address(0);
+    let mut state = init_with_campaign("launch", 0);
+    execute_add_campaign_airdrop(&mut state, "launch", &address);
+
+    execute_revoke_campaign(&mut state, "launch");
+
+    assert_eq!(
+        state.get_best_allowance(&address, 5, 1, unix_epoch_now()),
+        None
+    );
+}
+
+#[test]
+#[should_panic(expected = "Airdrop campaign is revoked")]
+fn proper_execute_add_campaign_airdrop_revoked_campaign() {
+    let mut state = init_with_campaign("launch", 0);
+    execute_revoke_campaign(&mut state, "launch");
+
+    execute_add_campaign_airdrop(&mut state, "launch", &mock_address(0));
+}
+
+#[test]
+#[should_panic(expected = "Airdrop campaign is revoked")]
+fn proper_execute_claim_merkle_airdrop_revoked_campaign() {
+    let alice = mock_address(1);
+    let mut state = init_with_merkle_campaign();
+    execute_revoke_campaign(&mut state, "merkle");
+
+    execute_claim_merkle_airdrop(&mut state, &alice, &alice_claim_msg(), unix_epoch_now());
+}
+
+#[test]
+#[should_panic(expected = "Airdrop campaign is expired")]
+fn proper_execute_claim_merkle_airdrop_expired_campaign() {
+    let alice = mock_address(1);
+    let mut state = init_with_merkle_campaign();
+
+    execute_claim_merkle_airdrop(
+        &mut state,
+        &alice,
+        &alice_claim_msg(),
+        tomorrow_timestamp() + 1,
+    );
 }
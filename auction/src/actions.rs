@@ -0,0 +1,184 @@
+use pbc_contract_common::{address::Address, avl_tree_map::AvlTreeMap};
+
+use crate::{
+    msg::{AuctionBidMsg, AuctionCreateMsg, AuctionRevealMsg},
+    state::{bid_commitment, Auction, AuctionBid, AuctionSettlement, AuctionState, Unit},
+};
+
+pub fn execute_init() -> AuctionState {
+    AuctionState {
+        auctions: AvlTreeMap::new(),
+        premium_domains: AvlTreeMap::new(),
+    }
+}
+
+/// Flags the domain as premium, or removes the flag
+pub fn execute_set_premium_domain(state: &mut AuctionState, domain: &str, premium: bool) {
+    if premium {
+        state.premium_domains.insert(domain.to_string(), Unit {});
+    } else {
+        state.premium_domains.remove(&domain.to_string());
+    }
+}
+
+/// Starts the auction of a premium domain
+pub fn execute_create_auction(
+    state: &mut AuctionState,
+    msg: &AuctionCreateMsg,
+    unix_millis_now: i64,
+) {
+    assert!(state.is_premium(&msg.domain), "Domain is not premium");
+
+    execute_open_auction(state, msg, unix_millis_now);
+}
+
+/// Starts the auction of a domain, premium or not
+pub fn execute_open_auction(
+    state: &mut AuctionState,
+    msg: &AuctionCreateMsg,
+    unix_millis_now: i64,
+) {
+    assert!(!state.is_in_auction(&msg.domain), "Auction already exists");
+    assert!(
+        msg.unrevealed_penalty_bps <= 10_000,
+        "Invalid unrevealed bid penalty"
+    );
+    assert!(
+        unix_millis_now < msg.commit_ends_at && msg.commit_ends_at < msg.reveal_ends_at,
+        "Invalid auction schedule"
+    );
+
+    state.auctions.insert(
+        msg.domain.clone(),
+        Auction {
+            payment_coin_id: msg.payment_coin_id,
+            reserve_price: msg.reserve_price,
+            subscription_years: msg.subscription_years,
+            unrevealed_penalty_bps: msg.unrevealed_penalty_bps,
+            commit_ends_at: msg.commit_ends_at,
+            reveal_ends_at: msg.reveal_ends_at,
+            bids: vec![],
+        },
+    );
+}
+
+/// Checks that the sealed bid can be committed
+pub fn validate_bid(state: &AuctionState, msg: &AuctionBidMsg, unix_millis_now: i64) -> Auction {
+    let auction = state.get_auction(&msg.domain);
+    assert!(auction.is_some(), "Auction not found");
+
+    let auction = auction.unwrap();
+    assert!(
+        auction.is_commit_open(unix_millis_now),
+        "Auction commit period is over"
+    );
+    assert!(
+        auction.get_bid(&msg.bidder).is_none(),
+        "Bid already committed"
+    );
+
+    let mut commitment_buf: [u8; 32] = [0; 32];
+    assert!(
+        hex::decode_to_slice(&msg.commitment, &mut commitment_buf).is_ok(),
+        "Invalid bid commitment"
+    );
+
+    auction
+}
+
+/// Stores the sealed bid before escrowing its deposit
+pub fn execute_commit_bid(state: &mut AuctionState, msg: &AuctionBidMsg, unix_millis_now: i64) {
+    let mut auction = validate_bid(state, msg, unix_millis_now);
+    assert!(
+        auction.payment_coin_id == msg.payment_coin_id,
+        "Invalid bid payment coin"
+    );
+
+    auction.bids.push(AuctionBid {
+        bidder: msg.bidder,
+        commitment: msg.commitment.clone(),
+        deposit: msg.deposit,
+        escrowed: false,
+        amount: None,
+    });
+    state.auctions.insert(msg.domain.clone(), auction);
+}
+
+/// Marks the deposit of the committed bid as escrowed
+/// Returns false when the bid is not pending anymore, the deposit must then be refunded
+pub fn execute_confirm_bid(state: &mut AuctionState, msg: &AuctionBidMsg) -> bool {
+    let Some(mut auction) = state.get_auction(&msg.domain) else {
+        return false;
+    };
+
+    let Some(bid) = auction
+        .bids
+        .iter_mut()
+        .find(|bid| bid.bidder == msg.bidder && !bid.escrowed)
+    else {
+        return false;
+    };
+
+    bid.escrowed = true;
+    state.auctions.insert(msg.domain.clone(), auction);
+
+    true
+}
+
+/// Removes the committed bid whose deposit could not be escrowed
+pub fn execute_cancel_bid(state: &mut AuctionState, msg: &AuctionBidMsg) {
+    if let Some(mut auction) = state.get_auction(&msg.domain) {
+        auction
+            .bids
+            .retain(|bid| bid.bidder != msg.bidder || bid.escrowed);
+        state.auctions.insert(msg.domain.clone(), auction);
+    }
+}
+
+pub fn execute_reveal_bid(
+    state: &mut AuctionState,
+    bidder: &Address,
+    msg: &AuctionRevealMsg,
+    unix_millis_now: i64,
+) {
+    let auction = state.get_auction(&msg.domain);
+    assert!(auction.is_some(), "Auction not found");
+
+    let mut auction = auction.unwrap();
+    assert!(
+        auction.is_reveal_open(unix_millis_now),
+        "Auction reveal period is not open"
+    );
+
+    let bid = auction.bids.iter_mut().find(|bid| bid.bidder == *bidder);
+    assert!(bid.is_some(), "Bid not found");
+
+    let bid = bid.unwrap();
+    assert!(bid.escrowed, "Bid deposit not escrowed");
+    assert!(bid.amount.is_none(), "Bid already revealed");
+    assert!(
+        bid.commitment == bid_commitment(bidder, msg.amount, &msg.salt),
+        "Bid commitment mismatch"
+    );
+    assert!(msg.amount <= bid.deposit, "Bid amount exceeds the deposit");
+
+    bid.amount = Some(msg.amount);
+    state.auctions.insert(msg.domain.clone(), auction);
+}
+
+/// Closes the auction and returns its outcome
+pub fn execute_settle_auction(
+    state: &mut AuctionState,
+    domain: &str,
+    unix_millis_now: i64,
+) -> AuctionSettlement {
+    let auction = state.get_auction(domain);
+    assert!(auction.is_some(), "Auction not found");
+
+    let auction = auction.unwrap();
+    assert!(auction.is_ended(unix_millis_now), "Auction is not ended");
+
+    state.auctions.remove(&domain.to_string());
+
+    auction.settle()
+}
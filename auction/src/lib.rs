@@ -0,0 +1,6 @@
+pub mod actions;
+pub mod msg;
+pub mod state;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,42 @@
+use create_type_spec_derive::CreateTypeSpec;
+use pbc_contract_common::address::Address;
+use read_write_rpc_derive::ReadWriteRPC;
+
+/// This structure describes fields for the auction creation msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct AuctionCreateMsg {
+    pub domain: String,
+    /// BYOC token id of the bids
+    pub payment_coin_id: u64,
+    /// Minimum price of the domain
+    pub reserve_price: u128,
+    /// Subscription years of the minted domain
+    pub subscription_years: u32,
+    /// Share of the deposit forfeited by the unrevealed bids, in basis points
+    pub unrevealed_penalty_bps: u32,
+    /// Unix millis timestamp
+    pub commit_ends_at: i64,
+    /// Unix millis timestamp
+    pub reveal_ends_at: i64,
+}
+
+/// This structure describes fields for the sealed bid commit msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct AuctionBidMsg {
+    pub domain: String,
+    pub bidder: Address,
+    /// BYOC token id of the deposit
+    pub payment_coin_id: u64,
+    /// Hex encoded commitment, see [`crate::state::bid_commitment`]
+    pub commitment: String,
+    /// Escrowed amount, must cover the revealed bid
+    pub deposit: u128,
+}
+
+/// This structure describes fields for the bid reveal msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct AuctionRevealMsg {
+    pub domain: String,
+    pub amount: u128,
+    pub salt: String,
+}
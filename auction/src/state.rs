@@ -0,0 +1,182 @@
+use std::cmp::Reverse;
+
+use create_type_spec_derive::CreateTypeSpec;
+use pbc_contract_common::{address::Address, avl_tree_map::AvlTreeMap};
+use read_write_state_derive::ReadWriteState;
+use sha3::Digest;
+
+#[repr(C)]
+#[derive(ReadWriteState, CreateTypeSpec, Default, Debug)]
+pub struct AuctionState {
+    /// Auctions of the premium domains, by domain
+    pub auctions: AvlTreeMap<String, Auction>,
+    /// Domains that can only be minted through an auction
+    pub premium_domains: AvlTreeMap<String, Unit>,
+}
+
+/// Empty value of the premium domains set, only the keys are meaningful
+#[repr(C)]
+#[derive(ReadWriteState, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct Unit {}
+
+/// Sealed-bid second price auction of a premium domain
+#[repr(C)]
+#[derive(ReadWriteState, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct Auction {
+    pub payment_coin_id: u64,
+    pub reserve_price: u128,
+    pub subscription_years: u32,
+    /// Share of the deposit forfeited by the unrevealed bids, in basis points
+    pub unrevealed_penalty_bps: u32,
+    /// Unix millis timestamp
+    pub commit_ends_at: i64,
+    /// Unix millis timestamp
+    pub reveal_ends_at: i64,
+    pub bids: Vec<AuctionBid>,
+}
+
+#[repr(C)]
+#[derive(ReadWriteState, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct AuctionBid {
+    pub bidder: Address,
+    pub commitment: String,
+    pub deposit: u128,
+    /// Set once the deposit is transferred to the contract
+    pub escrowed: bool,
+    /// Bid amount, set once revealed
+    pub amount: Option<u128>,
+}
+
+/// Outcome of a settled auction
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AuctionSettlement {
+    pub auction: Auction,
+    pub winner: Option<Address>,
+    /// Price paid by the winner, the second highest bid or the reserve price
+    pub price: u128,
+    /// Penalties of the unrevealed bids, paid along with the price
+    pub forfeited: u128,
+    /// Escrowed deposits to give back
+    pub refunds: Vec<AuctionRefund>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AuctionRefund {
+    pub to: Address,
+    pub amount: u128,
+}
+
+impl Auction {
+    pub fn is_commit_open(&self, unix_millis_now: i64) -> bool {
+        unix_millis_now < self.commit_ends_at
+    }
+
+    pub fn is_reveal_open(&self, unix_millis_now: i64) -> bool {
+        !self.is_commit_open(unix_millis_now) && unix_millis_now < self.reveal_ends_at
+    }
+
+    pub fn is_ended(&self, unix_millis_now: i64) -> bool {
+        unix_millis_now >= self.reveal_ends_at
+    }
+
+    pub fn get_bid(&self, bidder: &Address) -> Option<&AuctionBid> {
+        self.bids.iter().find(|bid| bid.bidder == *bidder)
+    }
+
+    /// Returns the penalty of an unrevealed bid
+    pub fn get_unrevealed_penalty(&self, deposit: u128) -> u128 {
+        deposit * self.unrevealed_penalty_bps as u128 / 10_000
+    }
+
+    /// Computes the winner and the price of the auction
+    /// Only the bids with an escrowed deposit are taken into account
+    /// The highest revealed bid at or above the reserve price wins, the earliest one on ties
+    /// The winner pays the second highest valid bid, or the reserve price if there is none
+    /// The unrevealed bids forfeit a share of their deposit
+    pub fn settle(&self) -> AuctionSettlement {
+        let mut valid_bids: Vec<&AuctionBid> = self
+            .bids
+            .iter()
+            .filter(|bid| {
+                bid.escrowed
+                    && bid
+                        .amount
+                        .is_some_and(|amount| amount >= self.reserve_price)
+            })
+            .collect();
+        // Stable sort keeps the earliest bid first on ties
+        valid_bids.sort_by_key(|bid| Reverse(bid.amount));
+
+        let winner = valid_bids.first().map(|bid| bid.bidder);
+        let price = match valid_bids.get(1) {
+            Some(second_bid) => second_bid.amount.unwrap(),
+            None => self.reserve_price,
+        };
+
+        let refunds = self
+            .bids
+            .iter()
+            .filter(|bid| bid.escrowed)
+            .map(|bid| {
+                let amount = if Some(bid.bidder) == winner {
+                    bid.deposit - price
+                } else if bid.amount.is_none() {
+                    bid.deposit - self.get_unrevealed_penalty(bid.deposit)
+                } else {
+                    bid.deposit
+                };
+
+                AuctionRefund {
+                    to: bid.bidder,
+                    amount,
+                }
+            })
+            .filter(|refund| refund.amount > 0)
+            .collect();
+
+        let forfeited = self
+            .bids
+            .iter()
+            .filter(|bid| bid.escrowed && bid.amount.is_none())
+            .map(|bid| self.get_unrevealed_penalty(bid.deposit))
+            .sum();
+
+        AuctionSettlement {
+            auction: self.clone(),
+            winner,
+            price: if winner.is_some() { price } else { 0 },
+            forfeited,
+            refunds,
+        }
+    }
+}
+
+impl AuctionState {
+    /// Check if the domain is reserved by an auction
+    pub fn is_in_auction(&self, domain: &str) -> bool {
+        self.auctions.contains_key(&domain.to_string())
+    }
+
+    pub fn get_auction(&self, domain: &str) -> Option<Auction> {
+        self.auctions.get(&domain.to_string())
+    }
+
+    /// Check if the domain can only be minted through an auction
+    pub fn is_premium(&self, domain: &str) -> bool {
+        self.premium_domains.contains_key(&domain.to_string())
+    }
+}
+
+/// Builds the commitment of a sealed bid
+/// The commitment is the hex encoded keccak256 hash of the utf8 string `{bidder_hex}:{amount}:{salt}`
+/// where `bidder_hex` is the hex encoded address type byte followed by the identifier
+pub fn bid_commitment(bidder: &Address, amount: u128, salt: &str) -> String {
+    let mut bidder_hex = format!("{:02x}", bidder.address_type as u8);
+    for byte in bidder.identifier {
+        bidder_hex.push_str(&format!("{:02x}", byte));
+    }
+
+    let preimage = format!("{}:{}:{}", bidder_hex, amount, salt);
+
+    hex::encode(sha3::Keccak256::digest(preimage.as_bytes()))
+}
@@ -0,0 +1,350 @@
+// Setup tests
+
+use utils::tests::mock_address;
+
+use crate::{
+    actions::{
+        execute_cancel_bid, execute_commit_bid, execute_confirm_bid, execute_create_auction,
+        execute_init, execute_open_auction, execute_reveal_bid, execute_set_premium_domain,
+        execute_settle_auction,
+    },
+    msg::{AuctionBidMsg, AuctionCreateMsg, AuctionRevealMsg},
+    state::{bid_commitment, AuctionRefund, AuctionState},
+};
+
+const DOMAIN: &str = "mpc";
+const COMMIT_ENDS_AT: i64 = 100;
+const REVEAL_ENDS_AT: i64 = 200;
+
+fn init_with_auction(reserve_price: u128) -> AuctionState {
+    let mut state = execute_init();
+    execute_set_premium_domain(&mut state, DOMAIN, true);
+    execute_create_auction(
+        &mut state,
+        &AuctionCreateMsg {
+            domain: DOMAIN.to_string(),
+            payment_coin_id: 0,
+            reserve_price,
+            subscription_years: 1,
+            unrevealed_penalty_bps: 1_000,
+            commit_ends_at: COMMIT_ENDS_AT,
+            reveal_ends_at: REVEAL_ENDS_AT,
+        },
+        0,
+    );
+
+    state
+}
+
+fn bid_msg(bidder: u8, amount: u128, deposit: u128) -> AuctionBidMsg {
+    let bidder = mock_address(bidder);
+
+    AuctionBidMsg {
+        domain: DOMAIN.to_string(),
+        bidder,
+        payment_coin_id: 0,
+        commitment: bid_commitment(&bidder, amount, "salt"),
+        deposit,
+    }
+}
+
+fn commit(state: &mut AuctionState, bidder: u8, amount: u128, deposit: u128) {
+    let msg = bid_msg(bidder, amount, deposit);
+    execute_commit_bid(state, &msg, 0);
+    assert!(execute_confirm_bid(state, &msg));
+}
+
+fn reveal(state: &mut AuctionState, bidder: u8, amount: u128, salt: &str) {
+    execute_reveal_bid(
+        state,
+        &mock_address(bidder),
+        &AuctionRevealMsg {
+            domain: DOMAIN.to_string(),
+            amount,
+            salt: salt.to_string(),
+        },
+        COMMIT_ENDS_AT,
+    );
+}
+
+#[test]
+fn proper_execute_create_auction() {
+    let state = init_with_auction(10);
+
+    assert!(state.is_in_auction(DOMAIN));
+    assert!(!state.is_in_auction("name"));
+}
+
+#[test]
+#[should_panic(expected = "Auction already exists")]
+fn proper_execute_create_auction_already_exists() {
+    let mut state = init_with_auction(10);
+
+    execute_create_auction(
+        &mut state,
+        &AuctionCreateMsg {
+            domain: DOMAIN.to_string(),
+            payment_coin_id: 0,
+            reserve_price: 10,
+            subscription_years: 1,
+            unrevealed_penalty_bps: 1_000,
+            commit_ends_at: COMMIT_ENDS_AT,
+            reveal_ends_at: REVEAL_ENDS_AT,
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Domain is not premium")]
+fn proper_execute_create_auction_not_premium() {
+    let mut state = init_with_auction(10);
+    execute_set_premium_domain(&mut state, "name", false);
+
+    execute_create_auction(
+        &mut state,
+        &AuctionCreateMsg {
+            domain: "name".to_string(),
+            payment_coin_id: 0,
+            reserve_price: 10,
+            subscription_years: 1,
+            unrevealed_penalty_bps: 1_000,
+            commit_ends_at: COMMIT_ENDS_AT,
+            reveal_ends_at: REVEAL_ENDS_AT,
+        },
+        0,
+    );
+}
+
+#[test]
+fn proper_execute_open_auction_not_premium() {
+    let mut state = execute_init();
+
+    execute_open_auction(
+        &mut state,
+        &AuctionCreateMsg {
+            domain: "name".to_string(),
+            payment_coin_id: 0,
+            reserve_price: 10,
+            subscription_years: 1,
+            unrevealed_penalty_bps: 1_000,
+            commit_ends_at: COMMIT_ENDS_AT,
+            reveal_ends_at: REVEAL_ENDS_AT,
+        },
+        0,
+    );
+
+    assert!(state.is_in_auction("name"));
+    assert!(!state.is_premium("name"));
+}
+
+#[test]
+#[should_panic(expected = "Invalid auction schedule")]
+fn proper_execute_create_auction_invalid_schedule() {
+    let mut state = execute_init();
+    execute_set_premium_domain(&mut state, DOMAIN, true);
+
+    execute_create_auction(
+        &mut state,
+        &AuctionCreateMsg {
+            domain: DOMAIN.to_string(),
+            payment_coin_id: 0,
+            reserve_price: 10,
+            subscription_years: 1,
+            unrevealed_penalty_bps: 1_000,
+            commit_ends_at: REVEAL_ENDS_AT,
+            reveal_ends_at: COMMIT_ENDS_AT,
+        },
+        0,
+    );
+}
+
+#[test]
+fn proper_execute_settle_auction_second_price() {
+    let mut state = init_with_auction(10);
+    commit(&mut state, 1, 50, 60);
+    commit(&mut state, 2, 30, 30);
+    commit(&mut state, 3, 20, 40);
+
+    reveal(&mut state, 1, 50, "salt");
+    reveal(&mut state, 2, 30, "salt");
+    reveal(&mut state, 3, 20, "salt");
+
+    let settlement = execute_settle_auction(&mut state, DOMAIN, REVEAL_ENDS_AT);
+
+    assert_eq!(settlement.winner, Some(mock_address(1)));
+    assert_eq!(settlement.price, 30);
+    assert_eq!(settlement.forfeited, 0);
+    assert_eq!(
+        settlement.refunds,
+        vec![
+            AuctionRefund {
+                to: mock_address(1),
+                amount: 30,
+            },
+            AuctionRefund {
+                to: mock_address(2),
+                amount: 30,
+            },
+            AuctionRefund {
+                to: mock_address(3),
+                amount: 40,
+            },
+        ]
+    );
+    assert!(!state.is_in_auction(DOMAIN));
+}
+
+#[test]
+fn proper_execute_settle_auction_reserve_price() {
+    let mut state = init_with_auction(10);
+    commit(&mut state, 1, 50, 50);
+    commit(&mut state, 2, 5, 5);
+
+    reveal(&mut state, 1, 50, "salt");
+    reveal(&mut state, 2, 5, "salt");
+
+    let settlement = execute_settle_auction(&mut state, DOMAIN, REVEAL_ENDS_AT);
+
+    assert_eq!(settlement.winner, Some(mock_address(1)));
+    assert_eq!(settlement.price, 10);
+}
+
+#[test]
+fn proper_execute_settle_auction_tie() {
+    let mut state = init_with_auction(10);
+    commit(&mut state, 2, 50, 50);
+    commit(&mut state, 1, 50, 50);
+
+    reveal(&mut state, 1, 50, "salt");
+    reveal(&mut state, 2, 50, "salt");
+
+    let settlement = execute_settle_auction(&mut state, DOMAIN, REVEAL_ENDS_AT);
+
+    assert_eq!(settlement.winner, Some(mock_address(2)));
+    assert_eq!(settlement.price, 50);
+}
+
+#[test]
+fn proper_execute_settle_auction_unrevealed_bids() {
+    let mut state = init_with_auction(10);
+    commit(&mut state, 1, 50, 50);
+
+    let settlement = execute_settle_auction(&mut state, DOMAIN, REVEAL_ENDS_AT);
+
+    assert_eq!(settlement.winner, None);
+    assert_eq!(settlement.price, 0);
+    assert_eq!(settlement.forfeited, 5);
+    assert_eq!(
+        settlement.refunds,
+        vec![AuctionRefund {
+            to: mock_address(1),
+            amount: 45,
+        }]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Auction commit period is over")]
+fn proper_execute_commit_bid_after_commit_period() {
+    let mut state = init_with_auction(10);
+    let bidder = mock_address(1);
+
+    execute_commit_bid(
+        &mut state,
+        &AuctionBidMsg {
+            domain: DOMAIN.to_string(),
+            bidder,
+            payment_coin_id: 0,
+            commitment: bid_commitment(&bidder, 50, "salt"),
+            deposit: 50,
+        },
+        COMMIT_ENDS_AT,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Bid already committed")]
+fn proper_execute_commit_bid_twice() {
+    let mut state = init_with_auction(10);
+
+    commit(&mut state, 1, 50, 50);
+    commit(&mut state, 1, 60, 60);
+}
+
+#[test]
+#[should_panic(expected = "Bid commitment mismatch")]
+fn proper_execute_reveal_bid_wrong_salt() {
+    let mut state = init_with_auction(10);
+    commit(&mut state, 1, 50, 50);
+
+    reveal(&mut state, 1, 50, "wrong");
+}
+
+#[test]
+#[should_panic(expected = "Bid amount exceeds the deposit")]
+fn proper_execute_reveal_bid_exceeds_deposit() {
+    let mut state = init_with_auction(10);
+    commit(&mut state, 1, 50, 40);
+
+    reveal(&mut state, 1, 50, "salt");
+}
+
+#[test]
+#[should_panic(expected = "Auction reveal period is not open")]
+fn proper_execute_reveal_bid_during_commit_period() {
+    let mut state = init_with_auction(10);
+    commit(&mut state, 1, 50, 50);
+
+    execute_reveal_bid(
+        &mut state,
+        &mock_address(1),
+        &AuctionRevealMsg {
+            domain: DOMAIN.to_string(),
+            amount: 50,
+            salt: "salt".to_string(),
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Auction is not ended")]
+fn proper_execute_settle_auction_not_ended() {
+    let mut state = init_with_auction(10);
+
+    execute_settle_auction(&mut state, DOMAIN, COMMIT_ENDS_AT);
+}
+
+#[test]
+fn proper_execute_cancel_bid() {
+    let mut state = init_with_auction(10);
+    let msg = bid_msg(1, 50, 50);
+    execute_commit_bid(&mut state, &msg, 0);
+
+    execute_cancel_bid(&mut state, &msg);
+
+    let auction = state.get_auction(DOMAIN).unwrap();
+    assert!(auction.get_bid(&mock_address(1)).is_none());
+}
+
+#[test]
+fn proper_execute_confirm_bid_after_settlement() {
+    let mut state = init_with_auction(10);
+    let msg = bid_msg(1, 50, 50);
+    execute_commit_bid(&mut state, &msg, 0);
+
+    let settlement = execute_settle_auction(&mut state, DOMAIN, REVEAL_ENDS_AT);
+
+    assert_eq!(settlement.refunds, vec![]);
+    assert!(!execute_confirm_bid(&mut state, &msg));
+}
+
+#[test]
+#[should_panic(expected = "Bid deposit not escrowed")]
+fn proper_execute_reveal_bid_not_escrowed() {
+    let mut state = init_with_auction(10);
+    execute_commit_bid(&mut state, &bid_msg(1, 50, 50), 0);
+
+    reveal(&mut state, 1, 50, "salt");
+}
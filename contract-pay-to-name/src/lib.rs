@@ -0,0 +1,145 @@
+#![doc = include_str!("../README.md")]
+#![allow(unused_variables)]
+
+#[macro_use]
+extern crate pbc_contract_codegen;
+extern crate contract_version_base;
+extern crate pbc_contract_common;
+
+use contract_version_base::state::ContractVersionBase;
+use create_type_spec_derive::CreateTypeSpec;
+use partisia_name_system::state::RecordClass;
+use pbc_contract_common::address::{Address, Shortname};
+use pbc_contract_common::avl_tree_map::AvlTreeMap;
+use pbc_contract_common::context::ContractContext;
+use pbc_contract_common::events::EventGroup;
+use read_write_rpc_derive::ReadWriteRPC;
+use read_write_state_derive::ReadWriteState;
+use resolver_interface::{ResolveRequest, ResolveResponse};
+use rpc_msg_derive::IntoShortnameRPCEvent;
+use utils::events::IntoShortnameRPCEvent;
+
+const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const ON_NAME_RESOLVED_SHORTNAME: u32 = 0x02;
+
+/// The state of the contract, which is persisted on-chain.
+#[state]
+pub struct PayToNameState {
+    /// Meta Names contract resolving the domains.
+    pub meta_names: Address,
+    /// MPC20 token of the payments.
+    pub token: Address,
+    pub next_request_id: u64,
+    /// Payments waiting for the domain resolution, by request id.
+    pub pending_payments: AvlTreeMap<u64, PendingPayment>,
+    pub version: ContractVersionBase,
+}
+
+#[derive(ReadWriteState, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct PendingPayment {
+    pub payer: Address,
+    pub domain: String,
+    pub amount: u128,
+}
+
+#[derive(ReadWriteRPC, CreateTypeSpec, IntoShortnameRPCEvent, Clone, PartialEq, Eq, Debug)]
+#[rpc_msg(action = 0x03)]
+pub struct MPC20TransferFromMsg {
+    pub from: Address,
+    pub to: Address,
+    pub amount: u128,
+}
+
+/// Initialize a new pay to name contract.
+///
+/// # Arguments
+///
+/// * `_ctx` - the contract context containing information about the sender and the blockchain.
+/// * `meta_names` - the address of the Meta Names contract.
+/// * `token` - the address of the MPC20 token of the payments.
+///
+/// # Returns
+///
+/// The initial state of the contract.
+#[init]
+pub fn initialize(ctx: ContractContext, meta_names: Address, token: Address) -> PayToNameState {
+    PayToNameState {
+        meta_names,
+        token,
+        next_request_id: 0,
+        pending_payments: AvlTreeMap::new(),
+        version: ContractVersionBase::new(CONTRACT_NAME, CONTRACT_VERSION),
+    }
+}
+
+/// Pay the amount to the Wallet record of the domain.
+/// The payment is pending until Meta Names resolves the domain.
+#[action(shortname = 0x01)]
+pub fn pay_to_name(
+    ctx: ContractContext,
+    mut state: PayToNameState,
+    domain: String,
+    amount: u128,
+) -> (PayToNameState, Vec<EventGroup>) {
+    assert!(amount > 0, "The amount must be positive");
+
+    let request_id = state.next_request_id;
+    state.next_request_id += 1;
+    state.pending_payments.insert(
+        request_id,
+        PendingPayment {
+            payer: ctx.sender,
+            domain: domain.clone(),
+            amount,
+        },
+    );
+
+    let mut event_builder = EventGroup::builder();
+    ResolveRequest {
+        domain,
+        class: RecordClass::Wallet {},
+        callback_shortname: ON_NAME_RESOLVED_SHORTNAME,
+        request_id,
+    }
+    .as_interaction(&mut event_builder, &state.meta_names);
+
+    (state, vec![event_builder.build()])
+}
+
+/// Receive the resolution of the domain from Meta Names.
+/// The tokens are transferred to the resolved wallet, the payment is dropped otherwise.
+#[action(shortname = 0x02)]
+pub fn on_name_resolved(
+    ctx: ContractContext,
+    mut state: PayToNameState,
+    response: ResolveResponse,
+) -> (PayToNameState, Vec<EventGroup>) {
+    assert_eq!(
+        ctx.sender, state.meta_names,
+        "Only Meta Names can resolve the payments"
+    );
+
+    let payment = state.pending_payments.get(&response.request_id);
+    assert!(payment.is_some(), "The payment is not pending");
+    state.pending_payments.remove(&response.request_id);
+
+    let payment = payment.unwrap();
+    let mut events = vec![];
+    if let Some(wallet) = response.wallet_address() {
+        let mut event_builder = EventGroup::builder();
+        MPC20TransferFromMsg {
+            from: payment.payer,
+            to: wallet,
+            amount: payment.amount,
+        }
+        .as_interaction(&mut event_builder, &state.token);
+        events.push(event_builder.build());
+    }
+
+    (state, events)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,162 @@
+use partisia_name_system::state::{RecordClass, MAX_ADDRESS_TYPE};
+use pbc_contract_common::events::EventGroup;
+use resolver_interface::{ResolveRequest, ResolveResponse};
+use utils::{
+    events::IntoShortnameRPCEvent,
+    tests::{mock_address, mock_contract_context, ALICE_ADDRESS, BOB_ADDRESS},
+};
+
+use crate::{
+    initialize, on_name_resolved, pay_to_name, MPC20TransferFromMsg, PayToNameState,
+    PendingPayment, ON_NAME_RESOLVED_SHORTNAME,
+};
+
+const META_NAMES_ADDRESS: u8 = 20;
+const TOKEN_ADDRESS: u8 = 21;
+
+fn init_state() -> PayToNameState {
+    initialize(
+        mock_contract_context(ALICE_ADDRESS),
+        mock_address(META_NAMES_ADDRESS),
+        mock_address(TOKEN_ADDRESS),
+    )
+}
+
+fn wallet_response(request_id: u64, data: &str, active: bool) -> ResolveResponse {
+    ResolveResponse {
+        request_id,
+        domain: "bob.mpc".to_string(),
+        owner: Some(mock_address(BOB_ADDRESS)),
+        active,
+        data: Some(data.as_bytes().to_vec()),
+    }
+}
+
+#[test]
+fn proper_pay_to_name() {
+    let (state, events) = pay_to_name(
+        mock_contract_context(ALICE_ADDRESS),
+        init_state(),
+        "bob.mpc".to_string(),
+        100,
+    );
+
+    assert_eq!(state.next_request_id, 1);
+    assert_eq!(
+        state.pending_payments.get(&0),
+        Some(PendingPayment {
+            payer: mock_address(ALICE_ADDRESS),
+            domain: "bob.mpc".to_string(),
+            amount: 100,
+        })
+    );
+
+    let mut event_builder = EventGroup::builder();
+    ResolveRequest {
+        domain: "bob.mpc".to_string(),
+        class: RecordClass::Wallet {},
+        callback_shortname: ON_NAME_RESOLVED_SHORTNAME,
+        request_id: 0,
+    }
+    .as_interaction(&mut event_builder, &mock_address(META_NAMES_ADDRESS));
+    assert_eq!(events, vec![event_builder.build()]);
+}
+
+#[test]
+#[should_panic(expected = "The amount must be positive")]
+fn when_amount_is_zero_pay_to_name_fails() {
+    pay_to_name(
+        mock_contract_context(ALICE_ADDRESS),
+        init_state(),
+        "bob.mpc".to_string(),
+        0,
+    );
+}
+
+#[test]
+fn proper_name_resolved_pays_the_wallet() {
+    let (state, _) = pay_to_name(
+        mock_contract_context(ALICE_ADDRESS),
+        init_state(),
+        "bob.mpc".to_string(),
+        100,
+    );
+
+    let (state, events) = on_name_resolved(
+        mock_contract_context(META_NAMES_ADDRESS),
+        state,
+        wallet_response(0, "0x000900000000000000000000000000000000000000", true),
+    );
+
+    assert!(state.pending_payments.get(&0).is_none());
+
+    let mut event_builder = EventGroup::builder();
+    MPC20TransferFromMsg {
+        from: mock_address(ALICE_ADDRESS),
+        to: mock_address(9),
+        amount: 100,
+    }
+    .as_interaction(&mut event_builder, &mock_address(TOKEN_ADDRESS));
+    assert_eq!(events, vec![event_builder.build()]);
+}
+
+#[test]
+fn proper_name_not_resolved_drops_the_payment() {
+    let unresolved_responses = [
+        wallet_response(0, "0x000900000000000000000000000000000000000000", false),
+        wallet_response(
+            0,
+            &format!(
+                "{:02x}0900000000000000000000000000000000000000",
+                MAX_ADDRESS_TYPE + 1
+            ),
+            true,
+        ),
+        ResolveResponse {
+            data: None,
+            ..wallet_response(0, "", true)
+        },
+    ];
+
+    for response in unresolved_responses {
+        let (state, _) = pay_to_name(
+            mock_contract_context(ALICE_ADDRESS),
+            init_state(),
+            "bob.mpc".to_string(),
+            100,
+        );
+
+        let (state, events) =
+            on_name_resolved(mock_contract_context(META_NAMES_ADDRESS), state, response);
+
+        assert!(state.pending_payments.get(&0).is_none());
+        assert!(events.is_empty());
+    }
+}
+
+#[test]
+#[should_panic(expected = "Only Meta Names can resolve the payments")]
+fn when_sender_is_not_meta_names_name_resolved_fails() {
+    let (state, _) = pay_to_name(
+        mock_contract_context(ALICE_ADDRESS),
+        init_state(),
+        "bob.mpc".to_string(),
+        100,
+    );
+
+    on_name_resolved(
+        mock_contract_context(BOB_ADDRESS),
+        state,
+        wallet_response(0, "0x000200000000000000000000000000000000000000", true),
+    );
+}
+
+#[test]
+#[should_panic(expected = "The payment is not pending")]
+fn when_payment_is_not_pending_name_resolved_fails() {
+    on_name_resolved(
+        mock_contract_context(META_NAMES_ADDRESS),
+        init_state(),
+        wallet_response(0, "0x000900000000000000000000000000000000000000", true),
+    );
+}
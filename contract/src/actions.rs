@@ -1,12 +1,20 @@
 use crate::{
-    msg::{MPC20TransferFromMsg, MintMsg, RenewDomainMsg},
+    msg::{
+        BuySubdomainMsg, MPC20TransferFromMsg, MPC20TransferMsg, MintMsg, RenewDomainMsg,
+        SetStorageQuotaMsg, UpgradePremiumTierMsg,
+    },
     state::ContractState,
     ContractError,
 };
+use auction::{
+    msg::AuctionBidMsg,
+    state::{AuctionRefund, AuctionSettlement},
+};
 use nft::{actions as nft_actions, msg as nft_msg};
 use partisia_name_system::{
     actions::{self as pns_actions, execute_update_expiration},
     msg::{self as pns_msg, PnsDomainUpdateExpirationMsg},
+    state::CANNOT_TRANSFER,
 };
 use pbc_contract_common::{
     address::Address,
@@ -26,9 +34,10 @@ pub struct PaymentIntent {
 }
 
 /// Action to mint contract
+/// The caller is responsible for checking the access to the parent domain
 pub fn action_mint(
     ctx: &ContractContext,
-    mut state: ContractState,
+    state: ContractState,
     domain: &str,
     to: &Address,
     token_uri: &Option<String>,
@@ -36,36 +45,114 @@ pub fn action_mint(
     subscription_years: &Option<u32>,
 ) -> (ContractState, Vec<EventGroup>) {
     assert!(!state.pns.is_minted(domain), "{}", ContractError::Minted);
+    assert!(
+        !state.auction.is_in_auction(domain),
+        "{}",
+        ContractError::DomainInAuction
+    );
+    assert!(
+        !state.auction.is_premium(domain),
+        "{}",
+        ContractError::DomainIsPremium
+    );
 
-    pns_actions::validate_domain(domain);
+    pns_actions::validate_domain(&state.pns.config, domain);
+
+    if parent_id.is_none() {
+        let chars_count = domain.chars().count() as u32;
+        let launch_phase = state.config.get_launch_phase(ctx.block_production_time);
+        assert!(
+            state.is_supply_available(chars_count, launch_phase),
+            "{}",
+            ContractError::SupplyCapReached
+        );
+    }
 
     let mut expires_at: Option<i64> = None;
 
     // Parent validations
     if let Some(parent_id) = parent_id.clone() {
-        let parent = state.pns.get_domain(&parent_id);
-        assert!(parent.is_some(), "{}", ContractError::DomainNotMinted);
-
-        let parent = parent.unwrap();
         assert!(
-            parent.is_active(ctx.block_production_time),
+            state.pns.is_minted(&parent_id),
+            "{}",
+            ContractError::DomainNotMinted
+        );
+        assert!(
+            state.pns.is_active(&parent_id, ctx.block_production_time),
             "{}",
             ContractError::DomainNotActive
         );
 
         pns_actions::validate_domain_with_parent(domain, &parent_id);
 
-        let parent_token_id = parent.token_id;
+        let config = &state.config;
         assert!(
-            state.nft.is_approved_or_owner(ctx.sender, parent_token_id),
+            config.max_subdomain_depth == 0
+                || state.pns.get_depth(&parent_id) < config.max_subdomain_depth,
             "{}",
-            ContractError::Unauthorized
+            ContractError::MaxSubdomainDepthReached
+        );
+        assert!(
+            config.max_subdomains_per_domain == 0
+                || (state.pns.get_children(&parent_id).len() as u32)
+                    < config.max_subdomains_per_domain,
+            "{}",
+            ContractError::MaxSubdomainsReached
         );
     } else if let Some(years_active) = subscription_years {
         let date = ctx.block_production_time + milliseconds_in_years(*years_active as i64);
         expires_at = Some(date);
     }
 
+    execute_domain_mint(
+        ctx,
+        state,
+        domain,
+        to,
+        token_uri,
+        parent_id,
+        expires_at,
+        &ctx.sender,
+    )
+}
+
+/// Mints the domain won in an auction to the winner
+/// The supply caps are not checked, the domain was reserved by the auction
+pub fn action_mint_auction_domain(
+    ctx: &ContractContext,
+    state: ContractState,
+    domain: &str,
+    winner: &Address,
+    subscription_years: u32,
+) -> (ContractState, Vec<EventGroup>) {
+    assert!(!state.pns.is_minted(domain), "{}", ContractError::Minted);
+
+    let expires_at = ctx.block_production_time + milliseconds_in_years(subscription_years as i64);
+
+    execute_domain_mint(
+        ctx,
+        state,
+        domain,
+        winner,
+        &None,
+        &None,
+        Some(expires_at),
+        winner,
+    )
+}
+
+/// Mints the domain token and counts the mint for the minter
+#[allow(clippy::too_many_arguments)]
+fn execute_domain_mint(
+    ctx: &ContractContext,
+    mut state: ContractState,
+    domain: &str,
+    to: &Address,
+    token_uri: &Option<String>,
+    parent_id: &Option<String>,
+    expires_at: Option<i64>,
+    minter: &Address,
+) -> (ContractState, Vec<EventGroup>) {
     let token_id = state.nft.get_next_token_id();
     let nft_events = nft_actions::execute_mint(
         ctx,
@@ -88,13 +175,120 @@ pub fn action_mint(
         },
     );
 
-    state.stats.increase_mint_count(ctx.sender);
+    let (_, mint_count_window) = state.get_mint_count_limit(minter);
+    state
+        .stats
+        .increase_mint_count(*minter, mint_count_window, ctx.block_production_time);
+
+    if parent_id.is_none() {
+        let chars_count = domain.chars().count() as u32;
+        let launch_phase = state.config.get_launch_phase(ctx.block_production_time);
+        state.stats.increase_supply(chars_count, launch_phase);
+    }
 
     let events = nft_events.into_iter().chain(pns_events).collect();
 
     (state, events)
 }
 
+/// Mints the subdomain sold by the parent domain with the expiry of the sale policy
+/// The expiry is capped by the expiration of the parents
+pub fn action_sell_subdomain(
+    ctx: &ContractContext,
+    mut state: ContractState,
+    msg: &BuySubdomainMsg,
+) -> (ContractState, Vec<EventGroup>) {
+    let sale = pns_actions::execute_sell_subdomain(ctx, &mut state.pns, &msg.parent_id);
+
+    let (mut state, events) = action_mint(
+        ctx,
+        state,
+        &msg.domain,
+        &msg.to,
+        &msg.token_uri,
+        &Some(msg.parent_id.clone()),
+        &None,
+    );
+
+    if let Some(expiry_length) = sale.expiry_length {
+        execute_update_expiration(
+            ctx,
+            &mut state.pns,
+            &PnsDomainUpdateExpirationMsg {
+                domain: msg.domain.clone(),
+                expires_at: Some(ctx.block_production_time + expiry_length),
+            },
+        );
+    }
+
+    (state, events)
+}
+
+/// Moves the domain to the given address without approval checks, its records are removed
+/// The subdomains are moved along given the subdomain transfer policy
+/// The sender is recorded as the author of the records deletion
+pub fn action_move_domain(
+    ctx: &ContractContext,
+    mut state: ContractState,
+    domain: &str,
+    to: &Address,
+) -> (ContractState, Vec<EventGroup>) {
+    assert!(
+        !state.pns.has_fuses(domain, CANNOT_TRANSFER),
+        "{}",
+        ContractError::CannotTransfer
+    );
+
+    let token_id = state.pns.get_token_id(domain).unwrap();
+    let owner = state.nft.owner_of(token_id);
+    state.nft._transfer(owner, *to, token_id);
+
+    let mut events = pns_actions::execute_record_delete_all(
+        ctx,
+        &mut state.pns,
+        &pns_msg::PnsRecordDeleteAllMsg {
+            domain: domain.to_string(),
+            updated_by: ctx.sender,
+        },
+    );
+    pns_actions::execute_clear_subdomain_sale(&mut state.pns, domain);
+
+    let (state, move_events) = action_move_subdomains(ctx, state, domain, &owner, to, &ctx.sender);
+    events.extend(move_events);
+
+    (state, events)
+}
+
+/// Moves the subdomains along with the transferred domain, given the subdomain transfer policy
+/// Their records are removed, `updated_by` being recorded as the author of the deletion
+pub fn action_move_subdomains(
+    ctx: &ContractContext,
+    mut state: ContractState,
+    domain: &str,
+    from: &Address,
+    to: &Address,
+    updated_by: &Address,
+) -> (ContractState, Vec<EventGroup>) {
+    let mut events = vec![];
+    for subdomain in state.get_subdomains_to_transfer(domain, from) {
+        let token_id = state.pns.get_token_id(&subdomain).unwrap();
+        let owner = state.nft.owner_of(token_id);
+        state.nft._transfer(owner, *to, token_id);
+
+        events.extend(pns_actions::execute_record_delete_all(
+            ctx,
+            &mut state.pns,
+            &pns_msg::PnsRecordDeleteAllMsg {
+                domain: subdomain.clone(),
+                updated_by: *updated_by,
+            },
+        ));
+        pns_actions::execute_clear_subdomain_sale(&mut state.pns, &subdomain);
+    }
+
+    (state, events)
+}
+
 pub fn action_build_mint_callback(
     payment_intent: &PaymentIntent,
     mint_msg: &MintMsg,
@@ -133,6 +327,183 @@ pub fn action_build_renew_callback(
     vec![payout_transfer_events.build()]
 }
 
+pub fn action_build_premium_tier_callback(
+    payment_intent: &PaymentIntent,
+    upgrade_msg: &UpgradePremiumTierMsg,
+    callback_byte: u32,
+) -> Vec<EventGroup> {
+    assert!(
+        payment_intent.id == upgrade_msg.payment_coin_id,
+        "{}",
+        ContractError::PaymentInfoNotValid
+    );
+
+    let mut payout_transfer_events =
+        build_payout_fees_event_group(&upgrade_msg.payer, payment_intent);
+
+    build_msg_callback(&mut payout_transfer_events, callback_byte, upgrade_msg);
+
+    vec![payout_transfer_events.build()]
+}
+
+pub fn action_build_storage_quota_callback(
+    payment_intent: &PaymentIntent,
+    storage_quota_msg: &SetStorageQuotaMsg,
+    callback_byte: u32,
+) -> Vec<EventGroup> {
+    assert!(
+        payment_intent.id == storage_quota_msg.payment_coin_id,
+        "{}",
+        ContractError::PaymentInfoNotValid
+    );
+
+    let mut payout_transfer_events =
+        build_payout_fees_event_group(&storage_quota_msg.payer, payment_intent);
+
+    build_msg_callback(
+        &mut payout_transfer_events,
+        callback_byte,
+        storage_quota_msg,
+    );
+
+    vec![payout_transfer_events.build()]
+}
+
+/// Pays the subdomain price to the parent owner
+/// The protocol fees are taken from the price and paid to the payment receiver
+/// Escrows the subdomain price into the contract before selling the subdomain
+pub fn action_build_subdomain_sale_callback(
+    token: &Address,
+    escrow: &Address,
+    buy_msg: &BuySubdomainMsg,
+    callback_byte: u32,
+) -> Vec<EventGroup> {
+    let mut escrow_transfer_events = EventGroup::builder();
+
+    MPC20TransferFromMsg {
+        from: buy_msg.payer,
+        to: *escrow,
+        amount: buy_msg.price,
+    }
+    .as_interaction(&mut escrow_transfer_events, token);
+
+    build_msg_callback(&mut escrow_transfer_events, callback_byte, buy_msg);
+
+    vec![escrow_transfer_events.build()]
+}
+
+/// Pays the escrowed price of a sold subdomain to the parent owner, minus the protocol cut
+pub fn action_build_subdomain_sale_payouts(
+    token: &Address,
+    receiver: &Address,
+    parent_owner: &Address,
+    price: u128,
+    protocol_fees: u128,
+) -> Vec<EventGroup> {
+    let mut payout_transfer_events = EventGroup::builder();
+
+    MPC20TransferMsg {
+        to: *parent_owner,
+        amount: price - protocol_fees,
+    }
+    .as_interaction(&mut payout_transfer_events, token);
+
+    if protocol_fees > 0 {
+        MPC20TransferMsg {
+            to: *receiver,
+            amount: protocol_fees,
+        }
+        .as_interaction(&mut payout_transfer_events, token);
+    }
+
+    vec![payout_transfer_events.build()]
+}
+
+/// Refunds the escrowed price of a subdomain that could not be sold
+pub fn action_build_subdomain_sale_refund(
+    token: &Address,
+    buy_msg: &BuySubdomainMsg,
+) -> Vec<EventGroup> {
+    let mut refund_transfer_events = EventGroup::builder();
+
+    MPC20TransferMsg {
+        to: buy_msg.payer,
+        amount: buy_msg.price,
+    }
+    .as_interaction(&mut refund_transfer_events, token);
+
+    vec![refund_transfer_events.build()]
+}
+
+/// Escrows the bid deposit into the contract before committing the sealed bid
+pub fn action_build_auction_bid_callback(
+    token: &Address,
+    escrow: &Address,
+    bid_msg: &AuctionBidMsg,
+    callback_byte: u32,
+) -> Vec<EventGroup> {
+    let mut escrow_transfer_events = EventGroup::builder();
+
+    MPC20TransferFromMsg {
+        from: bid_msg.bidder,
+        to: *escrow,
+        amount: bid_msg.deposit,
+    }
+    .as_interaction(&mut escrow_transfer_events, token);
+
+    build_msg_callback(&mut escrow_transfer_events, callback_byte, bid_msg);
+
+    vec![escrow_transfer_events.build()]
+}
+
+/// Refunds an escrowed deposit
+pub fn action_build_auction_refund(token: &Address, refund: &AuctionRefund) -> Vec<EventGroup> {
+    let mut refund_transfer_events = EventGroup::builder();
+
+    MPC20TransferMsg {
+        to: refund.to,
+        amount: refund.amount,
+    }
+    .as_interaction(&mut refund_transfer_events, token);
+
+    vec![refund_transfer_events.build()]
+}
+
+/// Pays the auction price and the forfeited penalties to the receiver
+/// and refunds the escrowed deposits
+pub fn action_build_auction_payouts(
+    token: &Address,
+    receiver: &Address,
+    settlement: &AuctionSettlement,
+) -> Vec<EventGroup> {
+    let mut payout_transfer_events = EventGroup::builder();
+
+    let mut transfers: Vec<MPC20TransferMsg> = settlement
+        .refunds
+        .iter()
+        .map(|refund| MPC20TransferMsg {
+            to: refund.to,
+            amount: refund.amount,
+        })
+        .collect();
+    if settlement.price + settlement.forfeited > 0 {
+        transfers.push(MPC20TransferMsg {
+            to: *receiver,
+            amount: settlement.price + settlement.forfeited,
+        });
+    }
+
+    if transfers.is_empty() {
+        return vec![];
+    }
+
+    for transfer in transfers {
+        transfer.as_interaction(&mut payout_transfer_events, token);
+    }
+
+    vec![payout_transfer_events.build()]
+}
+
 pub fn action_renew_subscription(
     ctx: ContractContext,
     mut state: ContractState,
@@ -159,6 +530,25 @@ pub fn action_renew_subscription(
     (state, vec![])
 }
 
+/// Sets the storage quota of the large records of the domain
+pub fn action_set_storage_quota(
+    ctx: &ContractContext,
+    mut state: ContractState,
+    domain: &str,
+    storage_quota_kb: u32,
+) -> (ContractState, Vec<EventGroup>) {
+    let events = pns_actions::execute_set_storage_quota(
+        ctx,
+        &mut state.pns,
+        &pns_msg::PnsSetStorageQuotaMsg {
+            domain: domain.to_string(),
+            storage_quota_kb,
+        },
+    );
+
+    (state, events)
+}
+
 fn build_payout_fees_event_group(
     payer: &Address,
     payment_intent: &PaymentIntent,
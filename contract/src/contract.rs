@@ -1,15 +1,25 @@
 use crate::{
     actions::{
-        action_build_mint_callback, action_build_renew_callback, action_mint,
-        action_renew_subscription, PaymentIntent,
+        action_build_auction_bid_callback, action_build_auction_payouts,
+        action_build_auction_refund, action_build_mint_callback,
+        action_build_premium_tier_callback, action_build_renew_callback,
+        action_build_storage_quota_callback, action_build_subdomain_sale_callback,
+        action_build_subdomain_sale_payouts, action_build_subdomain_sale_refund, action_mint,
+        action_mint_auction_domain, action_move_domain, action_move_subdomains,
+        action_renew_subscription, action_sell_subdomain, action_set_storage_quota, PaymentIntent,
     },
-    msg::{InitMsg, MintMsg, OwnerInfoEvent, RenewDomainMsg},
-    state::{ContractConfig, ContractState, ContractStats, PaymentInfo, UserRole},
+    msg::{
+        AddressEvent, BuySubdomainMsg, CustomRecordHistoryEvent, InitMsg, LargeRecordEvent,
+        LaunchPhaseEvent, MintMsg, OwnerInfoEvent, RecordHistoryEvent, RenewDomainMsg,
+        SetStorageQuotaMsg, SubdomainsEvent, UpgradePremiumTierMsg,
+    },
+    state::{ContractConfig, ContractState, ContractStats, LaunchPhase, PaymentInfo, UserRole},
 };
 
 use contract_version_base::state::ContractVersionBase;
 use pbc_contract_common::{
     address::Address,
+    avl_tree_map::AvlTreeMap,
     context::{CallbackContext, ContractContext},
     events::EventGroup,
 };
@@ -17,9 +27,27 @@ use pbc_contract_common::{
 use nft::{actions as nft_actions, msg as nft_msg};
 
 use access_control::{actions as ac_actions, msg as ac_msg};
-use airdrop::actions::{self as airdrop_actions, execute_airdrop};
-use partisia_name_system::{actions as pns_actions, msg as pns_msg, state::RecordClass};
-use utils::events::assert_callback_success;
+use airdrop::{
+    actions::{self as airdrop_actions, execute_airdrop},
+    msg::{AirdropCampaignMsg, AirdropMerkleClaimMsg},
+};
+use auction::{
+    actions as auction_actions,
+    msg::{AuctionBidMsg, AuctionCreateMsg, AuctionRevealMsg},
+    state::AuctionRefund,
+};
+use partisia_name_system::{
+    actions as pns_actions, msg as pns_msg,
+    state::{
+        storage_quota_bytes, DnsRecord, LargeRecordCommit, PnsConfig, PremiumTier, RecordClass,
+        CANNOT_CREATE_SUBDOMAIN, CANNOT_TRANSFER, MAX_RESOLVE_BATCH_SIZE, PARENT_CANNOT_CONTROL,
+    },
+};
+use resolver_interface::ResolveResponse;
+use utils::{
+    events::{assert_callback_success, is_callback_success},
+    time::milliseconds_in_years,
+};
 
 use crate::ContractError;
 
@@ -48,6 +76,9 @@ pub fn initialize(ctx: ContractContext, msg: InitMsg) -> (ContractState, Vec<Eve
         );
     });
 
+    assert_launch_schedule(&msg.config);
+    assert_subdomain_sale_fee(&msg.config);
+
     let pns = pns_actions::execute_init(&ctx);
     let nft = nft_actions::execute_init(
         &ctx,
@@ -62,14 +93,17 @@ pub fn initialize(ctx: ContractContext, msg: InitMsg) -> (ContractState, Vec<Eve
         additional_roles: vec![UserRole::Whitelist {} as u8, UserRole::Airdrop {} as u8],
     });
     let airdrop = airdrop_actions::execute_init();
+    let auction = auction_actions::execute_init();
 
     let state = ContractState {
         access_control,
         airdrop,
+        auction,
         config: msg.config,
         nft,
         pns,
         stats: ContractStats::default(),
+        sunrise_reservations: AvlTreeMap::new(),
         version: ContractVersionBase::new(CONTRACT_NAME, CONTRACT_VERSION),
     };
 
@@ -79,26 +113,14 @@ pub fn initialize(ctx: ContractContext, msg: InitMsg) -> (ContractState, Vec<Eve
 #[action(shortname = 0x03)]
 pub fn transfer_from(
     ctx: ContractContext,
-    mut state: ContractState,
+    state: ContractState,
     from: Address,
     to: Address,
     token_id: u128,
 ) -> (ContractState, Vec<EventGroup>) {
     assert_contract_enabled(&state);
 
-    let mut nft_events = nft_actions::execute_transfer_from(
-        &ctx,
-        &mut state.nft,
-        &nft_msg::NFTTransferFromMsg { from, to, token_id },
-    );
-
-    let (name, _) = state.pns.get_domain_by_token_id(token_id).unwrap();
-    let msg = &pns_msg::PnsRecordDeleteAllMsg { domain: name };
-    let pns_events = pns_actions::execute_record_delete_all(&ctx, &mut state.pns, msg);
-
-    nft_events.extend(pns_events);
-
-    (state, nft_events)
+    transfer_domain_token(&ctx, state, from, to, token_id, false)
 }
 
 #[action(shortname = 0x04)]
@@ -117,6 +139,23 @@ pub fn transfer_domain(
     transfer_from(ctx, state, from, to, token_id.unwrap())
 }
 
+/// Transfers the domain keeping its records, except the wallet records
+#[action(shortname = 0x47)]
+pub fn transfer_domain_with_records(
+    ctx: ContractContext,
+    state: ContractState,
+    from: Address,
+    to: Address,
+    domain: String,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+
+    let token_id = state.pns.get_token_id(&domain);
+    assert!(token_id.is_some(), "{}", ContractError::DomainNotMinted);
+
+    transfer_domain_token(&ctx, state, from, to, token_id.unwrap(), true)
+}
+
 #[action(shortname = 0x05)]
 pub fn approve(
     ctx: ContractContext,
@@ -242,6 +281,52 @@ pub fn owner_info(
     (state, vec![event_builder.build()])
 }
 
+/// Returns a page of the direct subdomains of the domain as data in the event
+/// the event data is of type SubdomainsEvent
+#[action(shortname = 0x44)]
+pub fn subdomains_of(
+    ctx: ContractContext,
+    state: ContractState,
+    domain: String,
+    offset: u32,
+    limit: u32,
+) -> (ContractState, Vec<EventGroup>) {
+    let mut event_builder = EventGroup::builder();
+
+    let subdomains_event = SubdomainsEvent {
+        subdomains: state.pns.get_subdomains(&domain, offset, limit),
+        total: state.pns.get_children(&domain).len() as u32,
+        domain,
+    };
+
+    event_builder.return_data(subdomains_event);
+
+    (state, vec![event_builder.build()])
+}
+
+/// Returns the current launch phase as data in the event
+/// the event data is of type LaunchPhaseEvent
+#[action(shortname = 0x2B)]
+pub fn launch_phase(
+    ctx: ContractContext,
+    state: ContractState,
+) -> (ContractState, Vec<EventGroup>) {
+    let mut event_builder = EventGroup::builder();
+
+    let launch_phase_event = LaunchPhaseEvent {
+        phase: state.config.get_launch_phase(ctx.block_production_time),
+        next_phase_at: state
+            .config
+            .launch_schedule
+            .as_ref()
+            .and_then(|schedule| schedule.get_next_phase_at(ctx.block_production_time)),
+    };
+
+    event_builder.return_data(launch_phase_event);
+
+    (state, vec![event_builder.build()])
+}
+
 #[action(shortname = 0x12)]
 pub fn is_domain_owner(
     ctx: ContractContext,
@@ -258,6 +343,41 @@ pub fn is_domain_owner(
     (state, vec![])
 }
 
+/// Returns whether the address owns or is approved for the domain as data in the event
+/// the event data is of type DomainOwnerCheckEvent, unlike is_domain_owner it never fails
+#[action(shortname = 0x53)]
+pub fn check_domain_owner(
+    ctx: ContractContext,
+    state: ContractState,
+    domain: String,
+    address: Address,
+) -> (ContractState, Vec<EventGroup>) {
+    let mut event_builder = EventGroup::builder();
+
+    let owner_check_event = state.check_domain_owner(&domain, address, ctx.block_production_time);
+
+    event_builder.return_data(owner_check_event);
+
+    (state, vec![event_builder.build()])
+}
+
+/// Returns the existence, owner, approval and expiration of the domain as data in the event
+/// the event data is of type DomainStatusEvent
+#[action(shortname = 0x54)]
+pub fn domain_status(
+    ctx: ContractContext,
+    state: ContractState,
+    domain: String,
+) -> (ContractState, Vec<EventGroup>) {
+    let mut event_builder = EventGroup::builder();
+
+    let domain_status_event = state.domain_status(&domain, ctx.block_production_time);
+
+    event_builder.return_data(domain_status_event);
+
+    (state, vec![event_builder.build()])
+}
+
 #[action(shortname = 0x16)]
 pub fn mint_custom_record_batch(
     ctx: ContractContext,
@@ -454,11 +574,115 @@ pub fn update_config(
         .has_role(UserRole::Admin {} as u8, &ctx.sender);
     assert!(is_admin, "{}", ContractError::Unauthorized);
 
+    assert_launch_schedule(&config);
+    assert_subdomain_sale_fee(&config);
+
     state.config = config;
 
     (state, vec![])
 }
 
+/// Reserves the domain for the claimant during the sunrise, `None` removes the reservation
+#[action(shortname = 0x5D)]
+pub fn set_sunrise_reservation(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+    claimant: Option<Address>,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_has_role(&state, UserRole::Admin {}, &ctx.sender);
+
+    match claimant {
+        Some(claimant) => state.sunrise_reservations.insert(domain, claimant),
+        None => state.sunrise_reservations.remove(&domain),
+    }
+
+    (state, vec![])
+}
+
+/// Updates the limits of the name system
+/// The existing premium tiers cannot be removed, reordered or have their limits changed
+#[action(shortname = 0x57)]
+pub fn update_pns_config(
+    ctx: ContractContext,
+    mut state: ContractState,
+    config: PnsConfig,
+) -> (ContractState, Vec<EventGroup>) {
+    let is_admin = state
+        .access_control
+        .has_role(UserRole::Admin {} as u8, &ctx.sender);
+    assert!(is_admin, "{}", ContractError::Unauthorized);
+    assert!(
+        config.max_domain_len > 0 && config.keeps_premium_tiers(&state.pns.config),
+        "{}",
+        ContractError::InvalidPnsConfig
+    );
+
+    state.pns.config = config;
+
+    (state, vec![])
+}
+
+/// Upgrades the domain to a premium tier with larger record limits
+/// Only the owner or an approved address of the domain can pay for an upgrade,
+/// admins upgrade without payment and can set any tier, or restore the default limits
+#[action(shortname = 0x58)]
+pub fn upgrade_premium_tier(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+    premium_tier: Option<u32>,
+    payment_coin_id: u64,
+    payer: Address,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+    assert!(
+        state.pns.is_minted(&domain),
+        "{}",
+        ContractError::DomainNotMinted
+    );
+
+    let is_admin = state
+        .access_control
+        .has_role(UserRole::Admin {} as u8, &ctx.sender);
+
+    let mut events = vec![];
+    if is_admin {
+        pns_actions::execute_set_premium_tier(
+            &ctx,
+            &mut state.pns,
+            &pns_msg::PnsSetPremiumTierMsg {
+                domain,
+                premium_tier,
+            },
+        );
+    } else {
+        assert!(premium_tier.is_some(), "{}", ContractError::Unauthorized);
+
+        let premium_tier = premium_tier.unwrap();
+        let tier = assert_premium_tier_upgrade(&ctx, &state, &domain, premium_tier);
+
+        let payment_info = assert_and_get_payment_info(&state.config, payment_coin_id);
+        events = action_build_premium_tier_callback(
+            &PaymentIntent {
+                id: payment_coin_id,
+                receiver: payment_info.receiver.unwrap(),
+                token: payment_info.token.unwrap(),
+                total_fees: payment_info.fees.scale(tier.price),
+            },
+            &UpgradePremiumTierMsg {
+                domain,
+                premium_tier,
+                payer,
+                payment_coin_id,
+            },
+            0x34,
+        );
+    }
+
+    (state, events)
+}
+
 #[action(shortname = 0x26)]
 pub fn renew_subscription(
     ctx: ContractContext,
@@ -475,6 +699,19 @@ pub fn renew_subscription(
         ContractError::InvalidSubscriptionYears
     );
 
+    let renewed_domain = state.pns.get_domain(&domain);
+    assert!(
+        renewed_domain.is_some(),
+        "{}",
+        ContractError::DomainNotMinted
+    );
+    // Subdomains follow their parents, only the parent owner sets their expiration
+    assert!(
+        renewed_domain.unwrap().parent_id.is_none(),
+        "{}",
+        ContractError::CannotRenewSubdomain
+    );
+
     let is_admin = state
         .access_control
         .has_role(UserRole::Admin {} as u8, &ctx.sender);
@@ -488,7 +725,16 @@ pub fn renew_subscription(
         events = renew_events;
     } else {
         let payment_info = assert_and_get_payment_info(&state.config, payment_coin_id);
-        let total_fees = payment_info.fees.get(&domain) * subscription_years as u128;
+        // The storage quota of the domain is billed along with each renewal year
+        let storage_quota_kb = state
+            .pns
+            .get_domain(&domain)
+            .map_or(0, |domain| domain.storage_quota_kb);
+        let total_fees = (payment_info.fees.get(&domain)
+            + payment_info
+                .fees
+                .scale(state.pns.config.get_storage_price(storage_quota_kb)))
+            * subscription_years as u128;
         events = action_build_renew_callback(
             &PaymentIntent {
                 id: payment_coin_id,
@@ -524,112 +770,1333 @@ pub fn add_airdrop(
     (state, vec![])
 }
 
-#[callback(shortname = 0x30)]
-pub fn on_mint_callback(
+#[action(shortname = 0x13)]
+pub fn create_airdrop_campaign(
     ctx: ContractContext,
-    callback_ctx: CallbackContext,
-    state: ContractState,
-    msg: MintMsg,
+    mut state: ContractState,
+    campaign: AirdropCampaignMsg,
 ) -> (ContractState, Vec<EventGroup>) {
-    assert_contract_enabled(&state);
-
-    assert_callback_success(&callback_ctx);
+    assert_has_role(&state, UserRole::Airdrop {}, &ctx.sender);
 
-    assert_and_get_payment_info(&state.config, msg.payment_coin_id);
+    airdrop_actions::execute_create_campaign(&mut state.airdrop, &campaign);
 
-    action_mint(
-        &ctx,
-        state,
-        &msg.domain,
-        &msg.to,
-        &msg.token_uri,
-        &msg.parent_id,
-        &msg.subscription_years,
-    )
+    (state, vec![])
 }
 
-#[callback(shortname = 0x31)]
-pub fn on_renew_subscription_callback(
+#[action(shortname = 0x14)]
+pub fn add_campaign_airdrop(
     ctx: ContractContext,
-    callback_ctx: CallbackContext,
-    state: ContractState,
-    msg: RenewDomainMsg,
+    mut state: ContractState,
+    campaign: String,
+    addresses: Vec<Address>,
 ) -> (ContractState, Vec<EventGroup>) {
-    assert_contract_enabled(&state);
-
-    assert_callback_success(&callback_ctx);
+    assert_has_role(&state, UserRole::Airdrop {}, &ctx.sender);
 
-    assert_and_get_payment_info(&state.config, msg.payment_coin_id);
+    for address in addresses {
+        airdrop_actions::execute_add_campaign_airdrop(&mut state.airdrop, &campaign, &address);
+    }
 
-    action_renew_subscription(ctx, state, msg.domain, msg.subscription_years)
+    (state, vec![])
 }
 
-fn mint_domain(
-    ctx: &ContractContext,
-    state: ContractState,
-    mint_msg: &MintMsg,
+#[action(shortname = 0x28)]
+pub fn add_merkle_airdrop(
+    ctx: ContractContext,
+    mut state: ContractState,
+    campaign: String,
+    merkle_root: String,
+    leaves_count: u32,
 ) -> (ContractState, Vec<EventGroup>) {
-    let MintMsg {
-        domain,
-        to,
-        payment_coin_id,
-        token_uri,
-        parent_id,
-        subscription_years,
-    } = mint_msg;
+    assert_has_role(&state, UserRole::Airdrop {}, &ctx.sender);
 
-    assert!(!state.pns.is_minted(domain), "{}", ContractError::Minted);
+    airdrop_actions::execute_add_merkle_airdrop(
+        &mut state.airdrop,
+        &campaign,
+        &merkle_root,
+        leaves_count,
+    );
 
-    pns_actions::validate_domain(domain);
+    (state, vec![])
+}
 
-    let mut events = vec![];
-    let mut mut_state = state;
+#[action(shortname = 0x29)]
+pub fn claim_merkle_airdrop(
+    ctx: ContractContext,
+    mut state: ContractState,
+    campaign: String,
+    index: u32,
+    amount: u128,
+    min_domain_length: u32,
+    proof: Vec<String>,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
 
-    let is_admin = mut_state
-        .access_control
-        .has_role(UserRole::Admin {} as u8, &ctx.sender);
-    if parent_id.is_some() || is_admin {
-        let (new_state, mint_events) = action_mint(
-            ctx,
-            mut_state,
-            domain,
-            to,
-            token_uri,
-            parent_id,
-            subscription_years,
-        );
+    airdrop_actions::execute_claim_merkle_airdrop(
+        &mut state.airdrop,
+        &ctx.sender,
+        &AirdropMerkleClaimMsg {
+            campaign,
+            index,
+            amount,
+            min_domain_length,
+            proof,
+        },
+        ctx.block_production_time,
+    );
 
-        mut_state = new_state;
+    (state, vec![])
+}
 
-        events.extend(mint_events);
-    } else {
-        let config = &mut_state.config;
-        if config.whitelist_enabled {
-            let is_whitelisted = mut_state
-                .access_control
-                .has_role(UserRole::Whitelist {} as u8, &ctx.sender);
-            assert!(is_whitelisted, "{}", ContractError::UserNotWhitelisted);
-        }
+#[action(shortname = 0x2A)]
+pub fn revoke_airdrop_campaign(
+    ctx: ContractContext,
+    mut state: ContractState,
+    campaign: String,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_has_role(&state, UserRole::Airdrop {}, &ctx.sender);
 
-        if config.mint_count_limit_enabled && !is_admin {
-            let mint_count = mut_state.stats.mint_count.get(&ctx.sender);
-            assert!(
-                mint_count.is_none() || mint_count <= Some(config.mint_count_limit),
-                "{}",
-                ContractError::MintCountLimitReached
-            );
-        }
+    airdrop_actions::execute_revoke_campaign(&mut state.airdrop, &campaign);
 
-        let has_airdrop = mut_state.airdrop.has_airdrop(&ctx.sender);
-        if has_airdrop {
-            let domain_length = domain.chars().count() as u32;
-            assert!(
-                domain_length >= config.airdrop_min_domain_length,
+    (state, vec![])
+}
+
+/// Flags the domain as premium, premium domains can only be minted through an auction
+#[action(shortname = 0x5E)]
+pub fn set_premium_domain(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+    premium: bool,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_has_role(&state, UserRole::Admin {}, &ctx.sender);
+
+    auction_actions::execute_set_premium_domain(&mut state.auction, &domain, premium);
+
+    (state, vec![])
+}
+
+/// Starts a sealed-bid auction for a premium domain
+/// The domain cannot be minted until the auction is settled
+#[action(shortname = 0x2C)]
+pub fn create_auction(
+    ctx: ContractContext,
+    mut state: ContractState,
+    auction: AuctionCreateMsg,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_has_role(&state, UserRole::Admin {}, &ctx.sender);
+
+    assert!(
+        !state.pns.is_minted(&auction.domain),
+        "{}",
+        ContractError::Minted
+    );
+    pns_actions::validate_domain(&state.pns.config, &auction.domain);
+    assert_and_get_payment_info(&state.config, auction.payment_coin_id);
+
+    auction_actions::execute_create_auction(
+        &mut state.auction,
+        &auction,
+        ctx.block_production_time,
+    );
+
+    (state, vec![])
+}
+
+/// Commits a sealed bid, the deposit is escrowed by the contract until the settlement
+/// The bid is recorded before the escrow transfer, and removed if the transfer fails
+#[action(shortname = 0x2D)]
+pub fn commit_auction_bid(
+    ctx: ContractContext,
+    state: ContractState,
+    domain: String,
+    commitment: String,
+    deposit: u128,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+
+    let auction = state.auction.get_auction(&domain);
+    assert!(auction.is_some(), "{}", ContractError::DomainNotInAuction);
+
+    let bid_msg = AuctionBidMsg {
+        domain,
+        bidder: ctx.sender,
+        payment_coin_id: auction.unwrap().payment_coin_id,
+        commitment,
+        deposit,
+    };
+
+    commit_bid(&ctx, state, &bid_msg)
+}
+
+/// Applies for a domain during the landrush with a sealed bid, escrowed like the auction bids
+/// The first application starts the auction of the domain, with the landrush fees as reserve price
+/// The applications are revealed once the general availability starts, then the auction is settled
+#[action(shortname = 0x61)]
+pub fn apply_landrush_domain(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+    payment_coin_id: u64,
+    commitment: String,
+    deposit: u128,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+
+    let config = &state.config;
+    assert!(
+        config.get_launch_phase(ctx.block_production_time) == Some(LaunchPhase::Landrush {}),
+        "{}",
+        ContractError::LandrushNotOpen
+    );
+    if config.whitelist_enabled {
+        assert_has_role(&state, UserRole::Whitelist {}, &ctx.sender);
+    }
+
+    if !state.auction.is_in_auction(&domain) {
+        assert!(!state.pns.is_minted(&domain), "{}", ContractError::Minted);
+        assert!(
+            !state.auction.is_premium(&domain),
+            "{}",
+            ContractError::DomainIsPremium
+        );
+        pns_actions::validate_domain(&state.pns.config, &domain);
+        assert!(
+            state.is_supply_available(
+                domain.chars().count() as u32,
+                Some(LaunchPhase::Landrush {})
+            ),
+            "{}",
+            ContractError::SupplyCapReached
+        );
+
+        let payment_info = assert_and_get_payment_info(config, payment_coin_id);
+        let schedule = config.launch_schedule.as_ref().unwrap();
+        let auction = AuctionCreateMsg {
+            domain: domain.clone(),
+            payment_coin_id,
+            reserve_price: payment_info.fees.get(&domain)
+                * config.get_fees_multiplier(ctx.block_production_time),
+            subscription_years: 1,
+            unrevealed_penalty_bps: schedule.landrush_unrevealed_penalty_bps,
+            commit_ends_at: schedule.general_availability_at,
+            reveal_ends_at: schedule.general_availability_at + schedule.landrush_reveal_period,
+        };
+
+        auction_actions::execute_open_auction(
+            &mut state.auction,
+            &auction,
+            ctx.block_production_time,
+        );
+    }
+
+    let bid_msg = AuctionBidMsg {
+        domain,
+        bidder: ctx.sender,
+        payment_coin_id,
+        commitment,
+        deposit,
+    };
+
+    commit_bid(&ctx, state, &bid_msg)
+}
+
+#[action(shortname = 0x2E)]
+pub fn reveal_auction_bid(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+    amount: u128,
+    salt: String,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+
+    auction_actions::execute_reveal_bid(
+        &mut state.auction,
+        &ctx.sender,
+        &AuctionRevealMsg {
+            domain,
+            amount,
+            salt,
+        },
+        ctx.block_production_time,
+    );
+
+    (state, vec![])
+}
+
+/// Settles an ended auction, anyone can call it
+/// The winner gets the domain at the second price, the other deposits are refunded
+#[action(shortname = 0x2F)]
+pub fn settle_auction(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+
+    let settlement = auction_actions::execute_settle_auction(
+        &mut state.auction,
+        &domain,
+        ctx.block_production_time,
+    );
+    let payment_info =
+        assert_and_get_payment_info(&state.config, settlement.auction.payment_coin_id);
+
+    let mut events = action_build_auction_payouts(
+        &payment_info.token.unwrap(),
+        &payment_info.receiver.unwrap(),
+        &settlement,
+    );
+
+    if let Some(winner) = settlement.winner {
+        let (new_state, mint_events) = action_mint_auction_domain(
+            &ctx,
+            state,
+            &domain,
+            &winner,
+            settlement.auction.subscription_years,
+        );
+
+        state = new_state;
+        events.extend(mint_events);
+    }
+
+    (state, events)
+}
+
+/// Publishes or replaces the subdomain sale policy of a domain
+/// Only the owner or an approved address of the domain can set it
+#[action(shortname = 0x40)]
+pub fn set_subdomain_sale(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+    price: u128,
+    payment_coin_id: u64,
+    max_subdomains: Option<u32>,
+    expiry_length: Option<i64>,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+    assert_domain_access(&ctx, &state, &domain);
+    assert_and_get_payment_info(&state.config, payment_coin_id);
+
+    let events = pns_actions::execute_set_subdomain_sale(
+        &ctx,
+        &mut state.pns,
+        &pns_msg::PnsSubdomainSaleMsg {
+            domain,
+            price,
+            payment_coin_id,
+            max_subdomains,
+            expiry_length,
+        },
+    );
+
+    (state, events)
+}
+
+#[action(shortname = 0x41)]
+pub fn remove_subdomain_sale(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+    assert_domain_access(&ctx, &state, &domain);
+
+    let events = pns_actions::execute_remove_subdomain_sale(&ctx, &mut state.pns, &domain);
+
+    (state, events)
+}
+
+/// Buys a subdomain from the sale policy of the parent domain
+/// The price is paid to the parent owner, minus the protocol fees
+#[action(shortname = 0x42)]
+pub fn buy_subdomain(
+    ctx: ContractContext,
+    state: ContractState,
+    domain: String,
+    parent_id: String,
+    to: Address,
+    token_uri: Option<String>,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+
+    assert!(!state.pns.is_minted(&domain), "{}", ContractError::Minted);
+    pns_actions::validate_domain(&state.pns.config, &domain);
+    pns_actions::validate_domain_with_parent(&domain, &parent_id);
+
+    let parent_token_id = state.pns.get_token_id(&parent_id);
+    assert!(
+        parent_token_id.is_some(),
+        "{}",
+        ContractError::DomainNotMinted
+    );
+    assert!(
+        state.pns.is_active(&parent_id, ctx.block_production_time),
+        "{}",
+        ContractError::DomainNotActive
+    );
+
+    assert!(
+        !state.pns.has_fuses(&parent_id, CANNOT_CREATE_SUBDOMAIN),
+        "{}",
+        ContractError::CannotCreateSubdomain
+    );
+
+    let sale = state.pns.get_subdomain_sale(&parent_id);
+    assert!(
+        sale.as_ref().is_some_and(|sale| sale.is_available()),
+        "{}",
+        ContractError::SubdomainSaleNotAvailable
+    );
+
+    let sale = sale.unwrap();
+    let buy_msg = BuySubdomainMsg {
+        domain,
+        parent_id,
+        to,
+        token_uri,
+        payer: ctx.sender,
+        price: sale.price,
+        payment_coin_id: sale.payment_coin_id,
+    };
+
+    if sale.price == 0 {
+        return action_sell_subdomain(&ctx, state, &buy_msg);
+    }
+
+    let payment_info = assert_and_get_payment_info(&state.config, sale.payment_coin_id);
+    let events = action_build_subdomain_sale_callback(
+        &payment_info.token.unwrap(),
+        &ctx.contract_address,
+        &buy_msg,
+        0x33,
+    );
+
+    (state, events)
+}
+
+/// Sets the expiration of a subdomain, capped by the expiration of its parents
+/// Only the owner or an approved address of the parent domain can set it,
+/// once the parent cannot control the subdomain it can only extend the expiration
+#[action(shortname = 0x43)]
+pub fn set_subdomain_expiration(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+    expires_at: Option<i64>,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+
+    let subdomain = state.pns.get_domain(&domain);
+    assert!(subdomain.is_some(), "{}", ContractError::DomainNotMinted);
+
+    let subdomain = subdomain.unwrap();
+    if subdomain.has_fuses(PARENT_CANNOT_CONTROL) {
+        assert!(
+            subdomain.parent_id.is_some(),
+            "{}",
+            ContractError::NotASubdomain
+        );
+        assert!(
+            is_parent_approved_or_owner(&ctx, &state, &domain),
+            "{}",
+            ContractError::Unauthorized
+        );
+
+        let is_extended = match (subdomain.expires_at, expires_at) {
+            (_, None) => true,
+            (None, Some(_)) => false,
+            (Some(current), Some(new)) => new >= current,
+        };
+        assert!(is_extended, "{}", ContractError::ParentCannotControl);
+    } else {
+        assert_parent_control(&ctx, &state, &domain);
+    }
+
+    let events = pns_actions::execute_update_expiration(
+        &ctx,
+        &mut state.pns,
+        &pns_msg::PnsDomainUpdateExpirationMsg { domain, expires_at },
+    );
+
+    (state, events)
+}
+
+/// Burns fuses of a domain, burned fuses cannot be restored
+/// The owner can burn the fuses of the domain, except the parent control fuse
+/// The parent owner can burn any fuse while it controls the subdomain
+#[action(shortname = 0x45)]
+pub fn burn_fuses(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+    fuses: u32,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+
+    let has_parent_control = has_parent_control(&ctx, &state, &domain);
+    if fuses & PARENT_CANNOT_CONTROL == 0 {
+        let token_id = state.pns.get_token_id(&domain);
+        assert!(token_id.is_some(), "{}", ContractError::DomainNotMinted);
+
+        let is_owner = state
+            .nft
+            .is_approved_or_owner(ctx.sender, token_id.unwrap());
+        assert!(
+            is_owner || has_parent_control,
+            "{}",
+            ContractError::Unauthorized
+        );
+    } else {
+        assert!(has_parent_control, "{}", ContractError::Unauthorized);
+    }
+
+    let events = pns_actions::execute_burn_fuses(
+        &ctx,
+        &mut state.pns,
+        &pns_msg::PnsBurnFusesMsg { domain, fuses },
+    );
+
+    (state, events)
+}
+
+/// Transfers a subdomain back to the given address, its records are removed
+/// Only the owner or an approved address of the parent domain can reclaim it,
+/// unless the parent cannot control the subdomain
+#[action(shortname = 0x46)]
+pub fn reclaim_subdomain(
+    ctx: ContractContext,
+    state: ContractState,
+    domain: String,
+    to: Address,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+    assert_parent_control(&ctx, &state, &domain);
+
+    action_move_domain(&ctx, state, &domain, &to)
+}
+
+/// Sets the address of a SLIP-44 coin type for the domain, `None` removes it
+/// Only the owner or an approved address of the domain can set it
+#[action(shortname = 0x48)]
+pub fn set_address(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+    coin_type: u32,
+    address: Option<Vec<u8>>,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+    assert_domain_access(&ctx, &state, &domain);
+
+    let events = pns_actions::execute_set_address(
+        &ctx,
+        &mut state.pns,
+        &pns_msg::PnsSetAddressMsg {
+            domain,
+            coin_type,
+            address,
+        },
+    );
+
+    (state, events)
+}
+
+/// Returns the address of a SLIP-44 coin type for the domain as data in the event
+/// the event data is of type AddressEvent, aliases are followed and inactive domains resolve to `None`
+#[action(shortname = 0x49)]
+pub fn address_of(
+    ctx: ContractContext,
+    state: ContractState,
+    domain: String,
+    coin_type: u32,
+) -> (ContractState, Vec<EventGroup>) {
+    let mut event_builder = EventGroup::builder();
+
+    let address_event = AddressEvent {
+        address: state
+            .pns
+            .get_address(&domain, coin_type, ctx.block_production_time),
+        domain,
+        coin_type,
+    };
+
+    event_builder.return_data(address_event);
+
+    (state, vec![event_builder.build()])
+}
+
+/// Sets the multicodec encoded content hash of the domain
+/// Only the owner or an approved address of the domain can set it
+#[action(shortname = 0x4A)]
+pub fn set_content_hash(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+    content_hash: Vec<u8>,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+    assert_domain_access(&ctx, &state, &domain);
+
+    let events = pns_actions::execute_set_content_hash(
+        &ctx,
+        &mut state.pns,
+        &pns_msg::PnsSetContentHashMsg {
+            domain,
+            content_hash: Some(content_hash),
+        },
+    );
+
+    (state, events)
+}
+
+/// Clears the content hash of the domain
+/// Only the owner or an approved address of the domain can clear it
+#[action(shortname = 0x4B)]
+pub fn clear_content_hash(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+    assert_domain_access(&ctx, &state, &domain);
+
+    let events = pns_actions::execute_set_content_hash(
+        &ctx,
+        &mut state.pns,
+        &pns_msg::PnsSetContentHashMsg {
+            domain,
+            content_hash: None,
+        },
+    );
+
+    (state, events)
+}
+
+/// Replaces the DNS record set of the domain, an empty set clears it
+/// Only the owner or an approved address of the domain can set it
+#[action(shortname = 0x4C)]
+pub fn set_dns_records(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+    records: Vec<DnsRecord>,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+    assert_domain_access(&ctx, &state, &domain);
+
+    let events = pns_actions::execute_set_dns_records(
+        &ctx,
+        &mut state.pns,
+        &pns_msg::PnsSetDnsRecordsMsg { domain, records },
+    );
+
+    (state, events)
+}
+
+/// Points the domain to the records of another domain, `None` removes the alias
+/// Only the owner or an approved address of the domain can set it
+#[action(shortname = 0x4D)]
+pub fn set_alias(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+    alias: Option<String>,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+    assert_domain_access(&ctx, &state, &domain);
+
+    let events = pns_actions::execute_set_alias(
+        &ctx,
+        &mut state.pns,
+        &pns_msg::PnsSetAliasMsg { domain, alias },
+    );
+
+    (state, events)
+}
+
+/// Enables or disables the wildcard of the domain,
+/// with the wildcard enabled the unminted subdomains resolve to the domain
+/// Only the owner or an approved address of the domain can set it
+#[action(shortname = 0x4E)]
+pub fn set_wildcard(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+    wildcard: bool,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+    assert_domain_access(&ctx, &state, &domain);
+
+    let events = pns_actions::execute_set_wildcard(
+        &ctx,
+        &mut state.pns,
+        &pns_msg::PnsSetWildcardMsg { domain, wildcard },
+    );
+
+    (state, events)
+}
+
+/// Sets the storage quota of the large records of the domain
+/// Only the owner or an approved address of the domain can set it, admins set it without payment
+/// An increase is billed for the added KB until the domain expires, the renewals bill the whole quota
+#[action(shortname = 0x60)]
+pub fn set_storage_quota(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+    storage_quota_kb: u32,
+    payment_coin_id: u64,
+    payer: Address,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+
+    let is_admin = state
+        .access_control
+        .has_role(UserRole::Admin {} as u8, &ctx.sender);
+    if !is_admin {
+        assert_domain_access(&ctx, &state, &domain);
+    }
+    assert_storage_quota(&ctx, &state, &domain, storage_quota_kb);
+
+    let current_quota_kb = state
+        .pns
+        .get_domain(&domain)
+        .map_or(0, |domain| domain.storage_quota_kb);
+
+    let events;
+    if is_admin || storage_quota_kb <= current_quota_kb {
+        let (new_state, quota_events) =
+            action_set_storage_quota(&ctx, state, &domain, storage_quota_kb);
+
+        state = new_state;
+        events = quota_events;
+    } else {
+        let payment_info = assert_and_get_payment_info(&state.config, payment_coin_id);
+        // A domain without expiration is billed a year, as on a renewal
+        let remaining_millis = state
+            .pns
+            .get_expires_at(&domain)
+            .map_or(milliseconds_in_years(1), |expires_at| {
+                expires_at - ctx.block_production_time
+            });
+        let storage_price = state
+            .pns
+            .config
+            .get_prorated_storage_price(storage_quota_kb - current_quota_kb, remaining_millis);
+        events = action_build_storage_quota_callback(
+            &PaymentIntent {
+                id: payment_coin_id,
+                receiver: payment_info.receiver.unwrap(),
+                token: payment_info.token.unwrap(),
+                total_fees: payment_info.fees.scale(storage_price),
+            },
+            &SetStorageQuotaMsg {
+                domain,
+                storage_quota_kb,
+                payer,
+                payment_coin_id,
+            },
+            0x35,
+        );
+    }
+
+    (state, events)
+}
+
+/// Writes a chunk of a large record of the domain, within the storage quota of the domain
+/// Only the owner or an approved address of the domain can write it
+#[action(shortname = 0x59)]
+pub fn write_large_record_chunk(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+    key: String,
+    index: u32,
+    data: Vec<u8>,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+    assert_domain_access(&ctx, &state, &domain);
+
+    let events = pns_actions::execute_write_large_record_chunk(
+        &ctx,
+        &mut state.pns,
+        &pns_msg::PnsWriteLargeRecordChunkMsg {
+            domain,
+            key,
+            index,
+            data,
+        },
+    );
+
+    (state, events)
+}
+
+/// Commits a large record of the domain once all its chunks are written
+/// Only the owner or an approved address of the domain can commit it
+#[action(shortname = 0x5A)]
+pub fn commit_large_record(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+    key: String,
+    commit: LargeRecordCommit,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+    assert_domain_access(&ctx, &state, &domain);
+
+    let events = pns_actions::execute_commit_large_record(
+        &ctx,
+        &mut state.pns,
+        &pns_msg::PnsCommitLargeRecordMsg {
+            domain,
+            key,
+            commit,
+        },
+    );
+
+    (state, events)
+}
+
+/// Deletes a large record of the domain, releasing its storage
+/// Only the owner or an approved address of the domain can delete it
+#[action(shortname = 0x5B)]
+pub fn delete_large_record(
+    ctx: ContractContext,
+    mut state: ContractState,
+    domain: String,
+    key: String,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+    assert_domain_access(&ctx, &state, &domain);
+
+    let events = pns_actions::execute_large_record_delete(
+        &ctx,
+        &mut state.pns,
+        &pns_msg::PnsLargeRecordDeleteMsg { domain, key },
+    );
+
+    (state, events)
+}
+
+/// Returns the committed large record of the domain as data in the event
+/// the event data is of type LargeRecordEvent
+#[action(shortname = 0x5C)]
+pub fn large_record(
+    ctx: ContractContext,
+    state: ContractState,
+    domain: String,
+    key: String,
+) -> (ContractState, Vec<EventGroup>) {
+    let mut event_builder = EventGroup::builder();
+
+    let large_record_event = LargeRecordEvent {
+        data: state.pns.get_large_record_value(&domain, &key),
+        commit: state
+            .pns
+            .get_large_records(&domain)
+            .get(&key)
+            .and_then(|large_record| large_record.commit.clone()),
+        domain,
+        key,
+    };
+
+    event_builder.return_data(large_record_event);
+
+    (state, vec![event_builder.build()])
+}
+
+/// Returns the record of the given class of the domain as data in the event
+/// the event data is of type ResolvedRecordEvent, aliases and wildcards are followed
+#[action(shortname = 0x4F)]
+pub fn resolve_record(
+    ctx: ContractContext,
+    state: ContractState,
+    domain: String,
+    class: RecordClass,
+) -> (ContractState, Vec<EventGroup>) {
+    let mut event_builder = EventGroup::builder();
+
+    let resolved_record_event = state.resolve_record(&domain, &class, ctx.block_production_time);
+
+    event_builder.return_data(resolved_record_event);
+
+    (state, vec![event_builder.build()])
+}
+
+/// Returns the custom record of the given key of the domain as data in the event
+/// the event data is of type ResolvedRecordEvent, aliases and wildcards are followed
+#[action(shortname = 0x50)]
+pub fn resolve_custom_record(
+    ctx: ContractContext,
+    state: ContractState,
+    domain: String,
+    key: String,
+) -> (ContractState, Vec<EventGroup>) {
+    let mut event_builder = EventGroup::builder();
+
+    let resolved_record_event =
+        state.resolve_custom_record(&domain, &key, ctx.block_production_time);
+
+    event_builder.return_data(resolved_record_event);
+
+    (state, vec![event_builder.build()])
+}
+
+/// Returns all the records of the domain as data in the event
+/// the event data is of type ResolvedDomainEvent, aliases and wildcards are followed
+#[action(shortname = 0x51)]
+pub fn resolve_all(
+    ctx: ContractContext,
+    state: ContractState,
+    domain: String,
+) -> (ContractState, Vec<EventGroup>) {
+    let mut event_builder = EventGroup::builder();
+
+    let resolved_domain_event = state.resolve_all(&domain, ctx.block_production_time);
+
+    event_builder.return_data(resolved_domain_event);
+
+    (state, vec![event_builder.build()])
+}
+
+/// Returns the record of the given class of the domain along with its change trail as data in the event
+/// the event data is of type RecordHistoryEvent
+#[action(shortname = 0x56)]
+pub fn record_history(
+    ctx: ContractContext,
+    state: ContractState,
+    domain: String,
+    class: RecordClass,
+) -> (ContractState, Vec<EventGroup>) {
+    let mut event_builder = EventGroup::builder();
+
+    let record_history_event = RecordHistoryEvent {
+        data: state
+            .pns
+            .get_domain(&domain)
+            .and_then(|domain| domain.get_record(&class).cloned()),
+        history: state.pns.get_record_history(&domain, &class),
+        domain,
+        class,
+    };
+
+    event_builder.return_data(record_history_event);
+
+    (state, vec![event_builder.build()])
+}
+
+/// Returns the custom record of the given key of the domain along with its change trail as data in the event
+/// the event data is of type CustomRecordHistoryEvent
+#[action(shortname = 0x5F)]
+pub fn custom_record_history(
+    ctx: ContractContext,
+    state: ContractState,
+    domain: String,
+    key: String,
+) -> (ContractState, Vec<EventGroup>) {
+    let mut event_builder = EventGroup::builder();
+
+    let custom_record_history_event = CustomRecordHistoryEvent {
+        data: state
+            .pns
+            .get_domain(&domain)
+            .and_then(|domain| domain.get_custom_record(&key).cloned()),
+        history: state.pns.get_custom_record_history(&domain, &key),
+        domain,
+        key,
+    };
+
+    event_builder.return_data(custom_record_history_event);
+
+    (state, vec![event_builder.build()])
+}
+
+/// Returns the owner, expiration and selected records of many domains as data in the event
+/// the event data is of type BatchResolvedEvent, aliases and wildcards are followed
+#[action(shortname = 0x55)]
+pub fn resolve_batch(
+    ctx: ContractContext,
+    state: ContractState,
+    domains: Vec<String>,
+    classes: Vec<RecordClass>,
+) -> (ContractState, Vec<EventGroup>) {
+    assert!(
+        domains.len() <= MAX_RESOLVE_BATCH_SIZE,
+        "{}",
+        ContractError::MaxResolveBatchSize
+    );
+
+    let mut event_builder = EventGroup::builder();
+
+    let batch_resolved_event = state.resolve_batch(&domains, &classes, ctx.block_production_time);
+
+    event_builder.return_data(batch_resolved_event);
+
+    (state, vec![event_builder.build()])
+}
+
+/// Answers a resolve request of another contract by calling its callback shortname
+/// with a ResolveResponse, aliases and wildcards are followed
+#[action(shortname = 0x52)]
+pub fn resolve_with_callback(
+    ctx: ContractContext,
+    state: ContractState,
+    domain: String,
+    class: RecordClass,
+    callback_shortname: u32,
+    request_id: u64,
+) -> (ContractState, Vec<EventGroup>) {
+    let mut event_builder = EventGroup::builder();
+
+    let resolved = state.resolve_record(&domain, &class, ctx.block_production_time);
+    let response = ResolveResponse {
+        request_id,
+        domain,
+        owner: resolved.info.owner,
+        active: resolved.info.active,
+        data: resolved.data,
+    };
+
+    response.as_callback_interaction(&mut event_builder, &ctx.sender, callback_shortname);
+
+    (state, vec![event_builder.build()])
+}
+
+#[callback(shortname = 0x30)]
+pub fn on_mint_callback(
+    ctx: ContractContext,
+    callback_ctx: CallbackContext,
+    state: ContractState,
+    msg: MintMsg,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+
+    assert_callback_success(&callback_ctx);
+
+    assert_and_get_payment_info(&state.config, msg.payment_coin_id);
+
+    action_mint(
+        &ctx,
+        state,
+        &msg.domain,
+        &msg.to,
+        &msg.token_uri,
+        &msg.parent_id,
+        &msg.subscription_years,
+    )
+}
+
+#[callback(shortname = 0x31)]
+pub fn on_renew_subscription_callback(
+    ctx: ContractContext,
+    callback_ctx: CallbackContext,
+    state: ContractState,
+    msg: RenewDomainMsg,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+
+    assert_callback_success(&callback_ctx);
+
+    assert_and_get_payment_info(&state.config, msg.payment_coin_id);
+
+    action_renew_subscription(ctx, state, msg.domain, msg.subscription_years)
+}
+
+#[callback(shortname = 0x35)]
+pub fn on_set_storage_quota_callback(
+    ctx: ContractContext,
+    callback_ctx: CallbackContext,
+    state: ContractState,
+    msg: SetStorageQuotaMsg,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+
+    assert_callback_success(&callback_ctx);
+
+    assert_and_get_payment_info(&state.config, msg.payment_coin_id);
+    assert_domain_access(&ctx, &state, &msg.domain);
+    assert_storage_quota(&ctx, &state, &msg.domain, msg.storage_quota_kb);
+
+    action_set_storage_quota(&ctx, state, &msg.domain, msg.storage_quota_kb)
+}
+
+#[callback(shortname = 0x34)]
+pub fn on_upgrade_premium_tier_callback(
+    ctx: ContractContext,
+    callback_ctx: CallbackContext,
+    mut state: ContractState,
+    msg: UpgradePremiumTierMsg,
+) -> (ContractState, Vec<EventGroup>) {
+    assert_contract_enabled(&state);
+
+    assert_callback_success(&callback_ctx);
+
+    assert_and_get_payment_info(&state.config, msg.payment_coin_id);
+    assert_premium_tier_upgrade(&ctx, &state, &msg.domain, msg.premium_tier);
+
+    pns_actions::execute_set_premium_tier(
+        &ctx,
+        &mut state.pns,
+        &pns_msg::PnsSetPremiumTierMsg {
+            domain: msg.domain,
+            premium_tier: Some(msg.premium_tier),
+        },
+    );
+
+    (state, vec![])
+}
+
+#[callback(shortname = 0x32)]
+pub fn on_auction_bid_callback(
+    ctx: ContractContext,
+    callback_ctx: CallbackContext,
+    mut state: ContractState,
+    msg: AuctionBidMsg,
+) -> (ContractState, Vec<EventGroup>) {
+    if !is_callback_success(&callback_ctx) {
+        auction_actions::execute_cancel_bid(&mut state.auction, &msg);
+
+        return (state, vec![]);
+    }
+
+    // The auction may have been settled before the deposit was escrowed
+    if auction_actions::execute_confirm_bid(&mut state.auction, &msg) {
+        return (state, vec![]);
+    }
+
+    let payment_info = assert_and_get_payment_info(&state.config, msg.payment_coin_id);
+    let events = action_build_auction_refund(
+        &payment_info.token.unwrap(),
+        &AuctionRefund {
+            to: msg.bidder,
+            amount: msg.deposit,
+        },
+    );
+
+    (state, events)
+}
+
+#[callback(shortname = 0x33)]
+pub fn on_buy_subdomain_callback(
+    ctx: ContractContext,
+    callback_ctx: CallbackContext,
+    state: ContractState,
+    msg: BuySubdomainMsg,
+) -> (ContractState, Vec<EventGroup>) {
+    // The price was not escrowed, there is nothing to sell nor refund
+    if !is_callback_success(&callback_ctx) {
+        return (state, vec![]);
+    }
+
+    let payment_info = assert_and_get_payment_info(&state.config, msg.payment_coin_id);
+    let token = payment_info.token.unwrap();
+
+    // The subdomain may have been taken or the sale changed since the escrow
+    if !state.can_sell_subdomain(&msg, ctx.block_production_time) {
+        let events = action_build_subdomain_sale_refund(&token, &msg);
+
+        return (state, events);
+    }
+
+    let parent_token_id = state.pns.get_token_id(&msg.parent_id).unwrap();
+    let parent_owner = state.nft.owner_of(parent_token_id);
+    let protocol_fees = state.config.get_subdomain_sale_fee(msg.price);
+
+    let (state, mut events) = action_sell_subdomain(&ctx, state, &msg);
+    events.extend(action_build_subdomain_sale_payouts(
+        &token,
+        &payment_info.receiver.unwrap(),
+        &parent_owner,
+        msg.price,
+        protocol_fees,
+    ));
+
+    (state, events)
+}
+
+fn transfer_domain_token(
+    ctx: &ContractContext,
+    mut state: ContractState,
+    from: Address,
+    to: Address,
+    token_id: u128,
+    keep_records: bool,
+) -> (ContractState, Vec<EventGroup>) {
+    let (name, domain) = state.pns.get_domain_by_token_id(token_id).unwrap();
+    assert!(
+        !domain.has_fuses(CANNOT_TRANSFER),
+        "{}",
+        ContractError::CannotTransfer
+    );
+
+    let mut nft_events = nft_actions::execute_transfer_from(
+        ctx,
+        &mut state.nft,
+        &nft_msg::NFTTransferFromMsg { from, to, token_id },
+    );
+
+    // The records are removed on behalf of the owner giving the domain away
+    let msg = &pns_msg::PnsRecordDeleteAllMsg {
+        domain: name.clone(),
+        updated_by: from,
+    };
+    let pns_events = if keep_records {
+        pns_actions::execute_record_delete_transfer_unsafe(ctx, &mut state.pns, msg)
+    } else {
+        pns_actions::execute_record_delete_all(ctx, &mut state.pns, msg)
+    };
+
+    nft_events.extend(pns_events);
+
+    // The sale policy of the previous owner does not apply to the new one
+    pns_actions::execute_clear_subdomain_sale(&mut state.pns, &name);
+
+    let (state, move_events) = action_move_subdomains(ctx, state, &name, &from, &to, &from);
+    nft_events.extend(move_events);
+
+    (state, nft_events)
+}
+
+fn mint_domain(
+    ctx: &ContractContext,
+    state: ContractState,
+    mint_msg: &MintMsg,
+) -> (ContractState, Vec<EventGroup>) {
+    let MintMsg {
+        domain,
+        to,
+        payment_coin_id,
+        token_uri,
+        parent_id,
+        subscription_years,
+    } = mint_msg;
+
+    assert!(!state.pns.is_minted(domain), "{}", ContractError::Minted);
+    assert!(
+        !state.auction.is_in_auction(domain),
+        "{}",
+        ContractError::DomainInAuction
+    );
+    assert!(
+        !state.auction.is_premium(domain),
+        "{}",
+        ContractError::DomainIsPremium
+    );
+
+    pns_actions::validate_domain(&state.pns.config, domain);
+
+    // Checked before the payment, the callback checks it again
+    if parent_id.is_none() {
+        let launch_phase = state.config.get_launch_phase(ctx.block_production_time);
+        assert!(
+            state.is_supply_available(domain.chars().count() as u32, launch_phase),
+            "{}",
+            ContractError::SupplyCapReached
+        );
+    }
+
+    let mut events = vec![];
+    let mut mut_state = state;
+
+    let is_admin = mut_state
+        .access_control
+        .has_role(UserRole::Admin {} as u8, &ctx.sender);
+    if let Some(parent_token_id) = parent_id
+        .as_ref()
+        .and_then(|parent_id| mut_state.pns.get_token_id(parent_id))
+    {
+        assert!(
+            mut_state
+                .nft
+                .is_approved_or_owner(ctx.sender, parent_token_id),
+            "{}",
+            ContractError::Unauthorized
+        );
+    }
+
+    if parent_id.is_some() || is_admin {
+        let (new_state, mint_events) = action_mint(
+            ctx,
+            mut_state,
+            domain,
+            to,
+            token_uri,
+            parent_id,
+            subscription_years,
+        );
+
+        mut_state = new_state;
+
+        events.extend(mint_events);
+    } else {
+        let config = &mut_state.config;
+        assert!(
+            config.is_launched(ctx.block_production_time),
+            "{}",
+            ContractError::LaunchNotStarted
+        );
+
+        match config.get_launch_phase(ctx.block_production_time) {
+            Some(LaunchPhase::Sunrise {}) => assert!(
+                mut_state.is_sunrise_claimant(domain, &ctx.sender),
+                "{}",
+                ContractError::UserNotSunriseClaimant
+            ),
+            Some(LaunchPhase::Landrush {}) => {
+                panic!("{}", ContractError::LandrushApplicationRequired)
+            }
+            _ => {}
+        }
+
+        if config.whitelist_enabled {
+            let is_whitelisted = mut_state
+                .access_control
+                .has_role(UserRole::Whitelist {} as u8, &ctx.sender);
+            assert!(is_whitelisted, "{}", ContractError::UserNotWhitelisted);
+        }
+
+        if config.mint_count_limit_enabled && !is_admin {
+            let (limit, window) = mut_state.get_mint_count_limit(&ctx.sender);
+            let mint_count = mut_state.stats.get_window_mint_count(
+                &ctx.sender,
+                window,
+                ctx.block_production_time,
+            );
+            assert!(
+                mint_count < limit,
                 "{}",
-                ContractError::AirdropNotValid
+                ContractError::MintCountLimitReached
             );
+        }
 
-            execute_airdrop(&mut mut_state.airdrop, &ctx.sender);
+        let domain_length = domain.chars().count() as u32;
+        let years = subscription_years.unwrap_or(1);
+        let airdrop_allowance = mut_state.airdrop.get_best_allowance(
+            &ctx.sender,
+            domain_length,
+            years,
+            ctx.block_production_time,
+        );
+        let has_airdrop = mut_state.airdrop.has_airdrop(&ctx.sender);
+        if airdrop_allowance.is_some() || has_airdrop {
+            if airdrop_allowance.is_none() {
+                assert!(
+                    domain_length >= config.airdrop_min_domain_length,
+                    "{}",
+                    ContractError::AirdropNotValid
+                );
+            }
+
+            execute_airdrop(
+                &mut mut_state.airdrop,
+                &ctx.sender,
+                domain_length,
+                years,
+                ctx.block_production_time,
+            );
 
             let (new_state, mint_events) = action_mint(
                 ctx,
@@ -638,7 +2105,7 @@ fn mint_domain(
                 to,
                 token_uri,
                 parent_id,
-                subscription_years,
+                &Some(years),
             );
 
             mut_state = new_state;
@@ -647,7 +2114,9 @@ fn mint_domain(
         } else {
             let payment_info = assert_and_get_payment_info(config, *payment_coin_id);
             let subscription_years = subscription_years.unwrap_or(1);
-            let total_fees = payment_info.fees.get(domain) * subscription_years as u128;
+            let total_fees = payment_info.fees.get(domain)
+                * subscription_years as u128
+                * config.get_fees_multiplier(ctx.block_production_time);
             let payout_transfer_events = action_build_mint_callback(
                 &PaymentIntent {
                     id: *payment_coin_id,
@@ -673,6 +2142,25 @@ fn mint_domain(
     (mut_state, events)
 }
 
+/// Stores the sealed bid and requests the escrow of its deposit
+fn commit_bid(
+    ctx: &ContractContext,
+    mut state: ContractState,
+    bid_msg: &AuctionBidMsg,
+) -> (ContractState, Vec<EventGroup>) {
+    let payment_info = assert_and_get_payment_info(&state.config, bid_msg.payment_coin_id);
+    auction_actions::execute_commit_bid(&mut state.auction, bid_msg, ctx.block_production_time);
+
+    let events = action_build_auction_bid_callback(
+        &payment_info.token.unwrap(),
+        &ctx.contract_address,
+        bid_msg,
+        0x32,
+    );
+
+    (state, events)
+}
+
 fn assert_has_role(state: &ContractState, role: UserRole, account: &Address) {
     assert!(
         state.access_control.has_role(role as u8, account),
@@ -681,6 +2169,25 @@ fn assert_has_role(state: &ContractState, role: UserRole, account: &Address) {
     );
 }
 
+fn assert_storage_quota(
+    ctx: &ContractContext,
+    state: &ContractState,
+    domain: &str,
+    storage_quota_kb: u32,
+) {
+    assert!(
+        state.pns.is_active(domain, ctx.block_production_time),
+        "{}",
+        ContractError::DomainNotActive
+    );
+    assert!(
+        storage_quota_kb <= state.pns.config.max_storage_quota_kb
+            && state.pns.get_storage_used(domain) as u64 <= storage_quota_bytes(storage_quota_kb),
+        "{}",
+        ContractError::InvalidStorageQuota
+    );
+}
+
 fn assert_contract_enabled(state: &ContractState) {
     assert!(
         state.config.contract_enabled,
@@ -689,6 +2196,110 @@ fn assert_contract_enabled(state: &ContractState) {
     );
 }
 
+fn assert_launch_schedule(config: &ContractConfig) {
+    if let Some(schedule) = &config.launch_schedule {
+        assert!(
+            schedule.is_valid(),
+            "{}",
+            ContractError::InvalidLaunchSchedule
+        );
+    }
+}
+
+fn assert_subdomain_sale_fee(config: &ContractConfig) {
+    assert!(
+        config.subdomain_sale_fee_bps <= 10_000,
+        "{}",
+        ContractError::InvalidSubdomainSaleFee
+    );
+}
+
+fn assert_domain_access(ctx: &ContractContext, state: &ContractState, domain: &str) {
+    let token_id = state.pns.get_token_id(domain);
+    assert!(token_id.is_some(), "{}", ContractError::DomainNotMinted);
+    assert!(
+        state
+            .nft
+            .is_approved_or_owner(ctx.sender, token_id.unwrap()),
+        "{}",
+        ContractError::Unauthorized
+    );
+}
+
+/// Checks that the sender can pay the upgrade of the domain to the premium tier
+/// The sender must own or be approved for the domain, and the tier must cost more than the current one
+fn assert_premium_tier_upgrade(
+    ctx: &ContractContext,
+    state: &ContractState,
+    domain: &str,
+    premium_tier: u32,
+) -> PremiumTier {
+    assert_domain_access(ctx, state, domain);
+
+    let tier = state.pns.config.get_premium_tier(premium_tier);
+    assert!(tier.is_some(), "{}", ContractError::InvalidPremiumTier);
+
+    let current_price = state
+        .pns
+        .get_domain(domain)
+        .and_then(|domain| domain.premium_tier)
+        .and_then(|tier| state.pns.config.get_premium_tier(tier))
+        .map_or(0, |tier| tier.price);
+    let tier = tier.unwrap();
+    assert!(
+        tier.price > current_price,
+        "{}",
+        ContractError::PremiumTierNotUpgrade
+    );
+
+    tier.clone()
+}
+
+/// Checks if the sender controls the subdomain through its parent domain
+fn has_parent_control(ctx: &ContractContext, state: &ContractState, domain: &str) -> bool {
+    let subdomain = state.pns.get_domain(domain);
+    assert!(subdomain.is_some(), "{}", ContractError::DomainNotMinted);
+
+    !subdomain.unwrap().has_fuses(PARENT_CANNOT_CONTROL)
+        && is_parent_approved_or_owner(ctx, state, domain)
+}
+
+/// Checks if the sender is the owner or an approved address of the parent domain
+fn is_parent_approved_or_owner(ctx: &ContractContext, state: &ContractState, domain: &str) -> bool {
+    let subdomain = state.pns.get_domain(domain);
+    assert!(subdomain.is_some(), "{}", ContractError::DomainNotMinted);
+
+    match subdomain.unwrap().parent_id {
+        Some(parent_id) => {
+            let parent_token_id = state.pns.get_token_id(&parent_id).unwrap();
+            state.nft.is_approved_or_owner(ctx.sender, parent_token_id)
+        }
+        None => false,
+    }
+}
+
+fn assert_parent_control(ctx: &ContractContext, state: &ContractState, domain: &str) {
+    let subdomain = state.pns.get_domain(domain);
+    assert!(subdomain.is_some(), "{}", ContractError::DomainNotMinted);
+
+    let subdomain = subdomain.unwrap();
+    assert!(
+        subdomain.parent_id.is_some(),
+        "{}",
+        ContractError::NotASubdomain
+    );
+    assert!(
+        !subdomain.has_fuses(PARENT_CANNOT_CONTROL),
+        "{}",
+        ContractError::ParentCannotControl
+    );
+    assert!(
+        has_parent_control(ctx, state, domain),
+        "{}",
+        ContractError::Unauthorized
+    );
+}
+
 fn assert_and_get_payment_info(config: &ContractConfig, payment_coin_id: u64) -> PaymentInfo {
     let payment_info = config.get_payment_info(payment_coin_id);
     assert!(
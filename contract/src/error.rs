@@ -30,6 +30,27 @@ pub enum ContractError {
     #[error("Mint count limit reached")]
     MintCountLimitReached,
 
+    #[error("The supply cap for the domain length has been reached")]
+    SupplyCapReached,
+
+    #[error("The launch has not started yet")]
+    LaunchNotStarted,
+
+    #[error("Only the claimant of a reserved domain can mint it during the sunrise")]
+    UserNotSunriseClaimant,
+
+    #[error("The launch schedule is not valid")]
+    InvalidLaunchSchedule,
+
+    #[error("The specified domain is reserved by an auction")]
+    DomainInAuction,
+
+    #[error("The specified domain is not in auction")]
+    DomainNotInAuction,
+
+    #[error("The specified domain can only be minted through an auction")]
+    DomainIsPremium,
+
     #[error("The specified domain is not active")]
     DomainNotActive,
 
@@ -38,4 +59,52 @@ pub enum ContractError {
 
     #[error("Domain not valid for airdrop")]
     AirdropNotValid,
+
+    #[error("The subdomain sale fee is not valid")]
+    InvalidSubdomainSaleFee,
+
+    #[error("The parent domain has no subdomain available for sale")]
+    SubdomainSaleNotAvailable,
+
+    #[error("The specified domain is not a subdomain")]
+    NotASubdomain,
+
+    #[error("The subdomains cannot be renewed, their expiration is set by the parent")]
+    CannotRenewSubdomain,
+
+    #[error("The specified domain cannot be transferred")]
+    CannotTransfer,
+
+    #[error("The specified domain cannot create subdomains")]
+    CannotCreateSubdomain,
+
+    #[error("The parent domain cannot control the subdomain")]
+    ParentCannotControl,
+
+    #[error("The maximum subdomain depth has been reached")]
+    MaxSubdomainDepthReached,
+
+    #[error("The maximum subdomains of the parent domain has been reached")]
+    MaxSubdomainsReached,
+
+    #[error("The maximum domains of a resolve batch has been reached")]
+    MaxResolveBatchSize,
+
+    #[error("The name system config is not valid")]
+    InvalidPnsConfig,
+
+    #[error("The premium tier is not valid")]
+    InvalidPremiumTier,
+
+    #[error("The premium tier must be an upgrade of the current one")]
+    PremiumTierNotUpgrade,
+
+    #[error("The storage quota is not valid")]
+    InvalidStorageQuota,
+
+    #[error("The landrush is not open")]
+    LandrushNotOpen,
+
+    #[error("The domains can only be applied for during the landrush")]
+    LandrushApplicationRequired,
 }
@@ -5,7 +5,9 @@ use read_write_rpc_derive::ReadWriteRPC;
 use rpc_msg_derive::IntoShortnameRPCEvent;
 use utils::events::IntoShortnameRPCEvent;
 
-use crate::state::ContractConfig;
+use partisia_name_system::state::{DnsRecord, LargeRecordCommit, RecordClass, RecordHistory};
+
+use crate::state::{ContractConfig, LaunchPhase};
 
 /// This structure describes fields for PNS initialize msg
 #[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
@@ -47,6 +49,15 @@ pub struct MPC20TransferFromMsg {
     pub amount: u128,
 }
 
+#[derive(ReadWriteRPC, CreateTypeSpec, IntoShortnameRPCEvent, Clone, PartialEq, Eq, Debug)]
+#[rpc_msg(action = 0x01)]
+pub struct MPC20TransferMsg {
+    /// token receiver
+    pub to: Address,
+    /// amount to transfer
+    pub amount: u128,
+}
+
 /// This structure describes fields for renew msg
 #[derive(ReadWriteRPC, CreateTypeSpec, IntoShortnameRPCEvent, Clone, PartialEq, Eq, Debug)]
 #[rpc_msg(action = 0x26)]
@@ -58,6 +69,44 @@ pub struct RenewDomainMsg {
     pub subscription_years: u32,
 }
 
+/// This structure describes fields for the subdomain buy msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct BuySubdomainMsg {
+    pub domain: String,
+    pub parent_id: String,
+    /// receiver address
+    pub to: Address,
+    /// optional token_uri
+    pub token_uri: Option<String>,
+    /// buyer address, refunded when the subdomain cannot be sold anymore
+    pub payer: Address,
+    /// escrowed price of the subdomain
+    pub price: u128,
+    pub payment_coin_id: u64,
+}
+
+/// This structure describes fields for the premium tier upgrade msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct UpgradePremiumTierMsg {
+    pub domain: String,
+    /// Index of the premium tier in the config
+    pub premium_tier: u32,
+    pub payer: Address,
+    /// BYOC token id
+    pub payment_coin_id: u64,
+}
+
+/// This structure describes fields for the storage quota msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct SetStorageQuotaMsg {
+    pub domain: String,
+    /// Large record storage quota in KB
+    pub storage_quota_kb: u32,
+    pub payer: Address,
+    /// BYOC token id
+    pub payment_coin_id: u64,
+}
+
 // Events structs
 
 /// Struct for owner info event
@@ -69,3 +118,175 @@ pub struct OwnerInfoEvent {
     pub domain_count: u128,
     pub total_supply: u128,
 }
+
+/// Struct for launch phase event
+/// Copy this struct to the external contracts that are using this action
+/// to be able to decode the event data
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct LaunchPhaseEvent {
+    pub phase: Option<LaunchPhase>,
+    /// Unix millis timestamp of the next phase start
+    pub next_phase_at: Option<i64>,
+}
+
+/// Struct for subdomains event
+/// Copy this struct to the external contracts that are using this action
+/// to be able to decode the event data
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct SubdomainsEvent {
+    pub domain: String,
+    pub subdomains: Vec<String>,
+    /// Total count of the direct subdomains
+    pub total: u32,
+}
+
+/// Struct for address event
+/// Copy this struct to the external contracts that are using this action
+/// to be able to decode the event data
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct AddressEvent {
+    pub domain: String,
+    /// SLIP-44 coin type
+    pub coin_type: u32,
+    pub address: Option<Vec<u8>>,
+}
+
+/// Struct for domain status event
+/// Copy this struct to the external contracts that are using this action
+/// to be able to decode the event data
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct DomainStatusEvent {
+    pub domain: String,
+    pub exists: bool,
+    pub owner: Option<Address>,
+    /// Address approved to manage the domain token
+    pub approved: Option<Address>,
+    pub active: bool,
+    /// Unix millis timestamp, capped by the expiration of the parents
+    pub expires_at: Option<i64>,
+}
+
+/// Struct for domain owner check event
+/// Copy this struct to the external contracts that are using this action
+/// to be able to decode the event data
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct DomainOwnerCheckEvent {
+    pub status: DomainStatusEvent,
+    pub address: Address,
+    pub is_owner: bool,
+    /// The address is approved for the domain token or as operator of the owner
+    pub is_approved: bool,
+}
+
+/// Struct for large record event
+/// Copy this struct to the external contracts that are using this action
+/// to be able to decode the event data
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct LargeRecordEvent {
+    pub domain: String,
+    pub key: String,
+    /// `None` until the large record is committed
+    pub data: Option<Vec<u8>>,
+    /// Length and keccak256 hash to verify the data against
+    pub commit: Option<LargeRecordCommit>,
+}
+
+/// Struct for record history event
+/// Copy this struct to the external contracts that are using this action
+/// to be able to decode the event data
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct RecordHistoryEvent {
+    pub domain: String,
+    pub class: RecordClass,
+    pub data: Option<Vec<u8>>,
+    /// `None` if the record was never changed
+    pub history: Option<RecordHistory>,
+}
+
+/// Struct for custom record history event
+/// Copy this struct to the external contracts that are using this action
+/// to be able to decode the event data
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct CustomRecordHistoryEvent {
+    pub domain: String,
+    pub key: String,
+    pub data: Option<Vec<u8>>,
+    /// `None` if the custom record was never changed
+    pub history: Option<RecordHistory>,
+}
+
+/// Compact resolution of a domain in a batch
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct BatchResolvedDomainInfo {
+    pub domain: String,
+    /// Owner of the domain, or of the wildcard ancestor for unminted domains
+    pub owner: Option<Address>,
+    /// Unix millis timestamp, capped by the expiration of the parents
+    pub expires_at: Option<i64>,
+    pub active: bool,
+    /// Data of the selected record classes, in the order of the selection
+    pub records: Vec<Option<Vec<u8>>>,
+}
+
+/// Struct for batch resolve event
+/// Copy this struct to the external contracts that are using this action
+/// to be able to decode the event data
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct BatchResolvedEvent {
+    pub domains: Vec<BatchResolvedDomainInfo>,
+}
+
+/// Owner, expiration and active status of a resolved domain
+/// An unminted domain resolved through a wildcard reports the status of the wildcard ancestor
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct ResolvedDomainInfo {
+    pub domain: String,
+    /// Domain holding the records once aliases and wildcards are followed,
+    /// `None` if the domain does not resolve
+    pub resolved_domain: Option<String>,
+    pub owner: Option<Address>,
+    /// Unix millis timestamp, capped by the expiration of the parents
+    pub expires_at: Option<i64>,
+    pub active: bool,
+}
+
+/// Struct for resolved record event
+/// Copy this struct to the external contracts that are using this action
+/// to be able to decode the event data
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct ResolvedRecordEvent {
+    pub info: ResolvedDomainInfo,
+    pub data: Option<Vec<u8>>,
+}
+
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct ResolvedRecord {
+    pub class: RecordClass,
+    pub data: Vec<u8>,
+}
+
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct ResolvedCustomRecord {
+    pub key: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct ResolvedAddress {
+    /// SLIP-44 coin type
+    pub coin_type: u32,
+    pub address: Vec<u8>,
+}
+
+/// Struct for resolved domain event, holding all the records of the resolved domain
+/// Copy this struct to the external contracts that are using this action
+/// to be able to decode the event data
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct ResolvedDomainEvent {
+    pub info: ResolvedDomainInfo,
+    pub records: Vec<ResolvedRecord>,
+    pub custom_records: Vec<ResolvedCustomRecord>,
+    pub addresses: Vec<ResolvedAddress>,
+    pub content_hash: Option<Vec<u8>>,
+    pub dns_records: Vec<DnsRecord>,
+}
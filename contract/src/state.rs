@@ -1,25 +1,37 @@
 use access_control::state::AccessControlState;
 use airdrop::state::AirdropState;
+use auction::state::AuctionState;
 use contract_version_base::state::ContractVersionBase;
 use create_type_spec_derive::CreateTypeSpec;
 use nft::state::NFTContractState;
-use partisia_name_system::state::PartisiaNameSystemState;
+use partisia_name_system::state::{
+    Domain, PartisiaNameSystemState, RecordClass, CANNOT_CREATE_SUBDOMAIN, CANNOT_TRANSFER,
+    PARENT_CANNOT_CONTROL,
+};
 use pbc_contract_common::{address::Address, avl_tree_map::AvlTreeMap};
 use read_write_rpc_derive::ReadWriteRPC;
 use read_write_state_derive::ReadWriteState;
 
 #[allow(unused_imports)]
 use crate::contract::__PBC_IS_ZK_CONTRACT;
+use crate::msg::{
+    BatchResolvedDomainInfo, BatchResolvedEvent, BuySubdomainMsg, DomainOwnerCheckEvent,
+    DomainStatusEvent, ResolvedAddress, ResolvedCustomRecord, ResolvedDomainEvent,
+    ResolvedDomainInfo, ResolvedRecord, ResolvedRecordEvent,
+};
 
 #[state]
 #[derive(Default, Debug)]
 pub struct ContractState {
     pub access_control: AccessControlState,
     pub airdrop: AirdropState,
+    pub auction: AuctionState,
     pub config: ContractConfig,
     pub nft: NFTContractState,
     pub pns: PartisiaNameSystemState,
     pub stats: ContractStats,
+    /// Claimants of the domains reserved for the sunrise
+    pub sunrise_reservations: AvlTreeMap<String, Address>,
     pub version: ContractVersionBase,
 }
 
@@ -44,6 +56,40 @@ pub enum UserRole {
     Airdrop {},
 }
 
+#[repr(u8)]
+#[derive(
+    ReadWriteRPC, ReadWriteState, CreateTypeSpec, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug,
+)]
+pub enum LaunchPhase {
+    #[discriminant(0)]
+    Sunrise {},
+    #[discriminant(1)]
+    Landrush {},
+    #[discriminant(2)]
+    GeneralAvailability {},
+}
+
+/// What happens to the subdomains when their parent domain is transferred
+#[repr(u8)]
+#[derive(ReadWriteRPC, ReadWriteState, CreateTypeSpec, PartialEq, Eq, Copy, Clone, Debug)]
+pub enum SubdomainTransferPolicy {
+    /// The subdomains stay with their owners
+    #[discriminant(0)]
+    Keep {},
+    /// The subdomains owned by the previous owner are transferred to the new owner
+    #[discriminant(1)]
+    TransferAlong {},
+    /// The subdomains controlled by the parent are transferred to the new owner
+    #[discriminant(2)]
+    Revoke {},
+}
+
+impl Default for SubdomainTransferPolicy {
+    fn default() -> Self {
+        SubdomainTransferPolicy::Keep {}
+    }
+}
+
 #[repr(C)]
 #[derive(ReadWriteRPC, ReadWriteState, CreateTypeSpec, PartialEq, Eq, Default, Clone, Debug)]
 pub struct Fee {
@@ -64,16 +110,320 @@ pub struct Fees {
 pub struct ContractConfig {
     pub airdrop_min_domain_length: u32,
     pub contract_enabled: bool,
+    pub launch_schedule: Option<LaunchSchedule>,
+    /// Maximum depth of the subdomains, 0 means no limit
+    pub max_subdomain_depth: u32,
+    /// Maximum direct subdomains of a domain, 0 means no limit
+    pub max_subdomains_per_domain: u32,
     pub mint_count_limit_enabled: bool,
     pub mint_count_limit: u32,
+    /// Sliding window of the mint count limit in milliseconds, 0 means the count never resets
+    pub mint_count_window: i64,
+    /// Limits overriding the default mint count limit for users with the given role
+    pub mint_count_role_limits: Vec<MintCountLimit>,
     pub payment_info: Vec<PaymentInfo>,
+    /// Protocol cut of the subdomain sales in basis points, paid to the payment receiver
+    pub subdomain_sale_fee_bps: u32,
+    pub subdomain_transfer_policy: SubdomainTransferPolicy,
+    pub supply_caps: Vec<SupplyCap>,
     pub whitelist_enabled: bool,
 }
 
+/// Unix millis timestamps at which each launch phase starts
+/// Only the claimants of the reserved domains can mint them during the sunrise
+/// During the landrush the domains are applied for with sealed bids,
+/// settled as auctions once the general availability starts
+#[repr(C)]
+#[derive(ReadWriteRPC, ReadWriteState, CreateTypeSpec, PartialEq, Eq, Clone, Debug)]
+pub struct LaunchSchedule {
+    pub sunrise_at: i64,
+    pub landrush_at: i64,
+    pub general_availability_at: i64,
+    /// Multiplier applied to the mint fees during the landrush, the reserve price of the applications
+    pub landrush_fees_multiplier: u32,
+    /// Reveal period of the landrush applications in milliseconds, from the general availability
+    pub landrush_reveal_period: i64,
+    /// Share of the deposit forfeited by the unrevealed landrush applications, in basis points
+    pub landrush_unrevealed_penalty_bps: u32,
+}
+
+/// Maximum amount of domains with the given chars count that can be minted
+/// When the phase is set, only the domains minted during that phase are counted
+#[repr(C)]
+#[derive(ReadWriteRPC, ReadWriteState, CreateTypeSpec, PartialEq, Eq, Clone, Debug)]
+pub struct SupplyCap {
+    pub chars_count: u32,
+    pub phase: Option<LaunchPhase>,
+    pub cap: u32,
+}
+
+#[repr(C)]
+#[derive(ReadWriteRPC, ReadWriteState, CreateTypeSpec, PartialEq, Eq, Clone, Debug)]
+pub struct MintCountLimit {
+    pub role: UserRole,
+    pub limit: u32,
+    /// Sliding window of the limit in milliseconds, 0 means the count never resets
+    pub window: i64,
+}
+
 #[repr(C)]
 #[derive(ReadWriteState, CreateTypeSpec, Default, Debug)]
 pub struct ContractStats {
     pub mint_count: AvlTreeMap<Address, u32>,
+    pub mint_windows: AvlTreeMap<Address, MintWindow>,
+    pub supply: AvlTreeMap<SupplyKey, u32>,
+}
+
+/// Key of the minted domains counters, a `None` phase counts the domains of all phases
+#[repr(C)]
+#[derive(ReadWriteState, CreateTypeSpec, PartialEq, Eq, PartialOrd, Ord, Clone, Debug)]
+pub struct SupplyKey {
+    pub chars_count: u32,
+    pub phase: Option<LaunchPhase>,
+}
+
+#[repr(C)]
+#[derive(ReadWriteState, CreateTypeSpec, PartialEq, Eq, Clone, Debug)]
+pub struct MintWindow {
+    /// Unix millis timestamps of the mints within the last window
+    pub minted_at: Vec<i64>,
+}
+
+impl ContractState {
+    /// Returns the mint count limit and window of the address
+    /// The first role limit matching one of the address roles overrides the default limit
+    pub fn get_mint_count_limit(&self, address: &Address) -> (u32, i64) {
+        self.config
+            .mint_count_role_limits
+            .iter()
+            .find(|limit| self.access_control.has_role(limit.role as u8, address))
+            .map(|limit| (limit.limit, limit.window))
+            .unwrap_or((self.config.mint_count_limit, self.config.mint_count_window))
+    }
+
+    /// Checks if the subdomain can still be sold at the escrowed price, without panicking
+    pub fn can_sell_subdomain(&self, msg: &BuySubdomainMsg, unix_millis_now: i64) -> bool {
+        let Some(sale) = self.pns.get_subdomain_sale(&msg.parent_id) else {
+            return false;
+        };
+
+        let config = &self.config;
+        config.contract_enabled
+            && sale.is_available()
+            && sale.price == msg.price
+            && sale.payment_coin_id == msg.payment_coin_id
+            && !self.pns.is_minted(&msg.domain)
+            && !self.auction.is_in_auction(&msg.domain)
+            && !self.auction.is_premium(&msg.domain)
+            && self.pns.is_active(&msg.parent_id, unix_millis_now)
+            && !self.pns.has_fuses(&msg.parent_id, CANNOT_CREATE_SUBDOMAIN)
+            && (config.max_subdomain_depth == 0
+                || self.pns.get_depth(&msg.parent_id) < config.max_subdomain_depth)
+            && (config.max_subdomains_per_domain == 0
+                || (self.pns.get_children(&msg.parent_id).len() as u32)
+                    < config.max_subdomains_per_domain)
+    }
+
+    /// Returns the subdomains transferred along with the domain, given the subdomain transfer policy
+    /// The subdomains with the transfer fuse burned stay, along with their own subdomains
+    pub fn get_subdomains_to_transfer(&self, domain: &str, from: &Address) -> Vec<String> {
+        let mut subdomains = vec![];
+        let mut pending = self.pns.get_children(domain);
+
+        while let Some(subdomain) = pending.pop() {
+            if self.pns.has_fuses(&subdomain, CANNOT_TRANSFER) {
+                continue;
+            }
+
+            let moves_along = match self.config.subdomain_transfer_policy {
+                SubdomainTransferPolicy::Keep {} => false,
+                SubdomainTransferPolicy::TransferAlong {} => {
+                    let token_id = self.pns.get_token_id(&subdomain).unwrap();
+                    self.nft.owner_of(token_id) == *from
+                }
+                SubdomainTransferPolicy::Revoke {} => {
+                    !self.pns.has_fuses(&subdomain, PARENT_CANNOT_CONTROL)
+                }
+            };
+
+            if moves_along {
+                pending.extend(self.pns.get_children(&subdomain));
+                subdomains.push(subdomain);
+            }
+        }
+
+        subdomains
+    }
+
+    /// Resolves the record of the given class of the domain
+    pub fn resolve_record(
+        &self,
+        domain: &str,
+        class: &RecordClass,
+        unix_millis_now: i64,
+    ) -> ResolvedRecordEvent {
+        let (info, resolved) = self.resolve_domain(domain, unix_millis_now);
+
+        ResolvedRecordEvent {
+            info,
+            data: resolved.and_then(|resolved| resolved.get_record(class).cloned()),
+        }
+    }
+
+    /// Resolves the custom record of the given key of the domain
+    pub fn resolve_custom_record(
+        &self,
+        domain: &str,
+        key: &str,
+        unix_millis_now: i64,
+    ) -> ResolvedRecordEvent {
+        let (info, resolved) = self.resolve_domain(domain, unix_millis_now);
+
+        ResolvedRecordEvent {
+            info,
+            data: resolved.and_then(|resolved| resolved.get_custom_record(key).cloned()),
+        }
+    }
+
+    /// Resolves all the records of the domain
+    pub fn resolve_all(&self, domain: &str, unix_millis_now: i64) -> ResolvedDomainEvent {
+        let (info, resolved) = self.resolve_domain(domain, unix_millis_now);
+        ResolvedDomainEvent {
+            info,
+            records: resolved
+                .iter()
+                .flat_map(|resolved| resolved.records.iter())
+                .map(|(class, data)| ResolvedRecord {
+                    class: *class,
+                    data: data.clone(),
+                })
+                .collect(),
+            custom_records: resolved
+                .iter()
+                .flat_map(|resolved| resolved.custom_records.iter())
+                .map(|(key, data)| ResolvedCustomRecord {
+                    key: key.clone(),
+                    data: data.clone(),
+                })
+                .collect(),
+            addresses: resolved
+                .iter()
+                .flat_map(|resolved| resolved.addresses.iter())
+                .map(|(coin_type, address)| ResolvedAddress {
+                    coin_type: *coin_type,
+                    address: address.clone(),
+                })
+                .collect(),
+            content_hash: resolved
+                .as_ref()
+                .and_then(|resolved| resolved.content_hash.clone()),
+            dns_records: resolved
+                .map(|resolved| resolved.dns_records)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Resolves the selected record classes of many domains at once
+    pub fn resolve_batch(
+        &self,
+        domains: &[String],
+        classes: &[RecordClass],
+        unix_millis_now: i64,
+    ) -> BatchResolvedEvent {
+        BatchResolvedEvent {
+            domains: self
+                .pns
+                .resolve_batch(domains, classes, unix_millis_now)
+                .into_iter()
+                .map(|resolved| BatchResolvedDomainInfo {
+                    domain: resolved.domain,
+                    owner: resolved
+                        .token_id
+                        .map(|token_id| self.nft.owner_of(token_id)),
+                    expires_at: resolved.expires_at,
+                    active: resolved.active,
+                    records: resolved.records,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the status of the domain, without panicking when it is not minted
+    pub fn domain_status(&self, domain: &str, unix_millis_now: i64) -> DomainStatusEvent {
+        let token_id = self.pns.get_token_id(domain);
+
+        DomainStatusEvent {
+            domain: domain.to_string(),
+            exists: token_id.is_some(),
+            owner: token_id.map(|token_id| self.nft.owner_of(token_id)),
+            approved: token_id.and_then(|token_id| self.nft.get_approved(token_id)),
+            active: self.pns.is_active(domain, unix_millis_now),
+            expires_at: self.pns.get_expires_at(domain),
+        }
+    }
+
+    /// Checks whether the address owns or is approved for the domain, without panicking
+    pub fn check_domain_owner(
+        &self,
+        domain: &str,
+        address: Address,
+        unix_millis_now: i64,
+    ) -> DomainOwnerCheckEvent {
+        let status = self.domain_status(domain, unix_millis_now);
+        let is_owner = status.owner == Some(address);
+        let is_approved = status.approved == Some(address)
+            || status
+                .owner
+                .is_some_and(|owner| self.nft.is_approved_for_all(owner, address));
+
+        DomainOwnerCheckEvent {
+            status,
+            address,
+            is_owner,
+            is_approved,
+        }
+    }
+
+    /// Returns the status of the domain along with the domain holding its records
+    fn resolve_domain(
+        &self,
+        domain: &str,
+        unix_millis_now: i64,
+    ) -> (ResolvedDomainInfo, Option<Domain>) {
+        let resolved = self.pns.resolve(domain, unix_millis_now);
+
+        let status_domain = self.pns.get_status_domain(domain);
+        let owner = status_domain
+            .as_ref()
+            .and_then(|status_domain| self.pns.get_token_id(status_domain))
+            .map(|token_id| self.nft.owner_of(token_id));
+
+        let info = ResolvedDomainInfo {
+            domain: domain.to_string(),
+            resolved_domain: resolved.as_ref().map(|(name, _)| name.clone()),
+            owner,
+            expires_at: status_domain
+                .as_ref()
+                .and_then(|status_domain| self.pns.get_expires_at(status_domain)),
+            active: status_domain
+                .as_ref()
+                .is_some_and(|status_domain| self.pns.is_active(status_domain, unix_millis_now)),
+        };
+
+        (info, resolved.map(|(_, domain)| domain))
+    }
+
+    /// Checks if the domain is reserved for the address during the sunrise
+    pub fn is_sunrise_claimant(&self, domain: &str, address: &Address) -> bool {
+        self.sunrise_reservations.get(&domain.to_string()) == Some(*address)
+    }
+
+    /// Checks that none of the supply caps of the domain length is reached
+    pub fn is_supply_available(&self, chars_count: u32, phase: Option<LaunchPhase>) -> bool {
+        self.config
+            .get_supply_caps(chars_count, phase)
+            .all(|supply_cap| self.stats.get_supply(chars_count, supply_cap.phase) < supply_cap.cap)
+    }
 }
 
 impl ContractConfig {
@@ -86,26 +436,176 @@ impl ContractConfig {
 
         None
     }
+
+    /// Returns the launch phase at the given time
+    /// No phase is returned when there is no launch schedule or the launch has not started
+    pub fn get_launch_phase(&self, unix_millis_now: i64) -> Option<LaunchPhase> {
+        self.launch_schedule
+            .as_ref()
+            .and_then(|schedule| schedule.get_phase(unix_millis_now))
+    }
+
+    /// Checks if the domains can be minted by the users at the given time
+    pub fn is_launched(&self, unix_millis_now: i64) -> bool {
+        match &self.launch_schedule {
+            Some(schedule) => unix_millis_now >= schedule.sunrise_at,
+            None => true,
+        }
+    }
+
+    pub fn get_fees_multiplier(&self, unix_millis_now: i64) -> u128 {
+        match (
+            &self.launch_schedule,
+            self.get_launch_phase(unix_millis_now),
+        ) {
+            (Some(schedule), Some(LaunchPhase::Landrush {})) => {
+                schedule.landrush_fees_multiplier as u128
+            }
+            _ => 1,
+        }
+    }
+
+    /// Returns the protocol cut of a subdomain sold at the given price
+    pub fn get_subdomain_sale_fee(&self, price: u128) -> u128 {
+        price * self.subdomain_sale_fee_bps as u128 / 10_000
+    }
+
+    /// Returns the supply caps applying to a domain minted during the given phase
+    pub fn get_supply_caps(
+        &self,
+        chars_count: u32,
+        phase: Option<LaunchPhase>,
+    ) -> impl Iterator<Item = &SupplyCap> {
+        self.supply_caps.iter().filter(move |supply_cap| {
+            supply_cap.chars_count == chars_count
+                && (supply_cap.phase.is_none() || supply_cap.phase == phase)
+        })
+    }
+}
+
+impl LaunchSchedule {
+    pub fn get_phase(&self, unix_millis_now: i64) -> Option<LaunchPhase> {
+        if unix_millis_now >= self.general_availability_at {
+            Some(LaunchPhase::GeneralAvailability {})
+        } else if unix_millis_now >= self.landrush_at {
+            Some(LaunchPhase::Landrush {})
+        } else if unix_millis_now >= self.sunrise_at {
+            Some(LaunchPhase::Sunrise {})
+        } else {
+            None
+        }
+    }
+
+    /// Returns the start of the next phase, if any
+    pub fn get_next_phase_at(&self, unix_millis_now: i64) -> Option<i64> {
+        [
+            self.sunrise_at,
+            self.landrush_at,
+            self.general_availability_at,
+        ]
+        .into_iter()
+        .find(|phase_at| unix_millis_now < *phase_at)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.sunrise_at <= self.landrush_at
+            && self.landrush_at <= self.general_availability_at
+            && self.landrush_fees_multiplier > 0
+            && self.landrush_reveal_period > 0
+            && self.landrush_unrevealed_penalty_bps <= 10_000
+    }
 }
 
 impl ContractStats {
-    pub fn increase_mint_count(&mut self, address: Address) {
+    /// Returns the mint count of the address within the window sliding up to now
+    /// Without a window, the lifetime mint count is returned
+    pub fn get_window_mint_count(
+        &self,
+        address: &Address,
+        window: i64,
+        unix_millis_now: i64,
+    ) -> u32 {
+        if window == 0 {
+            return self.mint_count.get(address).unwrap_or(0);
+        }
+
+        self.mint_windows
+            .get(address)
+            .map(|mint_window| mint_window.count(window, unix_millis_now))
+            .unwrap_or(0)
+    }
+
+    /// Counts a mint of the address, dropping the mints that left the window
+    pub fn increase_mint_count(&mut self, address: Address, window: i64, unix_millis_now: i64) {
         let count = self.mint_count.get(&address).unwrap_or(0);
         self.mint_count.insert(address, count + 1);
+
+        if window == 0 {
+            return;
+        }
+
+        let mut minted_at = self
+            .mint_windows
+            .get(&address)
+            .map(|mint_window| mint_window.minted_at)
+            .unwrap_or_default();
+        minted_at.retain(|minted_at| MintWindow::is_within(*minted_at, window, unix_millis_now));
+        minted_at.push(unix_millis_now);
+
+        self.mint_windows.insert(address, MintWindow { minted_at });
+    }
+
+    pub fn get_supply(&self, chars_count: u32, phase: Option<LaunchPhase>) -> u32 {
+        self.supply
+            .get(&SupplyKey { chars_count, phase })
+            .unwrap_or(0)
+    }
+
+    /// Counts a minted domain both in the total and in the phase supply
+    pub fn increase_supply(&mut self, chars_count: u32, phase: Option<LaunchPhase>) {
+        let mut keys = vec![SupplyKey {
+            chars_count,
+            phase: None,
+        }];
+        if phase.is_some() {
+            keys.push(SupplyKey { chars_count, phase });
+        }
+
+        for key in keys {
+            let supply = self.supply.get(&key).unwrap_or(0);
+            self.supply.insert(key, supply + 1);
+        }
+    }
+}
+
+impl MintWindow {
+    /// Counts the mints within the window ending now
+    pub fn count(&self, window: i64, unix_millis_now: i64) -> u32 {
+        self.minted_at
+            .iter()
+            .filter(|minted_at| Self::is_within(**minted_at, window, unix_millis_now))
+            .count() as u32
+    }
+
+    fn is_within(minted_at: i64, window: i64, unix_millis_now: i64) -> bool {
+        unix_millis_now < minted_at + window
     }
 }
 
 impl Fees {
-    pub fn get(&self, domain: &str) -> u128 {
-        let decimals = 10_u128.pow(self.decimals);
+    /// Scales a whole token amount by the decimals of the fees
+    pub fn scale(&self, amount: u128) -> u128 {
+        amount * 10_u128.pow(self.decimals)
+    }
 
+    pub fn get(&self, domain: &str) -> u128 {
         let chars_count = domain.chars().count() as u32;
         for fee in &self.mapping {
             if fee.chars_count == chars_count {
-                return fee.amount * decimals;
+                return self.scale(fee.amount);
             }
         }
 
-        self.default_fee * decimals
+        self.scale(self.default_fee)
     }
 }
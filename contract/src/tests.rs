@@ -1,4 +1,9 @@
-use crate::state::{Fee, Fees};
+use pbc_contract_common::avl_tree_map::AvlTreeMap;
+use utils::{tests::mock_address, time::milliseconds_in_years};
+
+use crate::state::{
+    ContractConfig, ContractStats, Fee, Fees, LaunchPhase, LaunchSchedule, SupplyCap,
+};
 
 #[test]
 fn test_mint_fees() {
@@ -44,3 +49,194 @@ fn test_mint_fees() {
         assert_eq!(fees, fee);
     }
 }
+
+#[test]
+fn test_mint_window_count() {
+    let address = mock_address(1);
+    let window = milliseconds_in_years(1);
+    let mut stats = ContractStats {
+        mint_count: AvlTreeMap::new(),
+        mint_windows: AvlTreeMap::new(),
+        supply: AvlTreeMap::new(),
+    };
+
+    assert_eq!(stats.get_window_mint_count(&address, window, 0), 0);
+
+    stats.increase_mint_count(address, window, 0);
+    stats.increase_mint_count(address, window, window - 1);
+
+    assert_eq!(stats.get_window_mint_count(&address, window, window - 1), 2);
+    // The window slides, only the first mint left it
+    assert_eq!(stats.get_window_mint_count(&address, window, window), 1);
+
+    stats.increase_mint_count(address, window, window);
+
+    assert_eq!(stats.get_window_mint_count(&address, window, window), 2);
+    assert_eq!(
+        stats.get_window_mint_count(&address, window, 2 * window - 1),
+        1
+    );
+    assert_eq!(stats.get_window_mint_count(&address, window, 2 * window), 0);
+    assert_eq!(stats.mint_count.get(&address), Some(3));
+
+    // The mints that left the window are dropped
+    let mint_window = stats.mint_windows.get(&address).unwrap();
+    assert_eq!(mint_window.minted_at, vec![window - 1, window]);
+}
+
+#[test]
+fn test_mint_window_count_without_window() {
+    let address = mock_address(1);
+    let mut stats = ContractStats {
+        mint_count: AvlTreeMap::new(),
+        mint_windows: AvlTreeMap::new(),
+        supply: AvlTreeMap::new(),
+    };
+
+    stats.increase_mint_count(address, 0, 0);
+    stats.increase_mint_count(address, 0, milliseconds_in_years(10));
+
+    assert_eq!(
+        stats.get_window_mint_count(&address, 0, milliseconds_in_years(100)),
+        2
+    );
+}
+
+#[test]
+fn test_supply_caps() {
+    let config = ContractConfig {
+        supply_caps: vec![
+            SupplyCap {
+                chars_count: 3,
+                phase: None,
+                cap: 500,
+            },
+            SupplyCap {
+                chars_count: 3,
+                phase: Some(LaunchPhase::Sunrise {}),
+                cap: 100,
+            },
+            SupplyCap {
+                chars_count: 4,
+                phase: None,
+                cap: 1000,
+            },
+        ],
+        ..ContractConfig::default()
+    };
+
+    let caps = |chars_count, phase| {
+        config
+            .get_supply_caps(chars_count, phase)
+            .map(|supply_cap| supply_cap.cap)
+            .collect::<Vec<u32>>()
+    };
+
+    assert_eq!(caps(3, Some(LaunchPhase::Sunrise {})), vec![500, 100]);
+    assert_eq!(caps(3, Some(LaunchPhase::Landrush {})), vec![500]);
+    assert_eq!(caps(3, None), vec![500]);
+    assert_eq!(caps(5, None), Vec::<u32>::new());
+}
+
+#[test]
+fn test_increase_supply() {
+    let mut stats = ContractStats {
+        mint_count: AvlTreeMap::new(),
+        mint_windows: AvlTreeMap::new(),
+        supply: AvlTreeMap::new(),
+    };
+
+    stats.increase_supply(3, Some(LaunchPhase::Sunrise {}));
+    stats.increase_supply(3, Some(LaunchPhase::Landrush {}));
+    stats.increase_supply(3, None);
+
+    assert_eq!(stats.get_supply(3, None), 3);
+    assert_eq!(stats.get_supply(3, Some(LaunchPhase::Sunrise {})), 1);
+    assert_eq!(stats.get_supply(3, Some(LaunchPhase::Landrush {})), 1);
+    assert_eq!(
+        stats.get_supply(3, Some(LaunchPhase::GeneralAvailability {})),
+        0
+    );
+    assert_eq!(stats.get_supply(4, None), 0);
+}
+
+#[test]
+fn test_launch_schedule() {
+    let schedule = LaunchSchedule {
+        sunrise_at: 100,
+        landrush_at: 200,
+        general_availability_at: 300,
+        landrush_fees_multiplier: 3,
+        landrush_reveal_period: 100,
+        landrush_unrevealed_penalty_bps: 1_000,
+    };
+
+    let phases_tuples = [
+        (0, None, Some(100)),
+        (100, Some(LaunchPhase::Sunrise {}), Some(200)),
+        (199, Some(LaunchPhase::Sunrise {}), Some(200)),
+        (200, Some(LaunchPhase::Landrush {}), Some(300)),
+        (300, Some(LaunchPhase::GeneralAvailability {}), None),
+    ];
+
+    for (now, phase, next_phase_at) in phases_tuples {
+        assert_eq!(schedule.get_phase(now), phase);
+        assert_eq!(schedule.get_next_phase_at(now), next_phase_at);
+    }
+
+    let config = ContractConfig {
+        launch_schedule: Some(schedule),
+        ..ContractConfig::default()
+    };
+
+    assert!(!config.is_launched(99));
+    assert!(config.is_launched(100));
+    assert_eq!(config.get_fees_multiplier(150), 1);
+    assert_eq!(config.get_fees_multiplier(250), 3);
+    assert_eq!(config.get_fees_multiplier(350), 1);
+}
+
+#[test]
+fn test_launch_schedule_validity() {
+    let schedule = LaunchSchedule {
+        sunrise_at: 100,
+        landrush_at: 200,
+        general_availability_at: 300,
+        landrush_fees_multiplier: 3,
+        landrush_reveal_period: 100,
+        landrush_unrevealed_penalty_bps: 1_000,
+    };
+    assert!(schedule.is_valid());
+
+    assert!(!LaunchSchedule {
+        landrush_reveal_period: 0,
+        ..schedule.clone()
+    }
+    .is_valid());
+    assert!(!LaunchSchedule {
+        landrush_unrevealed_penalty_bps: 10_001,
+        ..schedule
+    }
+    .is_valid());
+}
+
+#[test]
+fn test_launch_without_schedule() {
+    let config = ContractConfig::default();
+
+    assert!(config.is_launched(0));
+    assert_eq!(config.get_launch_phase(0), None);
+    assert_eq!(config.get_fees_multiplier(0), 1);
+}
+
+#[test]
+fn test_subdomain_sale_fee() {
+    let config = ContractConfig {
+        subdomain_sale_fee_bps: 250,
+        ..ContractConfig::default()
+    };
+
+    assert_eq!(config.get_subdomain_sale_fee(1000), 25);
+    assert_eq!(config.get_subdomain_sale_fee(10), 0);
+    assert_eq!(ContractConfig::default().get_subdomain_sale_fee(1000), 0);
+}
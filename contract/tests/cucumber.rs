@@ -1,24 +1,61 @@
-use std::{mem::take, panic::catch_unwind};
+use std::{
+    mem::take,
+    net::{Ipv4Addr, Ipv6Addr},
+    panic::catch_unwind,
+};
 
+use airdrop::msg::AirdropCampaignMsg;
+use auction::{
+    msg::{AuctionBidMsg, AuctionCreateMsg},
+    state::bid_commitment,
+};
 use cucumber::{given, then, when, World};
 use meta_names_contract::{
     contract::{
-        add_airdrop, approve_domain, initialize, mint, mint_batch, on_mint_callback,
-        on_renew_subscription_callback, renew_subscription, transfer_domain, update_config,
-        update_user_role,
+        add_airdrop, add_campaign_airdrop, add_merkle_airdrop, apply_landrush_domain,
+        approve_domain, burn_fuses, buy_subdomain, claim_merkle_airdrop, clear_content_hash,
+        commit_auction_bid, commit_large_record, create_airdrop_campaign, create_auction,
+        delete_large_record, initialize, mint, mint_batch, mint_custom_record,
+        on_auction_bid_callback, on_buy_subdomain_callback, on_mint_callback,
+        on_renew_subscription_callback, on_set_storage_quota_callback,
+        on_upgrade_premium_tier_callback, reclaim_subdomain, remove_subdomain_sale,
+        renew_subscription, reveal_auction_bid, revoke_airdrop_campaign, set_address, set_alias,
+        set_content_hash, set_dns_records, set_premium_domain, set_storage_quota,
+        set_subdomain_expiration, set_subdomain_sale, set_sunrise_reservation, set_wildcard,
+        settle_auction, transfer_domain, transfer_domain_with_records, update_config,
+        update_custom_record, update_pns_config, update_user_role, upgrade_premium_tier,
+        write_large_record_chunk,
+    },
+    msg::{
+        BuySubdomainMsg, InitMsg, MintMsg, RenewDomainMsg, SetStorageQuotaMsg,
+        UpgradePremiumTierMsg,
+    },
+    state::{
+        ContractConfig, ContractState, Fees, LaunchPhase, LaunchSchedule, MintCountLimit,
+        PaymentInfo, SubdomainTransferPolicy, SupplyCap, UserRole,
     },
-    msg::{InitMsg, MintMsg, RenewDomainMsg},
-    state::{ContractConfig, ContractState, Fees, PaymentInfo, UserRole},
 };
 use partisia_name_system::{
-    actions::{execute_record_mint, execute_record_update, execute_update_expiration},
-    msg::{PnsDomainUpdateExpirationMsg, PnsRecordMintMsg, PnsRecordUpdateMsg},
-    state::RecordClass,
+    actions::{
+        execute_record_mint, execute_record_update, execute_set_dns_records,
+        execute_update_expiration,
+    },
+    content_hash::{encode_content_hash, ContentProtocol},
+    large_record::{large_record_commit, split_into_chunks},
+    msg::{
+        PnsDomainUpdateExpirationMsg, PnsRecordMintMsg, PnsRecordUpdateMsg, PnsSetDnsRecordsMsg,
+    },
+    state::{
+        DnsRecord, DnsRecordData, PremiumTier, RecordClass, RecordLimits, CANNOT_CREATE_SUBDOMAIN,
+        CANNOT_SET_RECORDS, CANNOT_TRANSFER, COIN_TYPE_BITCOIN, COIN_TYPE_ETHEREUM,
+        COIN_TYPE_PARTISIA, PARENT_CANNOT_CONTROL,
+    },
 };
 use utils::{
     tests::{
         get_address_for_user, mock_address, mock_contract_context,
-        mock_successful_callback_context, ALICE_ADDRESS, PAYMENT_TOKEN_ADDRESS, SYSTEM_ADDRESS,
+        mock_successful_callback_context, unix_epoch_now, ALICE_ADDRESS, PAYMENT_TOKEN_ADDRESS,
+        SYSTEM_ADDRESS,
     },
     time::milliseconds_in_years,
 };
@@ -38,6 +75,15 @@ fn get_user_role(role: String) -> UserRole {
     }
 }
 
+fn get_launch_phase(phase: String) -> LaunchPhase {
+    match phase.as_str() {
+        "Sunrise" => LaunchPhase::Sunrise {},
+        "Landrush" => LaunchPhase::Landrush {},
+        "GeneralAvailability" => LaunchPhase::GeneralAvailability {},
+        _ => panic!("Unknown launch phase"),
+    }
+}
+
 // Taken from partisia-name-system/tests/cucumber.rs
 fn get_record_class_given(class: String) -> RecordClass {
     match class.as_str() {
@@ -55,6 +101,28 @@ fn get_record_class_given(class: String) -> RecordClass {
     }
 }
 
+fn get_fuses(fuses: String) -> u32 {
+    fuses
+        .split(',')
+        .map(|fuse| match fuse {
+            "CANNOT_TRANSFER" => CANNOT_TRANSFER,
+            "CANNOT_SET_RECORDS" => CANNOT_SET_RECORDS,
+            "CANNOT_CREATE_SUBDOMAIN" => CANNOT_CREATE_SUBDOMAIN,
+            "PARENT_CANNOT_CONTROL" => PARENT_CANNOT_CONTROL,
+            _ => panic!("Unknown fuse"),
+        })
+        .fold(0, |fuses, fuse| fuses | fuse)
+}
+
+fn get_coin_type(coin: String) -> u32 {
+    match coin.as_str() {
+        "Bitcoin" => COIN_TYPE_BITCOIN,
+        "Ethereum" => COIN_TYPE_ETHEREUM,
+        "Partisia" => COIN_TYPE_PARTISIA,
+        _ => coin.parse().unwrap(),
+    }
+}
+
 #[given(regex = "a meta names contract")]
 fn meta_names_contract(world: &mut ContractWorld) {
     let config = ContractConfig {
@@ -113,6 +181,31 @@ fn update_contract_config(world: &mut ContractWorld, user: String, key: String,
                 new_config.mint_count_limit = value.parse::<u32>().unwrap();
                 new_config
             }
+            "mint_count_window" => {
+                let mut new_config = world.state.config.clone();
+                new_config.mint_count_window = value.parse::<i64>().unwrap();
+                new_config
+            }
+            "subdomain_transfer_policy" => {
+                let mut new_config = world.state.config.clone();
+                new_config.subdomain_transfer_policy = match value.as_str() {
+                    "Keep" => SubdomainTransferPolicy::Keep {},
+                    "TransferAlong" => SubdomainTransferPolicy::TransferAlong {},
+                    "Revoke" => SubdomainTransferPolicy::Revoke {},
+                    _ => panic!("Unknown subdomain transfer policy"),
+                };
+                new_config
+            }
+            "max_subdomain_depth" => {
+                let mut new_config = world.state.config.clone();
+                new_config.max_subdomain_depth = value.parse::<u32>().unwrap();
+                new_config
+            }
+            "max_subdomains_per_domain" => {
+                let mut new_config = world.state.config.clone();
+                new_config.max_subdomains_per_domain = value.parse::<u32>().unwrap();
+                new_config
+            }
             _ => panic!("Unknown config key"),
         };
 
@@ -129,32 +222,30 @@ fn update_contract_config(world: &mut ContractWorld, user: String, key: String,
     }
 }
 
-#[given(regex = r"(\w+) minted '(.+)' domain without a (parent)")]
-#[when(regex = r"(Alice|Bob) mints '(.+)' domain without fees and a (parent)")]
-#[when(
-    regex = r"(Alice|Bob) mints '(.+)' domain with (.+) as payment token id and without a parent"
-)]
-fn mint_a_domain(world: &mut ContractWorld, user: String, domain: String, token_id_str: String) {
-    let payment_coin_id = if token_id_str == "parent" {
-        0
-    } else {
-        token_id_str.parse::<u64>().unwrap()
-    };
-
+#[given(regex = r"(contract) pns config '(.+)' is '(.+)'")]
+#[when(regex = r"(\w+) updates the pns config '(.+)' to '(.+)'")]
+fn update_pns_config_step(world: &mut ContractWorld, user: String, key: String, value: String) {
     let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut new_config = world.state.pns.config.clone();
+        match key.as_str() {
+            "record_history_limit" => new_config.record_history_limit = value.parse().unwrap(),
+            "max_domain_len" => new_config.max_domain_len = value.parse().unwrap(),
+            "max_record_data_length" => {
+                new_config.record_limits.max_record_data_length = value.parse().unwrap()
+            }
+            "max_custom_records" => {
+                new_config.record_limits.max_custom_records = value.parse().unwrap()
+            }
+            "storage_price_per_kb" => new_config.storage_price_per_kb = value.parse().unwrap(),
+            "max_storage_quota_kb" => new_config.max_storage_quota_kb = value.parse().unwrap(),
+            _ => panic!("Unknown pns config key"),
+        };
+
         let state = take(&mut world.state);
-        on_mint_callback(
+        update_pns_config(
             mock_contract_context(get_address_for_user(user.clone())),
-            mock_successful_callback_context(),
             state,
-            MintMsg {
-                domain,
-                to: mock_address(get_address_for_user(user)),
-                payment_coin_id,
-                token_uri: None,
-                parent_id: None,
-                subscription_years: None,
-            },
+            new_config,
         )
     }));
 
@@ -163,46 +254,69 @@ fn mint_a_domain(world: &mut ContractWorld, user: String, domain: String, token_
     }
 }
 
-#[when(regex = r"(Alice|Bob) batch mints '(.+)' and '(.+)' domain without fees and a (parent)")]
-fn mint_batch_domain(
-    world: &mut ContractWorld,
-    user: String,
-    domain: String,
-    domain2: String,
-    token_id_str: String,
-) {
-    let payment_coin_id = if token_id_str == "parent" {
-        0
-    } else {
-        token_id_str.parse::<u64>().unwrap()
-    };
+#[given(
+    expr = "contract pns config has a premium tier of {int} max record data length for {int} tokens"
+)]
+fn pns_config_premium_tier(world: &mut ContractWorld, max_record_data_length: u32, price: u128) {
+    let mut new_config = world.state.pns.config.clone();
+    new_config.premium_tiers.push(PremiumTier {
+        record_limits: RecordLimits {
+            max_record_data_length,
+            ..new_config.record_limits.clone()
+        },
+        price,
+    });
+
+    let state = take(&mut world.state);
+    let (new_state, _) =
+        update_pns_config(mock_contract_context(SYSTEM_ADDRESS), state, new_config);
+
+    world.state = new_state;
+}
+
+#[when(expr = "{word} updates the price of the premium tier {int} to {int} tokens")]
+fn update_premium_tier_price(world: &mut ContractWorld, user: String, tier: usize, price: u128) {
+    let mut new_config = world.state.pns.config.clone();
+    new_config.premium_tiers[tier].price = price;
+
+    let state = take(&mut world.state);
+    let (new_state, _) = update_pns_config(
+        mock_contract_context(get_address_for_user(user)),
+        state,
+        new_config,
+    );
+
+    world.state = new_state;
+}
+
+#[then(expr = "{word} cannot remove the premium tier {int}")]
+fn cannot_remove_premium_tier(world: &mut ContractWorld, user: String, tier: usize) {
+    let mut new_config = world.state.pns.config.clone();
+    new_config.premium_tiers.remove(tier);
 
     let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
         let state = take(&mut world.state);
+        update_pns_config(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            new_config,
+        )
+    }));
 
-        let domains_mint_msg = vec![
-            MintMsg {
-                domain,
-                to: mock_address(get_address_for_user(user.clone())),
-                payment_coin_id,
-                token_uri: None,
-                parent_id: None,
-                subscription_years: None,
-            },
-            MintMsg {
-                domain: domain2,
-                to: mock_address(get_address_for_user(user.clone())),
-                payment_coin_id,
-                token_uri: None,
-                parent_id: None,
-                subscription_years: None,
-            },
-        ];
+    assert!(res.is_err());
+}
 
-        mint_batch(
-            mock_contract_context(get_address_for_user(user)),
+#[when(expr = "{word} upgrades the '{word}' domain to the premium tier {int}")]
+fn upgrade_premium_tier_step(world: &mut ContractWorld, user: String, domain: String, tier: u32) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        upgrade_premium_tier(
+            mock_contract_context(get_address_for_user(user.clone())),
             state,
-            domains_mint_msg,
+            domain,
+            Some(tier),
+            0,
+            mock_address(get_address_for_user(user)),
         )
     }));
 
@@ -211,34 +325,38 @@ fn mint_batch_domain(
     }
 }
 
-#[given(regex = r"(\w+) user (with) the (\w+) (role)")]
-#[when(regex = r"(\w+) user (grants|denies) the (\w+) role for (\w+) user")]
-fn user_admin_role(
+#[when(
+    expr = "{word} upgrades the '{word}' domain to the premium tier {int} with {int} payment token id"
+)]
+fn upgrade_premium_tier_on_callback(
     world: &mut ContractWorld,
-    admin: String,
-    action: String,
-    role: String,
     user: String,
+    domain: String,
+    tier: u32,
+    payment_coin_id: u64,
 ) {
     let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
         let state = take(&mut world.state);
-        match action.as_str() {
-            "with" => update_user_role(
-                mock_contract_context(SYSTEM_ADDRESS),
-                state,
-                get_user_role(role),
-                mock_address(get_address_for_user(admin)),
-                true,
-            ),
-            "grants" | "denied" => update_user_role(
-                mock_contract_context(get_address_for_user(admin)),
-                state,
-                get_user_role(role),
-                mock_address(get_address_for_user(user)),
-                action == "grants",
-            ),
-            _ => panic!("Unknown action"),
-        }
+        let (state, _) = upgrade_premium_tier(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            domain.clone(),
+            Some(tier),
+            payment_coin_id,
+            mock_address(get_address_for_user(user.clone())),
+        );
+
+        on_upgrade_premium_tier_callback(
+            mock_contract_context(get_address_for_user(user.clone())),
+            mock_successful_callback_context(),
+            state,
+            UpgradePremiumTierMsg {
+                domain,
+                premium_tier: tier,
+                payer: mock_address(get_address_for_user(user)),
+                payment_coin_id,
+            },
+        )
     }));
 
     if let Ok((new_state, _)) = res {
@@ -246,115 +364,611 @@ fn user_admin_role(
     }
 }
 
-#[given(expr = "{word} approved {word} on '{word}' domain")]
-fn user_approve_domain(world: &mut ContractWorld, user: String, approved: String, domain: String) {
-    let state = take(&mut world.state);
-    let (new_state, _) = approve_domain(
-        mock_contract_context(get_address_for_user(user)),
-        state,
-        Some(mock_address(get_address_for_user(approved))),
-        domain,
-    );
-
-    world.state = new_state;
-}
-
-// Taken from partisia-name-system/tests/cucumber.rs
-#[given(regex = ".+ (minted) the '(.+)' record with '(.+)' data for the '(.+)' domain")]
-#[when(regex = ".+ (mints|updates) the '(.+)' record with '(.+)' data for the '(.+)' domain")]
-fn mint_a_record(
+#[then(
+    expr = "{word} cannot upgrade the '{word}' domain to the premium tier {int} with {int} payment token id"
+)]
+fn cannot_upgrade_premium_tier(
     world: &mut ContractWorld,
-    action: String,
-    class: String,
-    data: String,
+    user: String,
     domain: String,
+    tier: u32,
+    payment_coin_id: u64,
 ) {
     let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
-        let mut state = take(&mut world.state);
-        let context = &mock_contract_context(1);
-        match action.as_str() {
-            "mints" | "minted" => {
-                let msg = PnsRecordMintMsg {
-                    domain,
-                    class: get_record_class_given(class),
-                    data: data.into_bytes(),
-                };
-                execute_record_mint(context, &mut state.pns, &msg);
-            }
-
-            "updates" => {
-                let msg = PnsRecordUpdateMsg {
-                    domain,
-                    class: get_record_class_given(class),
-                    data: data.into_bytes(),
-                };
-
-                execute_record_update(context, &mut state.pns, &msg);
-            }
-
-            _ => panic!("Not handled"),
-        };
-
-        state
+        let state = take(&mut world.state);
+        upgrade_premium_tier(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            domain,
+            Some(tier),
+            payment_coin_id,
+            mock_address(get_address_for_user(user)),
+        )
     }));
 
-    if let Ok(new_state) = res {
-        world.state = new_state;
-    }
+    assert!(res.is_err());
 }
 
-#[when(expr = "{word} renews '{word}' domain with {int} payment token id for {int} years")]
-fn renew_domain_on_callback(
+#[then(
+    expr = "{word} cannot complete the upgrade of the '{word}' domain to the premium tier {int} with {int} payment token id"
+)]
+fn cannot_complete_premium_tier_upgrade(
     world: &mut ContractWorld,
     user: String,
-    domain_name: String,
+    domain: String,
+    tier: u32,
     payment_coin_id: u64,
-    years: u32,
 ) {
-    let context = mock_contract_context(get_address_for_user(user.clone()));
-
-    // To properly test renewing a domain, we need to override the expiration time of the domain
-    let expires_at = Some(world.point_in_time);
-    execute_update_expiration(
-        &context,
-        &mut world.state.pns,
-        &PnsDomainUpdateExpirationMsg {
-            domain: domain_name.clone(),
-            expires_at,
-        },
-    );
-
     let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
         let state = take(&mut world.state);
-        on_renew_subscription_callback(
-            context,
+        on_upgrade_premium_tier_callback(
+            mock_contract_context(get_address_for_user(user.clone())),
             mock_successful_callback_context(),
             state,
-            RenewDomainMsg {
-                domain: domain_name,
+            UpgradePremiumTierMsg {
+                domain,
+                premium_tier: tier,
                 payer: mock_address(get_address_for_user(user)),
                 payment_coin_id,
-                subscription_years: years,
             },
         )
     }));
 
-    if let Ok((new_state, _)) = res {
-        world.state = new_state;
+    assert!(res.is_err());
+}
+
+#[then(expr = "the pns config '{word}' is '{word}'")]
+fn pns_config_is(world: &mut ContractWorld, key: String, value: String) {
+    match key.as_str() {
+        "record_history_limit" => assert_eq!(
+            world.state.pns.config.record_history_limit.to_string(),
+            value
+        ),
+        _ => panic!("Unknown pns config key"),
     }
 }
 
-#[given(expr = "{word} renewed '{word}' domain for {int} years")]
-#[when(expr = "{word} renews '{word}' domain for {int} years")]
-fn renew_domain(world: &mut ContractWorld, user: String, domain_name: String, years: u32) {
-    let context = mock_contract_context(get_address_for_user(user.clone()));
+#[then(expr = "the premium tier {int} of the pns config costs {int} tokens")]
+fn pns_config_premium_tier_price(world: &mut ContractWorld, tier: u32, price: u128) {
+    let premium_tier = world.state.pns.config.get_premium_tier(tier).unwrap();
 
-    // To properly test renewing a domain, we need to override the expiration time of the domain
-    let expires_at = Some(world.point_in_time);
-    execute_update_expiration(
-        &context,
-        &mut world.state.pns,
-        &PnsDomainUpdateExpirationMsg {
+    assert_eq!(premium_tier.price, price);
+}
+
+#[then(expr = "'{word}' domain has the premium tier {int}")]
+fn domain_has_premium_tier(world: &mut ContractWorld, domain: String, tier: u32) {
+    let domain = world.state.pns.get_domain(&domain).unwrap();
+
+    assert_eq!(domain.premium_tier, Some(tier));
+}
+
+#[then(expr = "'{word}' domain does not have a premium tier")]
+fn domain_has_no_premium_tier(world: &mut ContractWorld, domain: String) {
+    let premium_tier = world
+        .state
+        .pns
+        .get_domain(&domain)
+        .and_then(|domain| domain.premium_tier);
+
+    assert_eq!(premium_tier, None);
+}
+
+#[given(expr = "{word} set the storage quota of '{word}' domain to {int} KB")]
+#[when(expr = "{word} sets the storage quota of '{word}' domain to {int} KB")]
+fn set_domain_storage_quota(
+    world: &mut ContractWorld,
+    user: String,
+    domain: String,
+    storage_quota_kb: u32,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        let (state, events) = set_storage_quota(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            domain.clone(),
+            storage_quota_kb,
+            0,
+            mock_address(get_address_for_user(user.clone())),
+        );
+
+        // An increase is applied once paid
+        if events.is_empty() {
+            (state, events)
+        } else {
+            on_set_storage_quota_callback(
+                mock_contract_context(get_address_for_user(user.clone())),
+                mock_successful_callback_context(),
+                state,
+                SetStorageQuotaMsg {
+                    domain,
+                    storage_quota_kb,
+                    payer: mock_address(get_address_for_user(user)),
+                    payment_coin_id: 0,
+                },
+            )
+        }
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[then(expr = "{word} cannot set the storage quota of '{word}' domain to {int} KB")]
+fn cannot_set_domain_storage_quota(
+    world: &mut ContractWorld,
+    user: String,
+    domain: String,
+    storage_quota_kb: u32,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        set_storage_quota(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            domain,
+            storage_quota_kb,
+            0,
+            mock_address(get_address_for_user(user)),
+        )
+    }));
+
+    assert!(res.is_err());
+}
+
+#[given(expr = "{word} wrote the '{word}' large record of {int} bytes for the '{word}' domain")]
+#[when(expr = "{word} writes the '{word}' large record of {int} bytes for the '{word}' domain")]
+fn write_large_record(
+    world: &mut ContractWorld,
+    user: String,
+    key: String,
+    size: usize,
+    domain: String,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut state = take(&mut world.state);
+        for (index, data) in split_into_chunks(&vec![b'a'; size]).into_iter().enumerate() {
+            (state, _) = write_large_record_chunk(
+                mock_contract_context(get_address_for_user(user.clone())),
+                state,
+                domain.clone(),
+                key.clone(),
+                index as u32,
+                data,
+            );
+        }
+        state
+    }));
+
+    if let Ok(new_state) = res {
+        world.state = new_state;
+    }
+}
+
+#[when(expr = "{word} commits the '{word}' large record of {int} bytes for the '{word}' domain")]
+fn commit_large_record_step(
+    world: &mut ContractWorld,
+    user: String,
+    key: String,
+    size: usize,
+    domain: String,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        commit_large_record(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            domain,
+            key,
+            large_record_commit(&vec![b'a'; size]),
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[when(expr = "{word} deletes the '{word}' large record for the '{word}' domain")]
+fn delete_large_record_step(world: &mut ContractWorld, user: String, key: String, domain: String) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        delete_large_record(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            domain,
+            key,
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[then(expr = "'{word}' domain has a storage quota of {int} KB")]
+fn domain_has_storage_quota(world: &mut ContractWorld, domain: String, storage_quota_kb: u32) {
+    let domain = world.state.pns.get_domain(&domain).unwrap();
+
+    assert_eq!(domain.storage_quota_kb, storage_quota_kb);
+}
+
+#[then(expr = "'{word}' domain has the '{word}' large record of {int} bytes")]
+fn domain_has_large_record(world: &mut ContractWorld, domain: String, key: String, size: usize) {
+    let value = world.state.pns.get_large_record_value(&domain, &key);
+
+    assert_eq!(value, Some(vec![b'a'; size]));
+}
+
+#[then(expr = "'{word}' domain does not have the '{word}' large record")]
+fn domain_has_no_large_record(world: &mut ContractWorld, domain: String, key: String) {
+    let value = world.state.pns.get_large_record_value(&domain, &key);
+
+    assert_eq!(value, None);
+}
+
+#[given(expr = "contract config mint count limit for the {word} role is {int} per {int} days")]
+fn mint_count_role_limit(world: &mut ContractWorld, role: String, limit: u32, days: i64) {
+    let mut new_config = world.state.config.clone();
+    new_config.mint_count_role_limits.push(MintCountLimit {
+        role: get_user_role(role),
+        limit,
+        window: days * 24 * 60 * 60 * 1000,
+    });
+
+    let state = take(&mut world.state);
+    let (new_state, _) = update_config(mock_contract_context(SYSTEM_ADDRESS), state, new_config);
+
+    world.state = new_state;
+}
+
+#[given(expr = "the launch schedule is in the '{word}' phase")]
+#[when(expr = "the launch schedule moves to the '{word}' phase")]
+fn launch_schedule(world: &mut ContractWorld, phase: String) {
+    let day = 24 * 60 * 60 * 1000;
+    let now = unix_epoch_now();
+    let sunrise_at = match phase.as_str() {
+        "NotStarted" => now + day / 2,
+        "Sunrise" => now - day / 2,
+        "Landrush" => now - day - day / 2,
+        "GeneralAvailability" => now - 2 * day - day / 2,
+        _ => panic!("Unknown launch phase"),
+    };
+
+    let mut new_config = world.state.config.clone();
+    new_config.launch_schedule = Some(LaunchSchedule {
+        sunrise_at,
+        landrush_at: sunrise_at + day,
+        general_availability_at: sunrise_at + 2 * day,
+        landrush_fees_multiplier: 2,
+        landrush_reveal_period: day,
+        landrush_unrevealed_penalty_bps: 1_000,
+    });
+
+    let state = take(&mut world.state);
+    let (new_state, _) = update_config(mock_contract_context(SYSTEM_ADDRESS), state, new_config);
+
+    world.state = new_state;
+}
+
+#[given(expr = "{word} reserved the '{word}' domain for {word} during the sunrise")]
+fn reserve_sunrise_domain(
+    world: &mut ContractWorld,
+    user: String,
+    domain: String,
+    claimant: String,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        set_sunrise_reservation(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            domain,
+            Some(mock_address(get_address_for_user(claimant))),
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[then(expr = "{word} cannot reserve the '{word}' domain for {word} during the sunrise")]
+fn cannot_reserve_sunrise_domain(
+    world: &mut ContractWorld,
+    user: String,
+    domain: String,
+    claimant: String,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        set_sunrise_reservation(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            domain,
+            Some(mock_address(get_address_for_user(claimant))),
+        )
+    }));
+
+    assert!(res.is_err());
+}
+
+#[given(
+    regex = r"contract config supply cap for (\d+) chars domains (is|during the (\w+) phase is) (\d+)"
+)]
+fn supply_cap(
+    world: &mut ContractWorld,
+    chars_count: u32,
+    _scope: String,
+    phase: String,
+    cap: u32,
+) {
+    let mut new_config = world.state.config.clone();
+    new_config.supply_caps.push(SupplyCap {
+        chars_count,
+        phase: (!phase.is_empty()).then(|| get_launch_phase(phase)),
+        cap,
+    });
+
+    let state = take(&mut world.state);
+    let (new_state, _) = update_config(mock_contract_context(SYSTEM_ADDRESS), state, new_config);
+
+    world.state = new_state;
+}
+
+#[given(regex = r"(\w+) minted '(.+)' domain without a (parent)")]
+#[when(regex = r"(Alice|Bob) mints '(.+)' domain without fees and a (parent)")]
+#[when(
+    regex = r"(Alice|Bob) mints '(.+)' domain with (.+) as payment token id and without a parent"
+)]
+fn mint_a_domain(world: &mut ContractWorld, user: String, domain: String, token_id_str: String) {
+    let payment_coin_id = if token_id_str == "parent" {
+        0
+    } else {
+        token_id_str.parse::<u64>().unwrap()
+    };
+
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        on_mint_callback(
+            mock_contract_context(get_address_for_user(user.clone())),
+            mock_successful_callback_context(),
+            state,
+            MintMsg {
+                domain,
+                to: mock_address(get_address_for_user(user)),
+                payment_coin_id,
+                token_uri: None,
+                parent_id: None,
+                subscription_years: None,
+            },
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[then(regex = r"(Alice|Bob) (can|cannot) request the payment of the '(.+)' domain mint")]
+fn request_mint_payment(world: &mut ContractWorld, user: String, can: String, domain: String) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        mint(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            domain,
+            mock_address(get_address_for_user(user)),
+            0,
+            None,
+            None,
+            None,
+        )
+    }));
+
+    match res {
+        Ok((new_state, events)) => {
+            assert_eq!(can, "can");
+            assert_eq!(events.len(), 1);
+            world.state = new_state;
+        }
+        Err(_) => assert_eq!(can, "cannot"),
+    }
+}
+
+#[when(regex = r"(Alice|Bob) batch mints '(.+)' and '(.+)' domain without fees and a (parent)")]
+fn mint_batch_domain(
+    world: &mut ContractWorld,
+    user: String,
+    domain: String,
+    domain2: String,
+    token_id_str: String,
+) {
+    let payment_coin_id = if token_id_str == "parent" {
+        0
+    } else {
+        token_id_str.parse::<u64>().unwrap()
+    };
+
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+
+        let domains_mint_msg = vec![
+            MintMsg {
+                domain,
+                to: mock_address(get_address_for_user(user.clone())),
+                payment_coin_id,
+                token_uri: None,
+                parent_id: None,
+                subscription_years: None,
+            },
+            MintMsg {
+                domain: domain2,
+                to: mock_address(get_address_for_user(user.clone())),
+                payment_coin_id,
+                token_uri: None,
+                parent_id: None,
+                subscription_years: None,
+            },
+        ];
+
+        mint_batch(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            domains_mint_msg,
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[given(regex = r"(\w+) user (with) the (\w+) (role)")]
+#[when(regex = r"(\w+) user (grants|denies) the (\w+) role for (\w+) user")]
+fn user_admin_role(
+    world: &mut ContractWorld,
+    admin: String,
+    action: String,
+    role: String,
+    user: String,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        match action.as_str() {
+            "with" => update_user_role(
+                mock_contract_context(SYSTEM_ADDRESS),
+                state,
+                get_user_role(role),
+                mock_address(get_address_for_user(admin)),
+                true,
+            ),
+            "grants" | "denied" => update_user_role(
+                mock_contract_context(get_address_for_user(admin)),
+                state,
+                get_user_role(role),
+                mock_address(get_address_for_user(user)),
+                action == "grants",
+            ),
+            _ => panic!("Unknown action"),
+        }
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[given(expr = "{word} approved {word} on '{word}' domain")]
+fn user_approve_domain(world: &mut ContractWorld, user: String, approved: String, domain: String) {
+    let state = take(&mut world.state);
+    let (new_state, _) = approve_domain(
+        mock_contract_context(get_address_for_user(user)),
+        state,
+        Some(mock_address(get_address_for_user(approved))),
+        domain,
+    );
+
+    world.state = new_state;
+}
+
+// Taken from partisia-name-system/tests/cucumber.rs
+#[given(regex = ".+ (minted) the '(.+)' record with '(.+)' data for the '(.+)' domain")]
+#[when(regex = ".+ (mints|updates) the '(.+)' record with '(.+)' data for the '(.+)' domain")]
+fn mint_a_record(
+    world: &mut ContractWorld,
+    action: String,
+    class: String,
+    data: String,
+    domain: String,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut state = take(&mut world.state);
+        let context = &mock_contract_context(1);
+        match action.as_str() {
+            "mints" | "minted" => {
+                let msg = PnsRecordMintMsg {
+                    domain,
+                    class: get_record_class_given(class),
+                    data: data.into_bytes(),
+                };
+                execute_record_mint(context, &mut state.pns, &msg);
+            }
+
+            "updates" => {
+                let msg = PnsRecordUpdateMsg {
+                    domain,
+                    class: get_record_class_given(class),
+                    data: data.into_bytes(),
+                };
+
+                execute_record_update(context, &mut state.pns, &msg);
+            }
+
+            _ => panic!("Not handled"),
+        };
+
+        state
+    }));
+
+    if let Ok(new_state) = res {
+        world.state = new_state;
+    }
+}
+
+#[when(expr = "{word} renews '{word}' domain with {int} payment token id for {int} years")]
+fn renew_domain_on_callback(
+    world: &mut ContractWorld,
+    user: String,
+    domain_name: String,
+    payment_coin_id: u64,
+    years: u32,
+) {
+    let context = mock_contract_context(get_address_for_user(user.clone()));
+
+    // To properly test renewing a domain, we need to override the expiration time of the domain
+    let expires_at = Some(world.point_in_time);
+    execute_update_expiration(
+        &context,
+        &mut world.state.pns,
+        &PnsDomainUpdateExpirationMsg {
+            domain: domain_name.clone(),
+            expires_at,
+        },
+    );
+
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        on_renew_subscription_callback(
+            context,
+            mock_successful_callback_context(),
+            state,
+            RenewDomainMsg {
+                domain: domain_name,
+                payer: mock_address(get_address_for_user(user)),
+                payment_coin_id,
+                subscription_years: years,
+            },
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[given(expr = "{word} renewed '{word}' domain for {int} years")]
+#[when(expr = "{word} renews '{word}' domain for {int} years")]
+fn renew_domain(world: &mut ContractWorld, user: String, domain_name: String, years: u32) {
+    let context = mock_contract_context(get_address_for_user(user.clone()));
+
+    // To properly test renewing a domain, we need to override the expiration time of the domain
+    let expires_at = Some(world.point_in_time);
+    execute_update_expiration(
+        &context,
+        &mut world.state.pns,
+        &PnsDomainUpdateExpirationMsg {
             domain: domain_name.clone(),
             expires_at,
         },
@@ -362,13 +976,828 @@ fn renew_domain(world: &mut ContractWorld, user: String, domain_name: String, ye
 
     let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
         let state = take(&mut world.state);
-        renew_subscription(
-            context,
+        renew_subscription(
+            context,
+            state,
+            domain_name,
+            0,
+            mock_address(get_address_for_user(user)),
+            years,
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[then(expr = "{word} cannot renew '{word}' domain for {int} years")]
+fn cannot_renew_domain(world: &mut ContractWorld, user: String, domain_name: String, years: u32) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        renew_subscription(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            domain_name,
+            0,
+            mock_address(get_address_for_user(user)),
+            years,
+        )
+    }));
+
+    assert!(res.is_err());
+}
+
+#[given(expr = "{word} airdropped to '{word}'")]
+#[when(expr = "{word} add airdrop to '{word}'")]
+fn airdrop(world: &mut ContractWorld, user: String, to: String) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        add_airdrop(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            vec![mock_address(get_address_for_user(to))],
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[given(expr = "{word} created the '{word}' airdrop campaign for at least {int} chars domains")]
+#[when(expr = "{word} creates the '{word}' airdrop campaign for at least {int} chars domains")]
+fn create_campaign(world: &mut ContractWorld, user: String, name: String, min_domain_length: u32) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        create_airdrop_campaign(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            AirdropCampaignMsg {
+                name,
+                expires_at: None,
+                min_domain_length,
+                max_subscription_years: 1,
+            },
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[when(expr = "{word} revokes the '{word}' airdrop campaign")]
+fn revoke_campaign(world: &mut ContractWorld, user: String, name: String) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        revoke_airdrop_campaign(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            name,
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[given(expr = "{word} airdropped to '{word}' in the '{word}' campaign")]
+#[when(expr = "{word} add airdrop to '{word}' in the '{word}' campaign")]
+fn campaign_airdrop(world: &mut ContractWorld, user: String, to: String, campaign: String) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        add_campaign_airdrop(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            campaign,
+            vec![mock_address(get_address_for_user(to))],
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[given(
+    expr = "{word} published the '{word}' merkle airdrop campaign with '{word}' root and {int} leaves"
+)]
+fn publish_merkle_airdrop(
+    world: &mut ContractWorld,
+    user: String,
+    campaign: String,
+    merkle_root: String,
+    leaves_count: u32,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        let (state, _) = create_airdrop_campaign(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            AirdropCampaignMsg {
+                name: campaign.clone(),
+                expires_at: None,
+                min_domain_length: 0,
+                max_subscription_years: 1,
+            },
+        );
+        add_merkle_airdrop(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            campaign,
+            merkle_root,
+            leaves_count,
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[when(
+    expr = "{word} claims the '{word}' merkle airdrop leaf {int} of {int} domains for at least {int} chars with '{word}' proof"
+)]
+fn claim_merkle_airdrop_leaf(
+    world: &mut ContractWorld,
+    user: String,
+    campaign: String,
+    index: u32,
+    amount: u128,
+    min_domain_length: u32,
+    proof: String,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        claim_merkle_airdrop(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            campaign,
+            index,
+            amount,
+            min_domain_length,
+            vec![proof],
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[given(expr = "{word} flagged '{word}' domain as premium")]
+fn flag_premium_domain(world: &mut ContractWorld, user: String, domain: String) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        set_premium_domain(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            domain,
+            true,
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[given(expr = "{word} started an auction for '{word}' domain with {int} reserve price")]
+#[when(expr = "{word} starts an auction for '{word}' domain with {int} reserve price")]
+fn start_auction(world: &mut ContractWorld, user: String, domain: String, reserve_price: u128) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let now = unix_epoch_now();
+        let state = take(&mut world.state);
+        create_auction(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            AuctionCreateMsg {
+                domain,
+                payment_coin_id: 0,
+                reserve_price,
+                subscription_years: 1,
+                unrevealed_penalty_bps: 1_000,
+                commit_ends_at: now + 1000,
+                reveal_ends_at: now + 2000,
+            },
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[then(expr = "{word} cannot start an auction for '{word}' domain")]
+fn cannot_start_auction(world: &mut ContractWorld, user: String, domain: String) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let now = unix_epoch_now();
+        let state = take(&mut world.state);
+        create_auction(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            AuctionCreateMsg {
+                domain,
+                payment_coin_id: 0,
+                reserve_price: 10,
+                subscription_years: 1,
+                unrevealed_penalty_bps: 1_000,
+                commit_ends_at: now + 1000,
+                reveal_ends_at: now + 2000,
+            },
+        )
+    }));
+
+    assert!(res.is_err());
+}
+
+#[given(regex = r"(\w+) bid (\d+) with (\d+) deposit on the '(.+)' auction( without paying)?$")]
+#[when(regex = r"(\w+) bids (\d+) with (\d+) deposit on the '(.+)' auction( without paying)?$")]
+fn commit_bid(
+    world: &mut ContractWorld,
+    user: String,
+    amount: u128,
+    deposit: u128,
+    domain: String,
+    without_paying: String,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let bidder = mock_address(get_address_for_user(user.clone()));
+        let state = take(&mut world.state);
+        let (state, _) = commit_auction_bid(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            domain.clone(),
+            bid_commitment(&bidder, amount, "salt"),
+            deposit,
+        );
+
+        let mut callback_ctx = mock_successful_callback_context();
+        callback_ctx.success = without_paying.is_empty();
+
+        on_auction_bid_callback(
+            mock_contract_context(get_address_for_user(user)),
+            callback_ctx,
+            state,
+            AuctionBidMsg {
+                domain,
+                bidder,
+                payment_coin_id: 0,
+                commitment: bid_commitment(&bidder, amount, "salt"),
+                deposit,
+            },
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[when(expr = "{word} reveals the {int} bid on the '{word}' auction")]
+fn reveal_bid(world: &mut ContractWorld, user: String, amount: u128, domain: String) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut ctx = mock_contract_context(get_address_for_user(user));
+        ctx.block_production_time = world
+            .state
+            .auction
+            .get_auction(&domain)
+            .unwrap()
+            .commit_ends_at;
+
+        let state = take(&mut world.state);
+        reveal_auction_bid(ctx, state, domain, amount, "salt".to_string())
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[when(expr = "{word} settles the '{word}' auction")]
+fn settle_domain_auction(world: &mut ContractWorld, user: String, domain: String) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut ctx = mock_contract_context(get_address_for_user(user));
+        ctx.block_production_time = world
+            .state
+            .auction
+            .get_auction(&domain)
+            .unwrap()
+            .reveal_ends_at;
+
+        let state = take(&mut world.state);
+        settle_auction(ctx, state, domain)
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[given(expr = "{word} applied for the '{word}' domain with a {int} bid and {int} deposit")]
+#[when(expr = "{word} applies for the '{word}' domain with a {int} bid and {int} deposit")]
+fn apply_landrush(
+    world: &mut ContractWorld,
+    user: String,
+    domain: String,
+    amount: u128,
+    deposit: u128,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let applicant = mock_address(get_address_for_user(user.clone()));
+        let state = take(&mut world.state);
+        let (state, _) = apply_landrush_domain(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            domain.clone(),
+            0,
+            bid_commitment(&applicant, amount, "salt"),
+            deposit,
+        );
+
+        on_auction_bid_callback(
+            mock_contract_context(get_address_for_user(user)),
+            mock_successful_callback_context(),
+            state,
+            AuctionBidMsg {
+                domain,
+                bidder: applicant,
+                payment_coin_id: 0,
+                commitment: bid_commitment(&applicant, amount, "salt"),
+                deposit,
+            },
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[then(expr = "{word} cannot apply for the '{word}' domain")]
+fn cannot_apply_landrush(world: &mut ContractWorld, user: String, domain: String) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let applicant = mock_address(get_address_for_user(user.clone()));
+        let state = take(&mut world.state);
+        apply_landrush_domain(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            domain,
+            0,
+            bid_commitment(&applicant, 10, "salt"),
+            10,
+        )
+    }));
+
+    assert!(res.is_err());
+}
+
+#[given(
+    expr = "{word} published a subdomain sale for '{word}' domain at {int} price for {int} subdomains"
+)]
+#[when(
+    expr = "{word} publishes a subdomain sale for '{word}' domain at {int} price for {int} subdomains"
+)]
+fn publish_subdomain_sale(
+    world: &mut ContractWorld,
+    user: String,
+    domain: String,
+    price: u128,
+    max_subdomains: u32,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        set_subdomain_sale(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            domain,
+            price,
+            0,
+            Some(max_subdomains),
+            None,
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[when(expr = "{word} removes the subdomain sale for '{word}' domain")]
+fn remove_domain_subdomain_sale(world: &mut ContractWorld, user: String, domain: String) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        remove_subdomain_sale(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            domain,
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[then(regex = r"'(.+)' domain (has|has no) subdomain sale")]
+fn has_subdomain_sale(world: &mut ContractWorld, domain: String, action: String) {
+    let sale = world.state.pns.get_subdomain_sale(&domain);
+
+    assert_eq!(sale.is_some(), action == "has");
+}
+
+#[when(regex = r"^(\w+) buys '(.+)' subdomain of '(.+)' domain( while (\w+) mints it)?$")]
+fn buy_domain_subdomain(
+    world: &mut ContractWorld,
+    user: String,
+    domain: String,
+    parent: String,
+    concurrent_mint: String,
+    concurrent_minter: String,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let sale = world.state.pns.get_subdomain_sale(&parent);
+        let state = take(&mut world.state);
+        let (mut state, events) = buy_subdomain(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            domain.clone(),
+            parent.clone(),
+            mock_address(get_address_for_user(user.clone())),
+            None,
+        );
+
+        if events.is_empty() {
+            return (state, events);
+        }
+
+        // The subdomain is taken between the escrow and the callback
+        if !concurrent_mint.is_empty() {
+            (state, _) = mint(
+                mock_contract_context(get_address_for_user(concurrent_minter.clone())),
+                state,
+                domain.clone(),
+                mock_address(get_address_for_user(concurrent_minter)),
+                0,
+                None,
+                Some(parent.clone()),
+                Some(1),
+            );
+        }
+
+        // The price is escrowed, the callback mints the subdomain or refunds the buyer
+        let sale = sale.unwrap();
+        let (state, events) = on_buy_subdomain_callback(
+            mock_contract_context(get_address_for_user(user.clone())),
+            mock_successful_callback_context(),
+            state,
+            BuySubdomainMsg {
+                domain,
+                parent_id: parent,
+                to: mock_address(get_address_for_user(user.clone())),
+                token_uri: None,
+                payer: mock_address(get_address_for_user(user)),
+                price: sale.price,
+                payment_coin_id: sale.payment_coin_id,
+            },
+        );
+
+        // Either the payouts or the refund
+        assert_eq!(events.len(), 1);
+
+        (state, events)
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[when(regex = r"(\w+) sets the '(.+)' subdomain expiration to (\d+) days (from now|ago)")]
+fn set_domain_subdomain_expiration(
+    world: &mut ContractWorld,
+    user: String,
+    domain: String,
+    days: i64,
+    direction: String,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let offset = days * 24 * 60 * 60 * 1000;
+        let expires_at = if direction == "ago" {
+            world.point_in_time - offset
+        } else {
+            world.point_in_time + offset
+        };
+
+        let state = take(&mut world.state);
+        set_subdomain_expiration(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            domain,
+            Some(expires_at),
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[then(regex = r"(\w+) cannot set the '(.+)' subdomain expiration to (\d+) days (from now|ago)")]
+fn cannot_set_subdomain_expiration(
+    world: &mut ContractWorld,
+    user: String,
+    domain: String,
+    days: i64,
+    direction: String,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let offset = days * 24 * 60 * 60 * 1000;
+        let expires_at = if direction == "ago" {
+            world.point_in_time - offset
+        } else {
+            world.point_in_time + offset
+        };
+
+        let state = take(&mut world.state);
+        set_subdomain_expiration(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            domain,
+            Some(expires_at),
+        )
+    }));
+
+    assert!(res.is_err());
+}
+
+#[given(expr = "{word} burned the '{word}' fuses of '{word}' domain")]
+#[when(expr = "{word} burns the '{word}' fuses of '{word}' domain")]
+fn burn_domain_fuses(world: &mut ContractWorld, user: String, fuses: String, domain: String) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        burn_fuses(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            domain,
+            get_fuses(fuses),
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[when(expr = "{word} reclaims '{word}' subdomain to {word}")]
+fn reclaim_domain_subdomain(world: &mut ContractWorld, user: String, domain: String, to: String) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        reclaim_subdomain(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            domain,
+            mock_address(get_address_for_user(to)),
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[then(expr = "{word} cannot reclaim '{word}' subdomain to {word}")]
+fn cannot_reclaim_subdomain(world: &mut ContractWorld, user: String, domain: String, to: String) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        reclaim_subdomain(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            domain,
+            mock_address(get_address_for_user(to)),
+        )
+    }));
+
+    assert!(res.is_err());
+}
+
+#[when(expr = "{word} mints '{word}' domain with '{word}' domain as the parent")]
+#[when(regex = r"(\w+) mints '(.+)' domain without (a parent)")]
+fn mint_domain_with_parent(
+    world: &mut ContractWorld,
+    user: String,
+    domain: String,
+    parent: String,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let parent_opt = if parent == "a parent" {
+            None
+        } else {
+            Some(parent.clone())
+        };
+
+        let state = take(&mut world.state);
+        mint(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            domain,
+            mock_address(get_address_for_user(user)),
+            0,
+            None,
+            parent_opt,
+            Some(1),
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[when(expr = "{word} transfers the '{word}' domain to {word}")]
+fn transfer_domain_to(world: &mut ContractWorld, user: String, domain: String, to: String) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        transfer_domain(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            mock_address(get_address_for_user(user.clone())),
+            mock_address(get_address_for_user(to)),
+            domain,
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[when(expr = "{word} transfers the '{word}' domain from {word} to {word}")]
+fn transfer_domain_from_to(
+    world: &mut ContractWorld,
+    user: String,
+    domain: String,
+    from: String,
+    to: String,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        transfer_domain(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            mock_address(get_address_for_user(from)),
+            mock_address(get_address_for_user(to)),
+            domain,
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[when(expr = "{word} transfers the '{word}' domain with its records to {word}")]
+fn transfer_domain_with_records_to(
+    world: &mut ContractWorld,
+    user: String,
+    domain: String,
+    to: String,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        transfer_domain_with_records(
+            mock_contract_context(get_address_for_user(user.clone())),
+            state,
+            mock_address(get_address_for_user(user.clone())),
+            mock_address(get_address_for_user(to)),
+            domain,
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[then(expr = "{word} owns '{word}' domain")]
+fn owns_the_domain(world: &mut ContractWorld, user: String, domain: String) {
+    let domain = world.state.pns.get_domain(&domain).unwrap();
+
+    assert_eq!(
+        world.state.nft.owner_of(domain.token_id),
+        mock_address(get_address_for_user(user))
+    );
+}
+
+#[then(expr = "'{word}' domain is not minted")]
+fn domain_is_not_minted(world: &mut ContractWorld, domain: String) {
+    let domain = world.state.pns.get_domain(&domain);
+
+    assert_eq!(domain, None);
+}
+
+#[given(expr = "{word} set the '{word}' address to '{word}' for the '{word}' domain")]
+#[when(expr = "{word} sets the '{word}' address to '{word}' for the '{word}' domain")]
+fn set_domain_address(
+    world: &mut ContractWorld,
+    user: String,
+    coin: String,
+    address: String,
+    domain: String,
+) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        set_address(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            domain,
+            get_coin_type(coin),
+            Some(address.into_bytes()),
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[when(expr = "{word} removes the '{word}' address for the '{word}' domain")]
+fn remove_domain_address(world: &mut ContractWorld, user: String, coin: String, domain: String) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        set_address(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            domain,
+            get_coin_type(coin),
+            None,
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[then(expr = "'{word}' domain resolves the '{word}' address to '{word}'")]
+fn domain_resolves_address(
+    world: &mut ContractWorld,
+    domain: String,
+    coin: String,
+    address: String,
+) {
+    let resolved = world.state.pns.get_address(
+        &domain,
+        get_coin_type(coin),
+        mock_contract_context(ALICE_ADDRESS).block_production_time,
+    );
+
+    assert_eq!(resolved, Some(address.into_bytes()));
+}
+
+#[then(expr = "'{word}' domain does not resolve the '{word}' address")]
+fn domain_does_not_resolve_address(world: &mut ContractWorld, domain: String, coin: String) {
+    let resolved = world.state.pns.get_address(
+        &domain,
+        get_coin_type(coin),
+        mock_contract_context(ALICE_ADDRESS).block_production_time,
+    );
+
+    assert_eq!(resolved, None);
+}
+
+fn get_content_protocol(protocol: String) -> ContentProtocol {
+    match protocol.as_str() {
+        "ipfs" => ContentProtocol::Ipfs,
+        "ipns" => ContentProtocol::Ipns,
+        "swarm" => ContentProtocol::Swarm,
+        "arweave" => ContentProtocol::Arweave,
+        _ => panic!("Unknown content protocol"),
+    }
+}
+
+#[when(expr = "{word} sets the '{word}' content hash to '{word}' for the '{word}' domain")]
+fn set_domain_content_hash(
+    world: &mut ContractWorld,
+    user: String,
+    protocol: String,
+    content_id: String,
+    domain: String,
+) {
+    let content_hash = encode_content_hash(
+        get_content_protocol(protocol),
+        &hex::decode(content_id).unwrap(),
+    );
+
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        set_content_hash(
+            mock_contract_context(get_address_for_user(user)),
             state,
-            domain_name,
-            0,
-            mock_address(get_address_for_user(user)),
-            years,
+            domain,
+            content_hash,
         )
     }));
 
@@ -377,15 +1806,14 @@ fn renew_domain(world: &mut ContractWorld, user: String, domain_name: String, ye
     }
 }
 
-#[given(expr = "{word} airdropped to '{word}'")]
-#[when(expr = "{word} add airdrop to '{word}'")]
-fn airdrop(world: &mut ContractWorld, user: String, to: String) {
+#[when(expr = "{word} clears the content hash of the '{word}' domain")]
+fn clear_domain_content_hash(world: &mut ContractWorld, user: String, domain: String) {
     let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
         let state = take(&mut world.state);
-        add_airdrop(
-            mock_contract_context(get_address_for_user(user.clone())),
+        clear_content_hash(
+            mock_contract_context(get_address_for_user(user)),
             state,
-            vec![mock_address(get_address_for_user(to))],
+            domain,
         )
     }));
 
@@ -394,31 +1822,138 @@ fn airdrop(world: &mut ContractWorld, user: String, to: String) {
     }
 }
 
-#[when(expr = "{word} mints '{word}' domain with '{word}' domain as the parent")]
-#[when(regex = r"(\w+) mints '(.+)' domain without (a parent)")]
-fn mint_domain_with_parent(
+#[then(expr = "'{word}' domain has the '{word}' content hash '{word}'")]
+fn domain_has_content_hash(
+    world: &mut ContractWorld,
+    domain: String,
+    protocol: String,
+    content_id: String,
+) {
+    let domain = world.state.pns.get_domain(&domain).unwrap();
+
+    assert_eq!(
+        domain.content_hash,
+        Some(encode_content_hash(
+            get_content_protocol(protocol),
+            &hex::decode(content_id).unwrap()
+        ))
+    );
+}
+
+#[then(expr = "'{word}' domain does not have a content hash")]
+fn domain_has_no_content_hash(world: &mut ContractWorld, domain: String) {
+    if let Some(domain) = world.state.pns.get_domain(&domain) {
+        assert_eq!(domain.content_hash, None);
+    }
+}
+
+fn get_dns_record_data(record_type: String, value: String) -> DnsRecordData {
+    match record_type.as_str() {
+        "A" => DnsRecordData::A {
+            address: value.parse::<Ipv4Addr>().unwrap().octets().to_vec(),
+        },
+        "AAAA" => DnsRecordData::Aaaa {
+            address: value.parse::<Ipv6Addr>().unwrap().octets().to_vec(),
+        },
+        "CNAME" => DnsRecordData::Cname { target: value },
+        "TXT" => DnsRecordData::Txt { text: value },
+        _ => panic!("Unknown DNS record type"),
+    }
+}
+
+#[when(regex = r"(\w+) sets the '(\w+)' DNS record of '(.*)' to '(.+)' for the '(.+)' domain")]
+fn set_domain_dns_record(
     world: &mut ContractWorld,
     user: String,
+    record_type: String,
+    name: String,
+    value: String,
     domain: String,
-    parent: String,
 ) {
+    let mut records = world
+        .state
+        .pns
+        .get_domain(&domain)
+        .map(|domain| domain.dns_records)
+        .unwrap_or_default();
+    records.push(DnsRecord {
+        name,
+        ttl: 3600,
+        data: get_dns_record_data(record_type, value),
+    });
+
     let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
-        let parent_opt = if parent == "a parent" {
-            None
-        } else {
-            Some(parent.clone())
-        };
+        let state = take(&mut world.state);
+        set_dns_records(
+            mock_contract_context(get_address_for_user(user)),
+            state,
+            domain,
+            records,
+        )
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[then(
+    regex = r"(\w+) cannot set the '(\w+)' DNS record of '(.*)' to '(.+)' for the '(.+)' domain"
+)]
+fn cannot_set_domain_dns_record(
+    world: &mut ContractWorld,
+    user: String,
+    record_type: String,
+    name: String,
+    value: String,
+    domain: String,
+) {
+    let mut records = world
+        .state
+        .pns
+        .get_domain(&domain)
+        .map(|domain| domain.dns_records)
+        .unwrap_or_default();
+    records.push(DnsRecord {
+        name,
+        ttl: 3600,
+        data: get_dns_record_data(record_type, value),
+    });
+
+    // Invalid records are rejected before the state is changed
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        execute_set_dns_records(
+            &mock_contract_context(get_address_for_user(user)),
+            &mut world.state.pns,
+            &PnsSetDnsRecordsMsg { domain, records },
+        )
+    }));
+
+    assert!(res.is_err());
+}
+
+#[then(expr = "'{word}' domain has {int} DNS records")]
+fn domain_has_dns_records(world: &mut ContractWorld, domain: String, count: usize) {
+    let records = world
+        .state
+        .pns
+        .get_domain(&domain)
+        .map(|domain| domain.dns_records)
+        .unwrap_or_default();
+
+    assert_eq!(records.len(), count);
+}
 
+#[given(expr = "{word} set the '{word}' domain alias to '{word}'")]
+#[when(expr = "{word} sets the '{word}' domain alias to '{word}'")]
+fn set_domain_alias(world: &mut ContractWorld, user: String, domain: String, alias: String) {
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
         let state = take(&mut world.state);
-        mint(
-            mock_contract_context(get_address_for_user(user.clone())),
+        set_alias(
+            mock_contract_context(get_address_for_user(user)),
             state,
             domain,
-            mock_address(get_address_for_user(user)),
-            0,
-            None,
-            parent_opt,
-            Some(1),
+            Some(alias),
         )
     }));
 
@@ -427,16 +1962,15 @@ fn mint_domain_with_parent(
     }
 }
 
-#[when(expr = "{word} transfers the '{word}' domain to {word}")]
-fn transfer_domain_to(world: &mut ContractWorld, user: String, domain: String, to: String) {
+#[when(regex = r"(\w+) (enables|disables) the wildcard of the '(.+)' domain")]
+fn set_domain_wildcard(world: &mut ContractWorld, user: String, action: String, domain: String) {
     let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
         let state = take(&mut world.state);
-        transfer_domain(
-            mock_contract_context(get_address_for_user(user.clone())),
+        set_wildcard(
+            mock_contract_context(get_address_for_user(user)),
             state,
-            mock_address(get_address_for_user(user.clone())),
-            mock_address(get_address_for_user(to)),
             domain,
+            action == "enables",
         )
     }));
 
@@ -445,21 +1979,247 @@ fn transfer_domain_to(world: &mut ContractWorld, user: String, domain: String, t
     }
 }
 
-#[then(expr = "{word} owns '{word}' domain")]
-fn owns_the_domain(world: &mut ContractWorld, user: String, domain: String) {
-    let domain = world.state.pns.get_domain(&domain).unwrap();
+#[then(expr = "'{word}' domain resolves to '{word}' domain")]
+fn domain_resolves_to(world: &mut ContractWorld, domain: String, target: String) {
+    let resolved = world.state.pns.resolve(
+        &domain,
+        mock_contract_context(ALICE_ADDRESS).block_production_time,
+    );
+
+    assert_eq!(resolved.map(|(name, _)| name), Some(target));
+}
+
+#[then(expr = "'{word}' domain does not resolve")]
+fn domain_does_not_resolve(world: &mut ContractWorld, domain: String) {
+    let resolved = world.state.pns.resolve(
+        &domain,
+        mock_contract_context(ALICE_ADDRESS).block_production_time,
+    );
+
+    assert_eq!(resolved, None);
+}
+
+#[then(expr = "'{word}' domain resolves the '{word}' record to '{word}' owned by {word}")]
+fn domain_resolves_record(
+    world: &mut ContractWorld,
+    domain: String,
+    class: String,
+    data: String,
+    owner: String,
+) {
+    let resolved = world.state.resolve_record(
+        &domain,
+        &get_record_class_given(class),
+        mock_contract_context(ALICE_ADDRESS).block_production_time,
+    );
 
+    assert_eq!(resolved.data, Some(data.into_bytes()));
     assert_eq!(
-        world.state.nft.owner_of(domain.token_id),
-        mock_address(get_address_for_user(user))
+        resolved.info.owner,
+        Some(mock_address(get_address_for_user(owner)))
     );
+    assert!(resolved.info.active);
 }
 
-#[then(expr = "'{word}' domain is not minted")]
-fn domain_is_not_minted(world: &mut ContractWorld, domain: String) {
-    let domain = world.state.pns.get_domain(&domain);
+#[then(expr = "'{word}' domain does not resolve the '{word}' record and is not active")]
+fn domain_does_not_resolve_record(world: &mut ContractWorld, domain: String, class: String) {
+    let resolved = world.state.resolve_record(
+        &domain,
+        &get_record_class_given(class),
+        mock_contract_context(ALICE_ADDRESS).block_production_time,
+    );
 
-    assert_eq!(domain, None);
+    assert_eq!(resolved.data, None);
+    assert_eq!(resolved.info.resolved_domain, None);
+    assert!(!resolved.info.active);
+}
+
+#[then(expr = "'{word}' domain resolves {int} records and {int} addresses")]
+fn domain_resolves_all(
+    world: &mut ContractWorld,
+    domain: String,
+    records: usize,
+    addresses: usize,
+) {
+    let resolved = world.state.resolve_all(
+        &domain,
+        mock_contract_context(ALICE_ADDRESS).block_production_time,
+    );
+
+    assert_eq!(resolved.records.len(), records);
+    assert_eq!(resolved.addresses.len(), addresses);
+}
+
+#[then(expr = "the '{word}' domains resolve the '{word}' records to '{word}' in a batch")]
+fn domains_resolve_batch(world: &mut ContractWorld, domains: String, class: String, data: String) {
+    let domains: Vec<String> = domains.split(',').map(String::from).collect();
+    let resolved = world.state.resolve_batch(
+        &domains,
+        &[get_record_class_given(class)],
+        mock_contract_context(ALICE_ADDRESS).block_production_time,
+    );
+
+    let expected: Vec<Vec<Option<Vec<u8>>>> = data
+        .split(',')
+        .map(|data| match data {
+            "" => vec![None],
+            data => vec![Some(data.as_bytes().to_vec())],
+        })
+        .collect();
+    let records: Vec<Vec<Option<Vec<u8>>>> = resolved
+        .domains
+        .into_iter()
+        .map(|resolved| resolved.records)
+        .collect();
+    assert_eq!(records, expected);
+}
+
+#[then(
+    expr = "the '{word}' record of the '{word}' domain is at version {int} with {int} revisions"
+)]
+fn record_history_is(
+    world: &mut ContractWorld,
+    class: String,
+    domain: String,
+    version: u32,
+    revisions: usize,
+) {
+    let history = world
+        .state
+        .pns
+        .get_record_history(&domain, &get_record_class_given(class))
+        .unwrap();
+
+    assert_eq!(history.version, version);
+    assert_eq!(history.revisions.len(), revisions);
+}
+
+#[given(regex = r"(\w+) minted the '(.+)' custom record with '(.+)' data for the '(.+)' domain")]
+#[when(
+    regex = r"(\w+) (?:mints|updates) the '(.+)' custom record with '(.+)' data for the '(.+)' domain"
+)]
+fn mint_or_update_custom_record(
+    world: &mut ContractWorld,
+    user: String,
+    key: String,
+    data: String,
+    domain: String,
+) {
+    let ctx = mock_contract_context(get_address_for_user(user));
+    let minted = world
+        .state
+        .pns
+        .get_domain(&domain)
+        .is_some_and(|domain| domain.is_custom_record_minted(&key));
+
+    let res = catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let state = take(&mut world.state);
+        if minted {
+            update_custom_record(ctx, state, domain, key, data.into_bytes())
+        } else {
+            mint_custom_record(ctx, state, domain, key, data.into_bytes())
+        }
+    }));
+
+    if let Ok((new_state, _)) = res {
+        world.state = new_state;
+    }
+}
+
+#[then(expr = "the '{word}' custom record of the '{word}' domain is at version {int} by {word}")]
+fn custom_record_history_is(
+    world: &mut ContractWorld,
+    key: String,
+    domain: String,
+    version: u32,
+    user: String,
+) {
+    let history = world
+        .state
+        .pns
+        .get_custom_record_history(&domain, &key)
+        .unwrap();
+
+    assert_eq!(history.version, version);
+    assert_eq!(history.updated_by, mock_address(get_address_for_user(user)));
+}
+
+#[then(expr = "the '{word}' custom record of the '{word}' domain has no history")]
+fn custom_record_history_is_empty(world: &mut ContractWorld, key: String, domain: String) {
+    assert_eq!(
+        world.state.pns.get_custom_record_history(&domain, &key),
+        None
+    );
+}
+
+#[then(regex = r"'(.+)' domain (is|is not) active")]
+fn domain_is_active(world: &mut ContractWorld, domain: String, action: String) {
+    let is_active = world.state.pns.is_active(
+        &domain,
+        mock_contract_context(ALICE_ADDRESS).block_production_time,
+    );
+
+    assert_eq!(is_active, action == "is");
+}
+
+#[then(regex = r"the '(.+)' domain owner check of (\w+) is '(owner|approved|denied)'")]
+fn domain_owner_check(world: &mut ContractWorld, domain: String, user: String, result: String) {
+    let owner_check = world.state.check_domain_owner(
+        &domain,
+        mock_address(get_address_for_user(user)),
+        mock_contract_context(ALICE_ADDRESS).block_production_time,
+    );
+
+    assert_eq!(owner_check.is_owner, result == "owner");
+    assert_eq!(owner_check.is_approved, result == "approved");
+}
+
+#[then(regex = r"the '(.+)' domain status is '(active|expired|not minted)'")]
+fn domain_status_is(world: &mut ContractWorld, domain: String, status: String) {
+    let domain_status = world.state.domain_status(
+        &domain,
+        mock_contract_context(ALICE_ADDRESS).block_production_time,
+    );
+
+    assert_eq!(domain_status.exists, status != "not minted");
+    assert_eq!(domain_status.owner.is_some(), status != "not minted");
+    assert_eq!(domain_status.active, status == "active");
+}
+
+#[then(regex = r"(\w+) (has|has not) a bid on the '(.+)' auction")]
+fn has_auction_bid(world: &mut ContractWorld, user: String, action: String, domain: String) {
+    let bidder = mock_address(get_address_for_user(user));
+    let auction = world.state.auction.get_auction(&domain).unwrap();
+
+    assert_eq!(auction.get_bid(&bidder).is_some(), action == "has");
+}
+
+#[then(regex = r"'(.+)' domain (is|is not) in auction")]
+fn domain_in_auction(world: &mut ContractWorld, domain: String, action: String) {
+    let is_in_auction = world.state.auction.is_in_auction(&domain);
+
+    assert_eq!(is_in_auction, action == "is");
+}
+
+#[then(regex = r"'(.+)' domain (has|has not) the '(.+)' fuses burned")]
+fn has_fuses_burned(world: &mut ContractWorld, domain: String, action: String, fuses: String) {
+    let has_fuses = world.state.pns.has_fuses(&domain, get_fuses(fuses));
+
+    assert_eq!(has_fuses, action == "has");
+}
+
+#[then(expr = "'{word}' domain has {int} subdomains")]
+fn has_subdomains(world: &mut ContractWorld, domain: String, count: usize) {
+    let subdomains = world.state.pns.get_children(&domain);
+
+    assert_eq!(subdomains.len(), count);
+}
+
+#[then(expr = "'{word}' domain sold {int} subdomains")]
+fn sold_subdomains(world: &mut ContractWorld, domain: String, count: u32) {
+    let sale = world.state.pns.get_subdomain_sale(&domain).unwrap();
+
+    assert_eq!(sale.sold_count, count);
 }
 
 #[then(expr = "{word} mint count is {int}")]
@@ -490,11 +2250,23 @@ fn contract_config_is(world: &mut ContractWorld, key: String, value: String) {
             let value = value.parse::<u32>().unwrap();
             assert_eq!(config.mint_count_limit, value);
         }
+        "mint_count_window" => {
+            let value = value.parse::<i64>().unwrap();
+            assert_eq!(config.mint_count_window, value);
+        }
         _ => panic!("Unknown config key"),
     }
 }
 
 // Taken from partisia-name-system/tests/cucumber.rs
+#[then(expr = "'{word}' domain has a '{word}' record with '{word}' data")]
+fn domain_has_record(world: &mut ContractWorld, domain: String, class: String, data: String) {
+    let domain = world.state.pns.get_domain(&domain).unwrap();
+    let record = domain.get_record(&get_record_class_given(class)).unwrap();
+
+    assert_eq!(*record, data.into_bytes());
+}
+
 #[then(expr = "'{word}' domain does not have a '{word}' record")]
 fn domain_has_no_record(world: &mut ContractWorld, domain: String, class: String) {
     let domain = world.state.pns.get_domain(&domain);
@@ -506,16 +2278,25 @@ fn domain_has_no_record(world: &mut ContractWorld, domain: String, class: String
     }
 }
 
-#[then(regex = r"'(.+)' domain (does not expire|expires) in (\d+) years")]
-fn domain_expires_in(world: &mut ContractWorld, domain: String, action: String, years: u32) {
-    let domain = world.state.pns.get_domain(&domain);
+#[then(regex = r"'(.+)' domain (does not expire|expires) in (\d+) (years|days)")]
+fn domain_expires_in(
+    world: &mut ContractWorld,
+    domain: String,
+    action: String,
+    count: u32,
+    unit: String,
+) {
+    let expires_at = world.state.pns.get_expires_at(&domain);
 
-    let expected_expires_at = world.point_in_time + milliseconds_in_years(years as i64);
+    let duration = match unit.as_str() {
+        "years" => milliseconds_in_years(count as i64),
+        _ => count as i64 * 24 * 60 * 60 * 1000,
+    };
+    let expected_expires_at = world.point_in_time + duration;
     if action == "expires" {
-        let domain = domain.unwrap();
-        assert_eq!(domain.expires_at, Some(expected_expires_at));
-    } else if let Some(domain) = domain {
-        assert_ne!(domain.expires_at, Some(expected_expires_at));
+        assert_eq!(expires_at, Some(expected_expires_at));
+    } else {
+        assert_ne!(expires_at, Some(expected_expires_at));
     }
 }
 
@@ -530,6 +2311,35 @@ fn has_airdrop(world: &mut ContractWorld, user: String, action: String) {
     assert_eq!(has_airdrop, has);
 }
 
+#[then(regex = r"(\w+) (has|has not) the '(\w+)' campaign airdrop$")]
+fn has_campaign_airdrop(world: &mut ContractWorld, user: String, action: String, campaign: String) {
+    let has_airdrop = world
+        .state
+        .airdrop
+        .has_campaign_airdrop(&mock_address(get_address_for_user(user)), &campaign);
+
+    let has = action == "has";
+    assert_eq!(has_airdrop, has);
+}
+
+#[then(regex = r"(\w+) (has|has not) the '(\w+)' campaign airdrop for (\d+) chars domains")]
+fn has_campaign_airdrop_for_length(
+    world: &mut ContractWorld,
+    user: String,
+    action: String,
+    campaign: String,
+    length: u32,
+) {
+    let has_airdrop = world
+        .state
+        .airdrop
+        .get_campaign_allowance(&mock_address(get_address_for_user(user)), &campaign, length)
+        .is_some();
+
+    let has = action == "has";
+    assert_eq!(has_airdrop, has);
+}
+
 // This runs before everything else, so you can setup things here.
 fn main() {
     // You may choose any executor you like (`tokio`, `async-std`, etc.).
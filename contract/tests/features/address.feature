@@ -0,0 +1,45 @@
+Feature: Address records feature
+
+  Scenario: The addresses of many chains are set
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice sets the 'Partisia' address to '00b2e734b5d8da089318d0d2b076c19f59c450855a' for the 'mpc.name' domain
+    And Alice sets the 'Ethereum' address to '0x71C7656EC7ab88b098defB751B7401B5f6d8976F' for the 'mpc.name' domain
+    And Alice sets the 'Bitcoin' address to 'bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq' for the 'mpc.name' domain
+    Then 'mpc.name' domain resolves the 'Partisia' address to '00b2e734b5d8da089318d0d2b076c19f59c450855a'
+    And 'mpc.name' domain resolves the 'Ethereum' address to '0x71C7656EC7ab88b098defB751B7401B5f6d8976F'
+    And 'mpc.name' domain resolves the 'Bitcoin' address to 'bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq'
+
+  Scenario: The address is replaced and removed
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice set the 'Bitcoin' address to '1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2' for the 'mpc.name' domain
+    When Alice sets the 'Bitcoin' address to '3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy' for the 'mpc.name' domain
+    Then 'mpc.name' domain resolves the 'Bitcoin' address to '3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy'
+    When Alice removes the 'Bitcoin' address for the 'mpc.name' domain
+    Then 'mpc.name' domain does not resolve the 'Bitcoin' address
+
+  Scenario: The address of another user domain is not set
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Bob sets the 'Ethereum' address to '0x71C7656EC7ab88b098defB751B7401B5f6d8976F' for the 'mpc.name' domain
+    Then 'mpc.name' domain does not resolve the 'Ethereum' address
+
+  Scenario: The address of an expired domain does not resolve
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And Alice sets the 'Ethereum' address to '0x71C7656EC7ab88b098defB751B7401B5f6d8976F' for the 'mpc.name.sub' domain
+    Then 'mpc.name.sub' domain resolves the 'Ethereum' address to '0x71C7656EC7ab88b098defB751B7401B5f6d8976F'
+    When Alice sets the 'mpc.name.sub' subdomain expiration to 1 days ago
+    Then 'mpc.name.sub' domain does not resolve the 'Ethereum' address
+
+  Scenario: The addresses are removed on transfer with records
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice minted the 'Bio' record with 'data' data for the 'mpc.name' domain
+    And Alice set the 'Ethereum' address to '0x71C7656EC7ab88b098defB751B7401B5f6d8976F' for the 'mpc.name' domain
+    When Alice transfers the 'mpc.name' domain with its records to Bob
+    Then Bob owns 'mpc.name' domain
+    And 'mpc.name' domain has a 'Bio' record with 'data' data
+    And 'mpc.name' domain does not resolve the 'Ethereum' address
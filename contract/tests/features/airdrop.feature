@@ -10,3 +10,89 @@ Feature: Airdrop feature
     Given a meta names contract
     When Alice add airdrop to 'Bob'
     Then Bob has not the airdrop
+
+  Scenario: An user with airdrop role can add a campaign airdrop
+    Given a meta names contract
+    And Alice user with the airdrop role
+    And Alice created the 'launch' airdrop campaign for at least 0 chars domains
+    When Alice add airdrop to 'Bob' in the 'launch' campaign
+    Then Bob has the 'launch' campaign airdrop
+
+  Scenario: An user without airdrop role cannot add a campaign airdrop
+    Given a meta names contract
+    And contract user with the airdrop role
+    And contract created the 'launch' airdrop campaign for at least 0 chars domains
+    When Alice add airdrop to 'Bob' in the 'launch' campaign
+    Then Bob has not the 'launch' campaign airdrop
+
+  Scenario: An user without airdrop role cannot create an airdrop campaign
+    Given a meta names contract
+    When Alice creates the 'launch' airdrop campaign for at least 0 chars domains
+    And contract add airdrop to 'Bob' in the 'launch' campaign
+    Then Bob has not the 'launch' campaign airdrop
+
+  Scenario: An user cannot add airdrop to a revoked campaign
+    Given a meta names contract
+    And Alice user with the airdrop role
+    And Alice created the 'launch' airdrop campaign for at least 0 chars domains
+    When Alice revokes the 'launch' airdrop campaign
+    And Alice add airdrop to 'Bob' in the 'launch' campaign
+    Then Bob has not the 'launch' campaign airdrop
+
+  Scenario: An user with an airdrop mints a domain for free
+    Given a meta names contract
+    And Alice user with the airdrop role
+    And Alice created the 'launch' airdrop campaign for at least 5 chars domains
+    And Alice airdropped to 'Bob' in the 'launch' campaign
+    When Bob mints 'mpc.name' domain without a parent
+    Then Bob owns 'mpc.name' domain
+    And Bob has not the 'launch' campaign airdrop
+
+  Scenario: An user cannot use an airdrop for a domain not eligible for the campaign
+    Given a meta names contract
+    And Alice user with the airdrop role
+    And Alice created the 'launch' airdrop campaign for at least 10 chars domains
+    And Alice airdropped to 'Bob' in the 'launch' campaign
+    When Bob mints 'mpc.name' domain without a parent
+    Then 'mpc.name' domain is not minted
+    And Bob has the 'launch' campaign airdrop
+
+  Scenario: An user cannot use an airdrop of a revoked campaign
+    Given a meta names contract
+    And Alice user with the airdrop role
+    And Alice created the 'launch' airdrop campaign for at least 0 chars domains
+    And Alice airdropped to 'Bob' in the 'launch' campaign
+    When Alice revokes the 'launch' airdrop campaign
+    And Bob mints 'mpc.name' domain without a parent
+    Then 'mpc.name' domain is not minted
+
+  Scenario: An user with an airdrop from the inventory mints a domain for free
+    Given a meta names contract
+    And Alice user with the airdrop role
+    And Alice airdropped to 'Bob'
+    When Bob mints 'mpc.name' domain without a parent
+    Then Bob owns 'mpc.name' domain
+    And Bob has not the airdrop
+
+  Scenario: An user can claim a merkle airdrop with a valid proof
+    Given a meta names contract
+    And Alice user with the airdrop role
+    And Alice published the 'merkle' merkle airdrop campaign with '638905b99e916595bcd551c56bea0a59cdf7b831630e05b028af9744776521ef' root and 2 leaves
+    When Alice claims the 'merkle' merkle airdrop leaf 0 of 2 domains for at least 5 chars with '360af899bd822eba4926c93574adfae06abef9d6cb5e1155e154bc950e829c27' proof
+    Then Alice has the 'merkle' campaign airdrop for 5 chars domains
+    And Alice has not the 'merkle' campaign airdrop for 4 chars domains
+
+  Scenario: An user cannot claim a merkle airdrop of another user
+    Given a meta names contract
+    And Alice user with the airdrop role
+    And Alice published the 'merkle' merkle airdrop campaign with '638905b99e916595bcd551c56bea0a59cdf7b831630e05b028af9744776521ef' root and 2 leaves
+    When Bob claims the 'merkle' merkle airdrop leaf 0 of 2 domains for at least 5 chars with '360af899bd822eba4926c93574adfae06abef9d6cb5e1155e154bc950e829c27' proof
+    Then Bob has not the 'merkle' campaign airdrop for 5 chars domains
+
+  Scenario: An user with a claimed merkle airdrop mints a domain for free
+    Given a meta names contract
+    And Alice user with the airdrop role
+    And Alice published the 'merkle' merkle airdrop campaign with '638905b99e916595bcd551c56bea0a59cdf7b831630e05b028af9744776521ef' root and 2 leaves
+    When Alice claims the 'merkle' merkle airdrop leaf 0 of 2 domains for at least 5 chars with '360af899bd822eba4926c93574adfae06abef9d6cb5e1155e154bc950e829c27' proof
+    And Alice mints 'mpc.name' domain without a parent
+    Then Alice owns 'mpc.name' domain
@@ -0,0 +1,21 @@
+Feature: Domain alias feature
+
+  Scenario: The alias resolves to the records of the target domain
+    Given a meta names contract
+    And Alice minted 'mpc.shop' domain without a parent
+    And Alice minted 'mpc.store' domain without a parent
+    And Alice set the 'Ethereum' address to '0x71C7656EC7ab88b098defB751B7401B5f6d8976F' for the 'mpc.store' domain
+    When Alice sets the 'mpc.shop' domain alias to 'mpc.store'
+    Then 'mpc.shop' domain resolves to 'mpc.store' domain
+    And 'mpc.shop' domain resolves the 'Ethereum' address to '0x71C7656EC7ab88b098defB751B7401B5f6d8976F'
+
+  Scenario: The alias to an expired target does not resolve
+    Given a meta names contract
+    And Alice minted 'mpc.shop' domain without a parent
+    And Alice minted 'mpc.store' domain without a parent
+    When Alice mints 'mpc.store.sub' domain with 'mpc.store' domain as the parent
+    And Alice sets the 'mpc.shop' domain alias to 'mpc.store.sub'
+    Then 'mpc.shop' domain resolves to 'mpc.store.sub' domain
+    When Alice sets the 'mpc.store.sub' subdomain expiration to 1 days ago
+    Then 'mpc.shop' domain does not resolve
+
@@ -0,0 +1,88 @@
+Feature: Auction feature
+
+  Scenario: An admin can start an auction for a premium domain
+    Given a meta names contract
+    And contract flagged 'mpc.name' domain as premium
+    When contract starts an auction for 'mpc.name' domain with 10 reserve price
+    Then 'mpc.name' domain is in auction
+
+  Scenario: An user without the admin role cannot start an auction
+    Given a meta names contract
+    And contract flagged 'mpc.name' domain as premium
+    When Alice starts an auction for 'mpc.name' domain with 10 reserve price
+    Then 'mpc.name' domain is not in auction
+
+  Scenario: An admin cannot start an auction for a domain not flagged premium
+    Given a meta names contract
+    And contract flagged 'mpc.name' domain as premium
+    When contract starts an auction for 'mpc.name' domain with 10 reserve price
+    Then 'mpc.name' domain is in auction
+    And contract cannot start an auction for 'mpc.meta' domain
+
+  Scenario: A premium domain cannot be minted without an auction
+    Given a meta names contract
+    And contract flagged 'mpc.name' domain as premium
+    Then Bob can request the payment of the 'mpc.meta' domain mint
+    And Bob cannot request the payment of the 'mpc.name' domain mint
+
+  Scenario: A domain in auction cannot be minted
+    Given a meta names contract
+    And contract flagged 'mpc.name' domain as premium
+    And Alice user with the admin role
+    And contract started an auction for 'mpc.name' domain with 10 reserve price
+    When Alice mints 'mpc.name' domain without a parent
+    Then 'mpc.name' domain is not minted
+
+  Scenario: The highest bidder wins the auction
+    Given a meta names contract
+    And contract flagged 'mpc.name' domain as premium
+    And contract started an auction for 'mpc.name' domain with 10 reserve price
+    And Alice bid 50 with 60 deposit on the 'mpc.name' auction
+    And Bob bid 30 with 30 deposit on the 'mpc.name' auction
+    When Alice reveals the 50 bid on the 'mpc.name' auction
+    And Bob reveals the 30 bid on the 'mpc.name' auction
+    And Bob settles the 'mpc.name' auction
+    Then Alice owns 'mpc.name' domain
+    And 'mpc.name' domain is not in auction
+
+  Scenario: An unrevealed bid cannot win the auction
+    Given a meta names contract
+    And contract flagged 'mpc.name' domain as premium
+    And contract started an auction for 'mpc.name' domain with 10 reserve price
+    And Alice bid 50 with 50 deposit on the 'mpc.name' auction
+    And Bob bid 80 with 80 deposit on the 'mpc.name' auction
+    When Alice reveals the 50 bid on the 'mpc.name' auction
+    And Alice settles the 'mpc.name' auction
+    Then Alice owns 'mpc.name' domain
+
+  Scenario: An auction without bids above the reserve price does not mint the domain
+    Given a meta names contract
+    And contract flagged 'mpc.name' domain as premium
+    And contract started an auction for 'mpc.name' domain with 10 reserve price
+    And Alice bid 5 with 5 deposit on the 'mpc.name' auction
+    When Alice reveals the 5 bid on the 'mpc.name' auction
+    And Alice settles the 'mpc.name' auction
+    Then 'mpc.name' domain is not minted
+    And 'mpc.name' domain is not in auction
+
+  Scenario: A bid is removed when its deposit cannot be escrowed
+    Given a meta names contract
+    And contract flagged 'mpc.name' domain as premium
+    And contract started an auction for 'mpc.name' domain with 10 reserve price
+    When Alice bids 50 with 50 deposit on the 'mpc.name' auction
+    And Bob bids 30 with 30 deposit on the 'mpc.name' auction without paying
+    Then Alice has a bid on the 'mpc.name' auction
+    And Bob has not a bid on the 'mpc.name' auction
+
+  Scenario: An auction is settled when the supply cap of the domain length is reached
+    Given a meta names contract
+    And contract flagged 'mpc.name' domain as premium
+    And contract started an auction for 'mpc.name' domain with 10 reserve price
+    And Alice bid 50 with 50 deposit on the 'mpc.name' auction
+    And contract config supply cap for 8 chars domains is 1
+    And Bob minted 'mpc.meta' domain without a parent
+    When Alice reveals the 50 bid on the 'mpc.name' auction
+    And Bob settles the 'mpc.name' auction
+    Then Alice owns 'mpc.name' domain
+    And Alice mint count is 1
+    And Bob mint count is 1
@@ -0,0 +1,34 @@
+Feature: Content hash feature
+
+  Scenario: The IPFS content hash is set
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice sets the 'ipfs' content hash to '0170122029f2d17be6139079dc48696d1f582a8530eb9805b561eda517e22a892c7e3f1f' for the 'mpc.name' domain
+    Then 'mpc.name' domain has the 'ipfs' content hash '0170122029f2d17be6139079dc48696d1f582a8530eb9805b561eda517e22a892c7e3f1f'
+
+  Scenario: The Arweave content hash replaces the IPFS content hash
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice sets the 'ipfs' content hash to '0170122029f2d17be6139079dc48696d1f582a8530eb9805b561eda517e22a892c7e3f1f' for the 'mpc.name' domain
+    And Alice sets the 'arweave' content hash to '29f2d17be6139079dc48696d1f582a8530eb9805b561eda517e22a892c7e3f1f' for the 'mpc.name' domain
+    Then 'mpc.name' domain has the 'arweave' content hash '29f2d17be6139079dc48696d1f582a8530eb9805b561eda517e22a892c7e3f1f'
+
+  Scenario: The content hash is cleared
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice sets the 'ipns' content hash to '0172002408011220ecda4a77ba6bcc0b41bd0ecd57ac2e8ae54ac5ab9cd81b68b4ed2f4bab1b0f3a' for the 'mpc.name' domain
+    Then 'mpc.name' domain has the 'ipns' content hash '0172002408011220ecda4a77ba6bcc0b41bd0ecd57ac2e8ae54ac5ab9cd81b68b4ed2f4bab1b0f3a'
+    When Alice clears the content hash of the 'mpc.name' domain
+    Then 'mpc.name' domain does not have a content hash
+
+  Scenario: An invalid content hash is not set
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice sets the 'ipfs' content hash to '0170122029f2d17be6139079' for the 'mpc.name' domain
+    Then 'mpc.name' domain does not have a content hash
+
+  Scenario: The content hash of another user domain is not set
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Bob sets the 'ipfs' content hash to '0170122029f2d17be6139079dc48696d1f582a8530eb9805b561eda517e22a892c7e3f1f' for the 'mpc.name' domain
+    Then 'mpc.name' domain does not have a content hash
@@ -0,0 +1,24 @@
+Feature: DNS records feature
+
+  Scenario: The DNS records are set
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice sets the 'A' DNS record of '' to '192.0.2.1' for the 'mpc.name' domain
+    And Alice sets the 'AAAA' DNS record of '' to '2001:db8::1' for the 'mpc.name' domain
+    And Alice sets the 'TXT' DNS record of '' to 'v=spf1 -all' for the 'mpc.name' domain
+    And Alice sets the 'CNAME' DNS record of 'www' to 'metanames.app' for the 'mpc.name' domain
+    Then 'mpc.name' domain has 4 DNS records
+
+  Scenario: A CNAME record cannot share its name with other records
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice sets the 'CNAME' DNS record of 'www' to 'metanames.app' for the 'mpc.name' domain
+    Then 'mpc.name' domain has 1 DNS records
+    And Alice cannot set the 'A' DNS record of 'www' to '192.0.2.1' for the 'mpc.name' domain
+    And 'mpc.name' domain has 1 DNS records
+
+  Scenario: The DNS records of another user domain are not set
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Bob sets the 'A' DNS record of '' to '192.0.2.1' for the 'mpc.name' domain
+    Then 'mpc.name' domain has 0 DNS records
@@ -0,0 +1,30 @@
+Feature: Domain status feature
+
+  Scenario: The owner check of the domain owner
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    Then the 'mpc.name' domain owner check of Alice is 'owner'
+    And the 'mpc.name' domain owner check of Bob is 'denied'
+
+  Scenario: The owner check of an approved address
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice approved Bob on 'mpc.name' domain
+    Then the 'mpc.name' domain owner check of Bob is 'approved'
+
+  Scenario: The owner check of an unminted domain does not fail
+    Given a meta names contract
+    Then the 'mpc.name' domain owner check of Alice is 'denied'
+    And the 'mpc.name' domain status is 'not minted'
+
+  Scenario: The status of an active domain
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    Then the 'mpc.name' domain status is 'active'
+
+  Scenario: The status of an expired domain
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And Alice sets the 'mpc.name.sub' subdomain expiration to 1 days ago
+    Then the 'mpc.name.sub' domain status is 'expired'
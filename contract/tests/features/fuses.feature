@@ -0,0 +1,50 @@
+Feature: Fuses feature
+
+  Scenario: The owner burns the fuses of a domain
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice burns the 'CANNOT_TRANSFER,CANNOT_SET_RECORDS' fuses of 'mpc.name' domain
+    Then 'mpc.name' domain has the 'CANNOT_TRANSFER,CANNOT_SET_RECORDS' fuses burned
+    And 'mpc.name' domain has not the 'CANNOT_CREATE_SUBDOMAIN' fuses burned
+
+  Scenario: The burned fuses are kept when burning other fuses
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice burned the 'CANNOT_TRANSFER' fuses of 'mpc.name' domain
+    When Alice burns the 'CANNOT_SET_RECORDS' fuses of 'mpc.name' domain
+    Then 'mpc.name' domain has the 'CANNOT_TRANSFER,CANNOT_SET_RECORDS' fuses burned
+
+  Scenario: The parent owner reclaims a subdomain
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And Alice transfers the 'mpc.name.sub' domain to Bob
+    Then Bob owns 'mpc.name.sub' domain
+    When Alice reclaims 'mpc.name.sub' subdomain to Alice
+    Then Alice owns 'mpc.name.sub' domain
+
+  Scenario: The parent owner burns the parent control fuse of a subdomain
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And Alice transfers the 'mpc.name.sub' domain to Bob
+    And Alice burns the 'PARENT_CANNOT_CONTROL' fuses of 'mpc.name.sub' domain
+    Then 'mpc.name.sub' domain has the 'PARENT_CANNOT_CONTROL' fuses burned
+    And Bob owns 'mpc.name.sub' domain
+
+  Scenario: A domain with the subdomain creation fuse burned cannot create subdomains
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice burned the 'CANNOT_CREATE_SUBDOMAIN' fuses of 'mpc.name' domain
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    Then 'mpc.name.sub' domain is not minted
+
+  Scenario: The parent owner extends a subdomain after burning the parent control fuse
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And Alice sets the 'mpc.name.sub' subdomain expiration to 10 days from now
+    And Alice burns the 'PARENT_CANNOT_CONTROL' fuses of 'mpc.name.sub' domain
+    And Alice sets the 'mpc.name.sub' subdomain expiration to 20 days from now
+    Then 'mpc.name.sub' domain expires in 20 days
+    And Alice cannot set the 'mpc.name.sub' subdomain expiration to 5 days from now
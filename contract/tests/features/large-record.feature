@@ -0,0 +1,91 @@
+Feature: Large record feature
+
+  Scenario: The large record is readable once committed
+    Given a meta names contract
+    And contract pns config 'max_storage_quota_kb' is '4'
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice set the storage quota of 'mpc.name' domain to 4 KB
+    When Alice writes the 'avatar' large record of 2500 bytes for the 'mpc.name' domain
+    Then 'mpc.name' domain does not have the 'avatar' large record
+    When Alice commits the 'avatar' large record of 2500 bytes for the 'mpc.name' domain
+    Then 'mpc.name' domain has the 'avatar' large record of 2500 bytes
+
+  Scenario: The large record cannot exceed the storage quota
+    Given a meta names contract
+    And contract pns config 'max_storage_quota_kb' is '4'
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice set the storage quota of 'mpc.name' domain to 1 KB
+    When Alice writes the 'avatar' large record of 2500 bytes for the 'mpc.name' domain
+    And Alice commits the 'avatar' large record of 2500 bytes for the 'mpc.name' domain
+    Then 'mpc.name' domain does not have the 'avatar' large record
+
+  Scenario: The storage quota can be extended
+    Given a meta names contract
+    And contract pns config 'max_storage_quota_kb' is '4'
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice set the storage quota of 'mpc.name' domain to 1 KB
+    When Alice sets the storage quota of 'mpc.name' domain to 4 KB
+    And Alice writes the 'avatar' large record of 2500 bytes for the 'mpc.name' domain
+    And Alice commits the 'avatar' large record of 2500 bytes for the 'mpc.name' domain
+    Then 'mpc.name' domain has a storage quota of 4 KB
+    And 'mpc.name' domain has the 'avatar' large record of 2500 bytes
+
+  Scenario: The storage quota cannot exceed the configured maximum
+    Given a meta names contract
+    And contract pns config 'max_storage_quota_kb' is '4'
+    And Alice minted 'mpc.name' domain without a parent
+    Then 'mpc.name' domain has a storage quota of 0 KB
+    And Alice cannot set the storage quota of 'mpc.name' domain to 8 KB
+
+  Scenario: Only the owner can write a large record
+    Given a meta names contract
+    And contract pns config 'max_storage_quota_kb' is '4'
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice set the storage quota of 'mpc.name' domain to 4 KB
+    When Bob writes the 'avatar' large record of 100 bytes for the 'mpc.name' domain
+    And Bob commits the 'avatar' large record of 100 bytes for the 'mpc.name' domain
+    Then 'mpc.name' domain does not have the 'avatar' large record
+
+  Scenario: The deleted large record releases its storage
+    Given a meta names contract
+    And contract pns config 'max_storage_quota_kb' is '4'
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice set the storage quota of 'mpc.name' domain to 1 KB
+    And Alice wrote the 'avatar' large record of 1000 bytes for the 'mpc.name' domain
+    When Alice deletes the 'avatar' large record for the 'mpc.name' domain
+    And Alice writes the 'banner' large record of 1000 bytes for the 'mpc.name' domain
+    And Alice commits the 'banner' large record of 1000 bytes for the 'mpc.name' domain
+    Then 'mpc.name' domain has the 'banner' large record of 1000 bytes
+
+  Scenario: Only the owner can set the storage quota
+    Given a meta names contract
+    And contract pns config 'max_storage_quota_kb' is '4'
+    And Alice minted 'mpc.name' domain without a parent
+    Then 'mpc.name' domain has a storage quota of 0 KB
+    And Bob cannot set the storage quota of 'mpc.name' domain to 4 KB
+
+  Scenario: The storage quota cannot go below the storage in use
+    Given a meta names contract
+    And contract pns config 'max_storage_quota_kb' is '4'
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice set the storage quota of 'mpc.name' domain to 4 KB
+    And Alice wrote the 'avatar' large record of 2500 bytes for the 'mpc.name' domain
+    When Alice sets the storage quota of 'mpc.name' domain to 3 KB
+    Then 'mpc.name' domain has a storage quota of 3 KB
+    And Alice cannot set the storage quota of 'mpc.name' domain to 1 KB
+
+  Scenario: The admin sets the storage quota without payment
+    Given a meta names contract
+    And contract pns config 'max_storage_quota_kb' is '4'
+    And Alice minted 'mpc.name' domain without a parent
+    When contract sets the storage quota of 'mpc.name' domain to 4 KB
+    Then 'mpc.name' domain has a storage quota of 4 KB
+
+  Scenario: The storage quota of an expired domain cannot be set
+    Given a meta names contract
+    And contract pns config 'max_storage_quota_kb' is '4'
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And Alice sets the 'mpc.name.sub' subdomain expiration to 1 days ago
+    Then 'mpc.name.sub' domain has a storage quota of 0 KB
+    And Alice cannot set the storage quota of 'mpc.name.sub' domain to 1 KB
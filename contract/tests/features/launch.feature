@@ -0,0 +1,108 @@
+Feature: Launch phases feature
+
+  Scenario: The mint does not occur before the launch
+    Given a meta names contract
+    And the launch schedule is in the 'NotStarted' phase
+    And Alice user with the airdrop role
+    And Alice created the 'launch' airdrop campaign for at least 0 chars domains
+    And Alice airdropped to 'Bob' in the 'launch' campaign
+    When Bob mints 'mpc.name' domain without a parent
+    Then 'mpc.name' domain is not minted
+
+  Scenario: An admin can mint before the launch
+    Given a meta names contract
+    And the launch schedule is in the 'NotStarted' phase
+    And Alice user with the admin role
+    When Alice mints 'mpc.name' domain without a parent
+    Then Alice owns 'mpc.name' domain
+
+  Scenario: A sunrise claimant can mint the reserved domain during the sunrise
+    Given a meta names contract
+    And the launch schedule is in the 'Sunrise' phase
+    And contract reserved the 'mpc.name' domain for Bob during the sunrise
+    And Alice user with the airdrop role
+    And Alice created the 'launch' airdrop campaign for at least 0 chars domains
+    And Alice airdropped to 'Bob' in the 'launch' campaign
+    When Bob mints 'mpc.name' domain without a parent
+    Then Bob owns 'mpc.name' domain
+
+  Scenario: A sunrise claimant cannot mint another domain during the sunrise
+    Given a meta names contract
+    And the launch schedule is in the 'Sunrise' phase
+    And contract reserved the 'mpc.name' domain for Bob during the sunrise
+    And Alice user with the airdrop role
+    And Alice created the 'launch' airdrop campaign for at least 0 chars domains
+    And Alice airdropped to 'Bob' in the 'launch' campaign
+    When Bob mints 'mpc.other' domain without a parent
+    Then 'mpc.other' domain is not minted
+
+  Scenario: An user cannot mint a domain reserved for another claimant during the sunrise
+    Given a meta names contract
+    And the launch schedule is in the 'Sunrise' phase
+    And contract reserved the 'mpc.name' domain for Alice during the sunrise
+    And Alice user with the airdrop role
+    And Alice created the 'launch' airdrop campaign for at least 0 chars domains
+    And Alice airdropped to 'Bob' in the 'launch' campaign
+    When Bob mints 'mpc.name' domain without a parent
+    Then 'mpc.name' domain is not minted
+
+  Scenario: An user without the admin role cannot reserve a domain for the sunrise
+    Given a meta names contract
+    Then Bob cannot reserve the 'mpc.name' domain for Bob during the sunrise
+
+  Scenario: Any user can mint during the general availability
+    Given a meta names contract
+    And the launch schedule is in the 'GeneralAvailability' phase
+    And Alice user with the airdrop role
+    And Alice created the 'launch' airdrop campaign for at least 0 chars domains
+    And Alice airdropped to 'Bob' in the 'launch' campaign
+    When Bob mints 'mpc.name' domain without a parent
+    Then Bob owns 'mpc.name' domain
+
+  Scenario: A domain cannot be minted directly during the landrush
+    Given a meta names contract
+    And the launch schedule is in the 'Landrush' phase
+    And Alice user with the airdrop role
+    And Alice created the 'launch' airdrop campaign for at least 0 chars domains
+    And Alice airdropped to 'Bob' in the 'launch' campaign
+    When Bob mints 'mpc.name' domain without a parent
+    Then 'mpc.name' domain is not minted
+
+  Scenario: A landrush application starts the auction of the domain
+    Given a meta names contract
+    And the launch schedule is in the 'Landrush' phase
+    When Bob applies for the 'mpc.name' domain with a 5 bid and 5 deposit
+    Then 'mpc.name' domain is in auction
+    And Bob has a bid on the 'mpc.name' auction
+
+  Scenario: The highest landrush application wins the domain
+    Given a meta names contract
+    And the launch schedule is in the 'Landrush' phase
+    And Alice applied for the 'mpc.name' domain with a 50 bid and 60 deposit
+    And Bob applied for the 'mpc.name' domain with a 30 bid and 30 deposit
+    When Alice reveals the 50 bid on the 'mpc.name' auction
+    And Bob reveals the 30 bid on the 'mpc.name' auction
+    And Bob settles the 'mpc.name' auction
+    Then Alice owns 'mpc.name' domain
+    And 'mpc.name' domain is not in auction
+
+  Scenario: A landrush application below the landrush fees does not win the domain
+    Given a meta names contract
+    And the launch schedule is in the 'Landrush' phase
+    And Bob applied for the 'mpc.name' domain with a 1 bid and 1 deposit
+    Then 'mpc.name' domain is in auction
+    When Bob reveals the 1 bid on the 'mpc.name' auction
+    And Bob settles the 'mpc.name' auction
+    Then 'mpc.name' domain is not minted
+    And 'mpc.name' domain is not in auction
+
+  Scenario: A domain cannot be applied for outside of the landrush
+    Given a meta names contract
+    And the launch schedule is in the 'GeneralAvailability' phase
+    Then Bob cannot apply for the 'mpc.name' domain
+
+  Scenario: A premium domain cannot be applied for during the landrush
+    Given a meta names contract
+    And the launch schedule is in the 'Landrush' phase
+    And contract flagged 'mpc.name' domain as premium
+    Then Bob cannot apply for the 'mpc.name' domain
@@ -42,6 +42,70 @@ Feature: Mint feature
     When Alice mints 'mpc.name' domain without a parent
     Then 'mpc.name' domain is not minted
 
+  Scenario: The minting process of a domain without any parent stops exactly at the mint count limit
+    Given a meta names contract
+    And contract config 'mint_count_limit_enabled' is 'true'
+    And contract config 'mint_count_limit' is '1'
+    And Alice user with the airdrop role
+    And Alice created the 'launch' airdrop campaign for at least 0 chars domains
+    And Alice airdropped to 'Bob' in the 'launch' campaign
+    And Alice airdropped to 'Bob' in the 'launch' campaign
+    When Bob mints 'mpc.name' domain without a parent
+    Then Bob owns 'mpc.name' domain
+    And Bob mint count is 1
+    When Bob mints 'mpc.other' domain without a parent
+    Then 'mpc.other' domain is not minted
+
+  Scenario: The minting process of a domain without any parent uses the mint count limit of the user role
+    Given a meta names contract
+    And contract config 'mint_count_limit_enabled' is 'true'
+    And contract config 'mint_count_limit' is '0'
+    And contract config mint count limit for the whitelist role is 1 per 1 days
+    And Bob user with the whitelist role
+    And Alice user with the airdrop role
+    And Alice created the 'launch' airdrop campaign for at least 0 chars domains
+    And Alice airdropped to 'Bob' in the 'launch' campaign
+    And Alice airdropped to 'Bob' in the 'launch' campaign
+    When Bob mints 'mpc.name' domain without a parent
+    Then Bob owns 'mpc.name' domain
+    When Bob mints 'mpc.other' domain without a parent
+    Then 'mpc.other' domain is not minted
+
+  Scenario: The mint of a domain fails when the supply cap of the domain length is reached
+    Given a meta names contract
+    And contract config supply cap for 8 chars domains is 1
+    When Alice mints 'mpc.name' domain without fees and a parent
+    Then Alice owns 'mpc.name' domain
+    When Alice mints 'mpc.meta' domain without fees and a parent
+    Then 'mpc.meta' domain is not minted
+
+  Scenario: The payment of a mint is not requested when the supply cap is reached
+    Given a meta names contract
+    And contract config supply cap for 8 chars domains is 1
+    Then Alice can request the payment of the 'mpc.name' domain mint
+    When Alice mints 'mpc.name' domain without fees and a parent
+    Then Alice owns 'mpc.name' domain
+    And Bob can request the payment of the 'mpc.tenchars' domain mint
+    And Bob cannot request the payment of the 'mpc.meta' domain mint
+
+  Scenario: The mint of a domain fails when the supply cap of the launch phase is reached
+    Given a meta names contract
+    And contract config supply cap for 8 chars domains during the Sunrise phase is 1
+    And the launch schedule is in the 'Sunrise' phase
+    When Alice mints 'mpc.name' domain without fees and a parent
+    And Alice mints 'mpc.meta' domain without fees and a parent
+    Then 'mpc.meta' domain is not minted
+
+  Scenario: The supply cap of a launch phase only counts the domains minted during the phase
+    Given a meta names contract
+    And contract config supply cap for 8 chars domains during the Sunrise phase is 1
+    And the launch schedule is in the 'Sunrise' phase
+    When Alice mints 'mpc.name' domain without fees and a parent
+    And the launch schedule moves to the 'Landrush' phase
+    And Alice mints 'mpc.meta' domain without fees and a parent
+    Then Alice owns 'mpc.name' domain
+    And Alice owns 'mpc.meta' domain
+
   Scenario: The minting process of a domain without any parent, with a wrong payment token id, fails
     Given a meta names contract
     When Alice mints 'mpc.name' domain with 1 as payment token id and without a parent
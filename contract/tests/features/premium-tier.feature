@@ -0,0 +1,68 @@
+Feature: Premium tier feature
+
+  Scenario: The record limits are configurable
+    Given a meta names contract
+    And contract pns config 'max_record_data_length' is '8'
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints the 'Bio' record with 'short' data for the 'mpc.name' domain
+    Then 'mpc.name' domain has a 'Bio' record with 'short' data
+    When Alice updates the 'Bio' record with 'much-longer' data for the 'mpc.name' domain
+    Then 'mpc.name' domain does not have a 'Bio' record
+
+  Scenario: The admin upgrades a domain to a premium tier
+    Given a meta names contract
+    And contract pns config 'max_record_data_length' is '8'
+    And contract pns config has a premium tier of 32 max record data length for 10 tokens
+    And Alice minted 'mpc.name' domain without a parent
+    When contract upgrades the 'mpc.name' domain to the premium tier 0
+    And Alice mints the 'Bio' record with 'much-longer' data for the 'mpc.name' domain
+    Then 'mpc.name' domain has the premium tier 0
+    And 'mpc.name' domain has a 'Bio' record with 'much-longer' data
+
+  Scenario: The paid upgrade of a domain to a premium tier
+    Given a meta names contract
+    And contract pns config has a premium tier of 32 max record data length for 10 tokens
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice upgrades the 'mpc.name' domain to the premium tier 0 with 0 payment token id
+    Then 'mpc.name' domain has the premium tier 0
+
+  Scenario: Only the owner of a domain can pay for its upgrade
+    Given a meta names contract
+    And contract pns config has a premium tier of 32 max record data length for 10 tokens
+    And Alice minted 'mpc.name' domain without a parent
+    Then 'mpc.name' domain does not have a premium tier
+    And Bob cannot upgrade the 'mpc.name' domain to the premium tier 0 with 0 payment token id
+
+  Scenario: The paid upgrade is checked again once paid
+    Given a meta names contract
+    And contract pns config has a premium tier of 32 max record data length for 10 tokens
+    And Alice minted 'mpc.name' domain without a parent
+    Then 'mpc.name' domain does not have a premium tier
+    And Bob cannot complete the upgrade of the 'mpc.name' domain to the premium tier 0 with 0 payment token id
+
+  Scenario: A paid upgrade cannot downgrade the domain
+    Given a meta names contract
+    And contract pns config has a premium tier of 32 max record data length for 10 tokens
+    And contract pns config has a premium tier of 16 max record data length for 5 tokens
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice upgrades the 'mpc.name' domain to the premium tier 0 with 0 payment token id
+    Then 'mpc.name' domain has the premium tier 0
+    And Alice cannot upgrade the 'mpc.name' domain to the premium tier 1 with 0 payment token id
+
+  Scenario: The upgrade to an unknown premium tier fails
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When contract upgrades the 'mpc.name' domain to the premium tier 1
+    Then 'mpc.name' domain does not have a premium tier
+
+  Scenario: The price of a premium tier can be updated
+    Given a meta names contract
+    And contract pns config has a premium tier of 32 max record data length for 10 tokens
+    When contract updates the price of the premium tier 0 to 20 tokens
+    Then the premium tier 0 of the pns config costs 20 tokens
+
+  Scenario: A premium tier cannot be removed from the config
+    Given a meta names contract
+    And contract pns config has a premium tier of 32 max record data length for 10 tokens
+    And contract pns config has a premium tier of 16 max record data length for 5 tokens
+    Then contract cannot remove the premium tier 0
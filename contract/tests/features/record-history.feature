@@ -0,0 +1,46 @@
+Feature: Record history feature
+
+  Scenario: The record changes are tracked
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice minted the 'Bio' record with 'first' data for the 'mpc.name' domain
+    When Alice updates the 'Bio' record with 'second' data for the 'mpc.name' domain
+    Then the 'Bio' record of the 'mpc.name' domain is at version 2 with 1 revisions
+
+  Scenario: The record history is bounded by the config
+    Given a meta names contract
+    And contract pns config 'record_history_limit' is '1'
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice minted the 'Bio' record with 'first' data for the 'mpc.name' domain
+    When Alice updates the 'Bio' record with 'second' data for the 'mpc.name' domain
+    And Alice updates the 'Bio' record with 'third' data for the 'mpc.name' domain
+    Then the 'Bio' record of the 'mpc.name' domain is at version 3 with 1 revisions
+
+  Scenario: The custom record changes are tracked
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice minted the 'key' custom record with 'first' data for the 'mpc.name' domain
+    When Alice updates the 'key' custom record with 'second' data for the 'mpc.name' domain
+    Then the 'key' custom record of the 'mpc.name' domain is at version 2 by Alice
+
+  Scenario: The record history is cleared by a transfer
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice minted the 'key' custom record with 'first' data for the 'mpc.name' domain
+    And Alice approved Bob on 'mpc.name' domain
+    When Bob transfers the 'mpc.name' domain from Alice to Bob
+    Then Bob owns 'mpc.name' domain
+    And the 'key' custom record of the 'mpc.name' domain has no history
+
+  Scenario: The record history is kept by a transfer with the records
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice minted the 'key' custom record with 'first' data for the 'mpc.name' domain
+    When Alice transfers the 'mpc.name' domain with its records to Bob
+    Then Bob owns 'mpc.name' domain
+    And the 'key' custom record of the 'mpc.name' domain is at version 1 by Alice
+
+  Scenario: Only an admin can update the pns config
+    Given a meta names contract
+    When Alice updates the pns config 'record_history_limit' to '1'
+    Then the pns config 'record_history_limit' is '5'
@@ -0,0 +1,45 @@
+Feature: Resolver queries feature
+
+  Scenario: The record resolves with the domain owner
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice minted the 'Bio' record with 'hello' data for the 'mpc.name' domain
+    Then 'mpc.name' domain resolves the 'Bio' record to 'hello' owned by Alice
+
+  Scenario: The record of an alias resolves from the target domain
+    Given a meta names contract
+    And Alice minted 'mpc.shop' domain without a parent
+    And Bob minted 'mpc.store' domain without a parent
+    And Bob minted the 'Bio' record with 'store' data for the 'mpc.store' domain
+    When Alice sets the 'mpc.shop' domain alias to 'mpc.store'
+    Then 'mpc.shop' domain resolves the 'Bio' record to 'store' owned by Alice
+
+  Scenario: The record of an unminted subdomain resolves from the wildcard domain
+    Given a meta names contract
+    And Alice minted 'mpc.brand' domain without a parent
+    And Alice minted the 'Bio' record with 'brand' data for the 'mpc.brand' domain
+    When Alice enables the wildcard of the 'mpc.brand' domain
+    Then 'mpc.brand.item' domain resolves the 'Bio' record to 'brand' owned by Alice
+
+  Scenario: The record of an expired domain does not resolve
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And Alice sets the 'mpc.name.sub' subdomain expiration to 1 days ago
+    Then 'mpc.name.sub' domain does not resolve the 'Bio' record and is not active
+
+  Scenario: All the records resolve
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice minted the 'Bio' record with 'hello' data for the 'mpc.name' domain
+    And Alice minted the 'Twitter' record with 'meta_names' data for the 'mpc.name' domain
+    And Alice set the 'Ethereum' address to '0x71C7656EC7ab88b098defB751B7401B5f6d8976F' for the 'mpc.name' domain
+    Then 'mpc.name' domain resolves 2 records and 1 addresses
+
+  Scenario: Many domains resolve in a batch
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice minted the 'Bio' record with 'hello' data for the 'mpc.name' domain
+    And Alice minted 'mpc.shop' domain without a parent
+    When Alice sets the 'mpc.shop' domain alias to 'mpc.name'
+    Then the 'mpc.name,mpc.shop,mpc.none' domains resolve the 'Bio' records to 'hello,hello,' in a batch
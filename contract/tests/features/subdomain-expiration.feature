@@ -0,0 +1,58 @@
+Feature: Subdomain expiration feature
+
+  Scenario: A subdomain inherits the expiration of the parent
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And contract renewed 'mpc.name' domain for 1 years
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    Then 'mpc.name.sub' domain expires in 1 years
+
+  Scenario: The parent owner sets the expiration of a subdomain
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And Alice sets the 'mpc.name.sub' subdomain expiration to 1 days ago
+    Then 'mpc.name.sub' domain is not active
+    And 'mpc.name' domain is active
+
+  Scenario: A subdomain is not active when an intermediate parent is expired
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And Alice mints 'mpc.name.sub.deep' domain with 'mpc.name.sub' domain as the parent
+    And Alice sets the 'mpc.name.sub' subdomain expiration to 1 days ago
+    Then 'mpc.name.sub.deep' domain is not active
+    And 'mpc.name' domain is active
+
+
+  Scenario: A subdomain follows the renewals of the parent
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And contract renewed 'mpc.name' domain for 1 years
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And contract renews 'mpc.name' domain for 2 years
+    Then 'mpc.name.sub' domain expires in 2 years
+
+  Scenario: A subdomain expiration beyond the expiration of the parent is capped by the parent
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And contract renewed 'mpc.name' domain for 1 years
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And Alice sets the 'mpc.name.sub' subdomain expiration to 730 days from now
+    Then 'mpc.name.sub' domain expires in 1 years
+    When contract renews 'mpc.name' domain for 3 years
+    Then 'mpc.name.sub' domain expires in 730 days
+
+  Scenario: A subdomain cannot be renewed, it follows the expiration of the parent
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And contract renewed 'mpc.name' domain for 1 years
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    Then 'mpc.name.sub' domain expires in 1 years
+    And contract cannot renew 'mpc.name.sub' domain for 2 years
+
+  Scenario: An user cannot pay the renewal of a subdomain
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    Then Bob cannot renew 'mpc.name.sub' domain for 2 years
@@ -0,0 +1,28 @@
+Feature: Subdomain index feature
+
+  Scenario: The subdomains of a domain are indexed
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.one' domain with 'mpc.name' domain as the parent
+    And Alice mints 'mpc.name.two' domain with 'mpc.name' domain as the parent
+    And Alice mints 'mpc.name.one.deep' domain with 'mpc.name.one' domain as the parent
+    Then 'mpc.name' domain has 2 subdomains
+    And 'mpc.name.one' domain has 1 subdomains
+
+  Scenario: The subdomain mint stops at the maximum subdomains per domain
+    Given a meta names contract
+    And contract config 'max_subdomains_per_domain' is '1'
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.one' domain with 'mpc.name' domain as the parent
+    Then Alice owns 'mpc.name.one' domain
+    When Alice mints 'mpc.name.two' domain with 'mpc.name' domain as the parent
+    Then 'mpc.name.two' domain is not minted
+
+  Scenario: The subdomain mint stops at the maximum subdomain depth
+    Given a meta names contract
+    And contract config 'max_subdomain_depth' is '1'
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.one' domain with 'mpc.name' domain as the parent
+    Then Alice owns 'mpc.name.one' domain
+    When Alice mints 'mpc.name.one.deep' domain with 'mpc.name.one' domain as the parent
+    Then 'mpc.name.one.deep' domain is not minted
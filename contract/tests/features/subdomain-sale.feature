@@ -0,0 +1,73 @@
+Feature: Subdomain sale feature
+
+  Scenario: An user buys a subdomain from the sale of the parent owner
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice published a subdomain sale for 'mpc.name' domain at 10 price for 5 subdomains
+    When Bob buys 'mpc.name.bob' subdomain of 'mpc.name' domain
+    Then Bob owns 'mpc.name.bob' domain
+    And 'mpc.name' domain sold 1 subdomains
+
+  Scenario: An user buys a free subdomain from the sale of the parent owner
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice published a subdomain sale for 'mpc.name' domain at 0 price for 5 subdomains
+    When Bob buys 'mpc.name.bob' subdomain of 'mpc.name' domain
+    Then Bob owns 'mpc.name.bob' domain
+
+  Scenario: An user is refunded when the subdomain is taken before the payment is escrowed
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice published a subdomain sale for 'mpc.name' domain at 10 price for 5 subdomains
+    When Bob buys 'mpc.name.bob' subdomain of 'mpc.name' domain while Alice mints it
+    Then Alice owns 'mpc.name.bob' domain
+    And 'mpc.name' domain sold 0 subdomains
+
+  Scenario: An user cannot buy a subdomain when the sale is sold out
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice published a subdomain sale for 'mpc.name' domain at 10 price for 1 subdomains
+    When Bob buys 'mpc.name.one' subdomain of 'mpc.name' domain
+    Then Bob owns 'mpc.name.one' domain
+    When Bob buys 'mpc.name.two' subdomain of 'mpc.name' domain
+    Then 'mpc.name.two' domain is not minted
+
+  Scenario: An user cannot buy a subdomain when the sale is removed
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice published a subdomain sale for 'mpc.name' domain at 10 price for 5 subdomains
+    When Alice removes the subdomain sale for 'mpc.name' domain
+    And Bob buys 'mpc.name.bob' subdomain of 'mpc.name' domain
+    Then 'mpc.name.bob' domain is not minted
+
+  Scenario: An user cannot publish a subdomain sale for a domain of another user
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Bob publishes a subdomain sale for 'mpc.name' domain at 10 price for 5 subdomains
+    And Bob buys 'mpc.name.bob' subdomain of 'mpc.name' domain
+    Then 'mpc.name.bob' domain is not minted
+
+  Scenario: An user cannot mint a subdomain of a domain of another user
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Bob mints 'mpc.name.bob' domain with 'mpc.name' domain as the parent
+    Then 'mpc.name.bob' domain is not minted
+
+  Scenario: The subdomain sale of a domain is removed when the domain is transferred
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice published a subdomain sale for 'mpc.name' domain at 0 price for 5 subdomains
+    Then 'mpc.name' domain has subdomain sale
+    When Alice transfers the 'mpc.name' domain to Bob
+    Then Bob owns 'mpc.name' domain
+    And 'mpc.name' domain has no subdomain sale
+    When Alice buys 'mpc.name.alice' subdomain of 'mpc.name' domain
+    Then 'mpc.name.alice' domain is not minted
+
+  Scenario: The subdomain sale of a domain is removed when the domain is transferred with its records
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice published a subdomain sale for 'mpc.name' domain at 0 price for 5 subdomains
+    When Alice transfers the 'mpc.name' domain with its records to Bob
+    Then Bob owns 'mpc.name' domain
+    And 'mpc.name' domain has no subdomain sale
@@ -0,0 +1,92 @@
+Feature: Subdomain transfer policy feature
+
+  Scenario: The subdomains stay with their owners when the parent is transferred
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And Alice transfers the 'mpc.name' domain to Bob
+    Then Bob owns 'mpc.name' domain
+    And Alice owns 'mpc.name.sub' domain
+
+  Scenario: The subdomains of the previous owner are transferred along with the parent
+    Given a meta names contract
+    And contract config 'subdomain_transfer_policy' is 'TransferAlong'
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And Alice mints 'mpc.name.sub.deep' domain with 'mpc.name.sub' domain as the parent
+    And Alice transfers the 'mpc.name' domain to Bob
+    Then Bob owns 'mpc.name' domain
+    And Bob owns 'mpc.name.sub' domain
+    And Bob owns 'mpc.name.sub.deep' domain
+
+  Scenario: The subdomains of other owners are kept when transferring along with the parent
+    Given a meta names contract
+    And contract config 'subdomain_transfer_policy' is 'TransferAlong'
+    And Bob minted 'mpc.name' domain without a parent
+    When Bob mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And Bob mints 'mpc.name.bob' domain with 'mpc.name' domain as the parent
+    And Bob transfers the 'mpc.name.sub' domain to Alice
+    And Bob transfers the 'mpc.name' domain to contract
+    Then contract owns 'mpc.name.bob' domain
+    And Alice owns 'mpc.name.sub' domain
+
+  Scenario: The subdomains controlled by the parent are revoked when the parent is transferred
+    Given a meta names contract
+    And contract config 'subdomain_transfer_policy' is 'Revoke'
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And Alice mints 'mpc.name.kept' domain with 'mpc.name' domain as the parent
+    And Alice transfers the 'mpc.name.sub' domain to Bob
+    And Alice transfers the 'mpc.name.kept' domain to Bob
+    And Alice burns the 'PARENT_CANNOT_CONTROL' fuses of 'mpc.name.kept' domain
+    And Alice transfers the 'mpc.name' domain to contract
+    Then contract owns 'mpc.name' domain
+    And contract owns 'mpc.name.sub' domain
+    And Bob owns 'mpc.name.kept' domain
+
+  Scenario: The subdomains with the transfer fuse burned stay when transferring along with the parent
+    Given a meta names contract
+    And contract config 'subdomain_transfer_policy' is 'TransferAlong'
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And Alice mints 'mpc.name.locked' domain with 'mpc.name' domain as the parent
+    And Alice mints 'mpc.name.locked.deep' domain with 'mpc.name.locked' domain as the parent
+    And Alice burns the 'CANNOT_TRANSFER' fuses of 'mpc.name.locked' domain
+    And Alice transfers the 'mpc.name' domain to Bob
+    Then Bob owns 'mpc.name' domain
+    And Bob owns 'mpc.name.sub' domain
+    And Alice owns 'mpc.name.locked' domain
+    And Alice owns 'mpc.name.locked.deep' domain
+
+  Scenario: The subdomains with the transfer fuse burned are not revoked when the parent is transferred
+    Given a meta names contract
+    And contract config 'subdomain_transfer_policy' is 'Revoke'
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.locked' domain with 'mpc.name' domain as the parent
+    And Alice transfers the 'mpc.name.locked' domain to Bob
+    And Alice burns the 'CANNOT_TRANSFER' fuses of 'mpc.name.locked' domain
+    And Alice transfers the 'mpc.name' domain to contract
+    Then contract owns 'mpc.name' domain
+    And Bob owns 'mpc.name.locked' domain
+
+  Scenario: A subdomain with the transfer fuse burned cannot be reclaimed
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And Alice transfers the 'mpc.name.sub' domain to Bob
+    And Alice burns the 'CANNOT_TRANSFER' fuses of 'mpc.name.sub' domain
+    Then Bob owns 'mpc.name.sub' domain
+    And Alice cannot reclaim 'mpc.name.sub' subdomain to Alice
+
+  Scenario: The subdomains of a reclaimed subdomain follow the transfer policy
+    Given a meta names contract
+    And contract config 'subdomain_transfer_policy' is 'TransferAlong'
+    And Alice minted 'mpc.name' domain without a parent
+    When Alice mints 'mpc.name.sub' domain with 'mpc.name' domain as the parent
+    And Alice mints 'mpc.name.sub.deep' domain with 'mpc.name.sub' domain as the parent
+    And Alice transfers the 'mpc.name.sub' domain to Bob
+    Then Bob owns 'mpc.name.sub' domain
+    And Bob owns 'mpc.name.sub.deep' domain
+    When Alice reclaims 'mpc.name.sub' subdomain to Alice
+    Then Alice owns 'mpc.name.sub' domain
+    And Alice owns 'mpc.name.sub.deep' domain
@@ -3,7 +3,25 @@ Feature: Transfer feature
   Scenario: The transfer cleans all the existing records
     Given a meta names contract
     And Alice minted 'mpc.name' domain without a parent
-    And Alice minted the 'Wallet' record with 'data' data for the 'mpc.name' domain
+    And Alice minted the 'Wallet' record with '00b2e734b5d8da089318d0d2b076c19f59c450855a' data for the 'mpc.name' domain
     When Alice transfers the 'mpc.name' domain to Bob
     Then Bob owns 'mpc.name' domain
     And 'mpc.name' domain does not have a 'Wallet' record
+
+  Scenario: The transfer with records keeps the records except the wallet
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice minted the 'Bio' record with 'data' data for the 'mpc.name' domain
+    And Alice minted the 'Wallet' record with '00b2e734b5d8da089318d0d2b076c19f59c450855a' data for the 'mpc.name' domain
+    When Alice transfers the 'mpc.name' domain with its records to Bob
+    Then Bob owns 'mpc.name' domain
+    And 'mpc.name' domain has a 'Bio' record with 'data' data
+    And 'mpc.name' domain does not have a 'Wallet' record
+
+  Scenario: The transfer cleans the records of the other classes
+    Given a meta names contract
+    And Alice minted 'mpc.name' domain without a parent
+    And Alice minted the 'Bio' record with 'data' data for the 'mpc.name' domain
+    When Alice transfers the 'mpc.name' domain to Bob
+    Then Bob owns 'mpc.name' domain
+    And 'mpc.name' domain does not have a 'Bio' record
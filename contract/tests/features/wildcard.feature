@@ -0,0 +1,42 @@
+Feature: Wildcard subdomain feature
+
+  Scenario: The unminted subdomains resolve to the wildcard domain
+    Given a meta names contract
+    And Alice minted 'mpc.brand' domain without a parent
+    When Alice enables the wildcard of the 'mpc.brand' domain
+    Then 'mpc.brand.shop' domain resolves to 'mpc.brand' domain
+    And 'mpc.brand.shop.deep' domain resolves to 'mpc.brand' domain
+
+  Scenario: The minted subdomains resolve to themselves
+    Given a meta names contract
+    And Alice minted 'mpc.brand' domain without a parent
+    When Alice mints 'mpc.brand.shop' domain with 'mpc.brand' domain as the parent
+    And Alice enables the wildcard of the 'mpc.brand' domain
+    Then 'mpc.brand.shop' domain resolves to 'mpc.brand.shop' domain
+    And 'mpc.brand.other' domain resolves to 'mpc.brand' domain
+
+  Scenario: The nearest wildcard ancestor is used
+    Given a meta names contract
+    And Alice minted 'mpc.brand' domain without a parent
+    When Alice mints 'mpc.brand.shop' domain with 'mpc.brand' domain as the parent
+    And Alice enables the wildcard of the 'mpc.brand' domain
+    And Alice enables the wildcard of the 'mpc.brand.shop' domain
+    Then 'mpc.brand.shop.item' domain resolves to 'mpc.brand.shop' domain
+    And 'mpc.brand.item' domain resolves to 'mpc.brand' domain
+
+  Scenario: The disabled wildcard does not resolve the unminted subdomains
+    Given a meta names contract
+    And Alice minted 'mpc.brand' domain without a parent
+    When Alice enables the wildcard of the 'mpc.brand' domain
+    Then 'mpc.brand.shop' domain resolves to 'mpc.brand' domain
+    When Alice disables the wildcard of the 'mpc.brand' domain
+    Then 'mpc.brand.shop' domain does not resolve
+
+  Scenario: The expired wildcard ancestor does not resolve the unminted subdomains
+    Given a meta names contract
+    And Alice minted 'mpc.brand' domain without a parent
+    When Alice mints 'mpc.brand.shop' domain with 'mpc.brand' domain as the parent
+    And Alice enables the wildcard of the 'mpc.brand.shop' domain
+    Then 'mpc.brand.shop.item' domain resolves to 'mpc.brand.shop' domain
+    When Alice sets the 'mpc.brand.shop' subdomain expiration to 1 days ago
+    Then 'mpc.brand.shop.item' domain does not resolve
@@ -5,14 +5,21 @@ use pbc_contract_common::{
 };
 
 use crate::{
+    large_record::verify_large_record,
     msg::{
-        PnsCustomRecordDeleteMsg, PnsCustomRecordMintMsg, PnsCustomRecordUpdateMsg,
-        PnsDomainUpdateExpirationMsg, PnsMintMsg, PnsRecordDeleteAllMsg, PnsRecordDeleteMsg,
-        PnsRecordMintMsg, PnsRecordUpdateMsg,
+        PnsBurnFusesMsg, PnsCommitLargeRecordMsg, PnsCustomRecordDeleteMsg, PnsCustomRecordMintMsg,
+        PnsCustomRecordUpdateMsg, PnsDomainUpdateExpirationMsg, PnsLargeRecordDeleteMsg,
+        PnsMintMsg, PnsRecordDeleteAllMsg, PnsRecordDeleteMsg, PnsRecordMintMsg,
+        PnsRecordUpdateMsg, PnsSetAddressMsg, PnsSetAliasMsg, PnsSetContentHashMsg,
+        PnsSetDnsRecordsMsg, PnsSetPremiumTierMsg, PnsSetStorageQuotaMsg, PnsSetWildcardMsg,
+        PnsSubdomainSaleMsg, PnsWriteLargeRecordChunkMsg,
     },
     state::{
-        Domain, PartisiaNameSystemState, MAX_CUSTOM_RECORDS, MAX_DOMAIN_LEN, MAX_RECORD_DATA_LENGTH,
+        storage_quota_bytes, Domain, LargeRecord, PartisiaNameSystemState, PnsConfig,
+        SubdomainSale, CANNOT_CREATE_SUBDOMAIN, CANNOT_SET_RECORDS, MAX_LARGE_RECORD_CHUNK_SIZE,
+        TRANSFER_UNSAFE_RECORDS,
     },
+    validation::{validate_address, validate_content_hash, validate_dns_records, validate_record},
     ContractError,
 };
 
@@ -25,6 +32,11 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub fn execute_init(ctx: &ContractContext) -> PartisiaNameSystemState {
     PartisiaNameSystemState {
         domains: AvlTreeMap::new(),
+        children: AvlTreeMap::new(),
+        subdomain_sales: AvlTreeMap::new(),
+        config: PnsConfig::default(),
+        large_records: AvlTreeMap::new(),
+        record_histories: AvlTreeMap::new(),
         version: ContractVersionBase::new(CONTRACT_NAME, CONTRACT_VERSION),
     }
 }
@@ -46,6 +58,11 @@ pub fn execute_mint(
             "{}",
             ContractError::DomainExpired
         );
+        assert!(
+            !state.has_fuses(&parent_id, CANNOT_CREATE_SUBDOMAIN),
+            "{}",
+            ContractError::CannotCreateSubdomain
+        );
     }
 
     state.domains.insert(
@@ -54,12 +71,26 @@ pub fn execute_mint(
             token_id: msg.token_id,
             records: SortedVecMap::new(),
             custom_records: SortedVecMap::new(),
+            addresses: SortedVecMap::new(),
+            content_hash: None,
+            dns_records: vec![],
+            alias: None,
+            wildcard: false,
             minted_at: ctx.block_production_time,
             expires_at: msg.expires_at,
             parent_id: msg.parent_id.clone(),
+            fuses: 0,
+            premium_tier: None,
+            storage_quota_kb: 0,
         },
     );
 
+    if let Some(parent_id) = msg.parent_id.clone() {
+        let mut children = state.get_children(&parent_id);
+        children.push(msg.domain.clone());
+        state.children.insert(parent_id, children);
+    }
+
     vec![]
 }
 
@@ -78,12 +109,20 @@ pub fn execute_record_mint(
         ContractError::DomainExpired
     );
     assert!(
-        msg.data.clone().len() < MAX_RECORD_DATA_LENGTH,
+        !state.has_fuses(&msg.domain, CANNOT_SET_RECORDS),
         "{}",
-        ContractError::RecordDataTooLong
+        ContractError::CannotSetRecords
     );
+    assert_record_data_length(state, &msg.domain, &msg.data);
+    validate_record(&msg.class, &msg.data);
 
     let mut domain = state.domains.get(&msg.domain).unwrap();
+    state.track_record_change(
+        &msg.domain,
+        &msg.class,
+        ctx.sender,
+        ctx.block_production_time,
+    );
     domain.mint_record(&msg.class, &msg.data);
     state.domains.insert(msg.domain.clone(), domain);
 
@@ -104,6 +143,13 @@ pub fn execute_record_update(
         "{}",
         ContractError::DomainExpired
     );
+    assert!(
+        !state.has_fuses(&msg.domain, CANNOT_SET_RECORDS),
+        "{}",
+        ContractError::CannotSetRecords
+    );
+
+    assert_record_data_length(state, &msg.domain, &msg.data);
 
     let mut domain = state.domains.get(&msg.domain).unwrap();
     assert!(
@@ -111,7 +157,14 @@ pub fn execute_record_update(
         "{}",
         ContractError::NotFound
     );
+    validate_record(&msg.class, &msg.data);
 
+    state.track_record_change(
+        &msg.domain,
+        &msg.class,
+        ctx.sender,
+        ctx.block_production_time,
+    );
     domain.update_record_data(&msg.class, &msg.data);
     state.domains.insert(msg.domain.clone(), domain);
 
@@ -132,6 +185,11 @@ pub fn execute_record_delete(
         "{}",
         ContractError::DomainExpired
     );
+    assert!(
+        !state.has_fuses(&msg.domain, CANNOT_SET_RECORDS),
+        "{}",
+        ContractError::CannotSetRecords
+    );
 
     let mut domain = state.domains.get(&msg.domain).unwrap();
     assert!(
@@ -140,6 +198,12 @@ pub fn execute_record_delete(
         ContractError::NotFound
     );
 
+    state.track_record_change(
+        &msg.domain,
+        &msg.class,
+        ctx.sender,
+        ctx.block_production_time,
+    );
     domain.delete_record(&msg.class);
     state.domains.insert(msg.domain.clone(), domain);
 
@@ -148,6 +212,7 @@ pub fn execute_record_delete(
 
 /// Delete all records for a domain
 /// Does not require the domain to be active
+/// The record history is cleared too, the previous values are not left to the next owner
 pub fn execute_record_delete_all(
     ctx: &ContractContext,
     state: &mut PartisiaNameSystemState,
@@ -158,6 +223,41 @@ pub fn execute_record_delete_all(
     let mut domain = state.domains.get(&msg.domain).unwrap();
     domain.records = SortedVecMap::new();
     domain.custom_records = SortedVecMap::new();
+    domain.addresses = SortedVecMap::new();
+    domain.content_hash = None;
+    domain.dns_records = vec![];
+    domain.alias = None;
+    domain.wildcard = false;
+    state.domains.insert(msg.domain.clone(), domain);
+    state.large_records.remove(&msg.domain);
+    state.record_histories.remove(&msg.domain);
+
+    vec![]
+}
+
+/// Delete the records that are not safe to keep when the domain changes hands
+/// The address records and the alias are deleted too, the other records are kept
+pub fn execute_record_delete_transfer_unsafe(
+    ctx: &ContractContext,
+    state: &mut PartisiaNameSystemState,
+    msg: &PnsRecordDeleteAllMsg,
+) -> Vec<EventGroup> {
+    assert!(state.is_minted(&msg.domain), "{}", ContractError::NotFound);
+
+    let mut domain = state.domains.get(&msg.domain).unwrap();
+    for class in TRANSFER_UNSAFE_RECORDS {
+        if domain.is_record_minted(&class) {
+            state.track_record_change(
+                &msg.domain,
+                &class,
+                msg.updated_by,
+                ctx.block_production_time,
+            );
+            domain.records.remove_entry(&class);
+        }
+    }
+    domain.addresses = SortedVecMap::new();
+    domain.alias = None;
     state.domains.insert(msg.domain.clone(), domain);
 
     vec![]
@@ -178,18 +278,21 @@ pub fn execute_custom_record_mint(
         ContractError::DomainExpired
     );
     assert!(
-        msg.data.len() < MAX_RECORD_DATA_LENGTH,
+        !state.has_fuses(&msg.domain, CANNOT_SET_RECORDS),
         "{}",
-        ContractError::RecordDataTooLong
+        ContractError::CannotSetRecords
     );
+    assert_record_data_length(state, &msg.domain, &msg.data);
 
     let mut domain = state.domains.get(&msg.domain).unwrap();
     assert!(
-        domain.custom_records.len() < MAX_CUSTOM_RECORDS,
+        domain.custom_records.len()
+            < state.get_record_limits(&msg.domain).max_custom_records as usize,
         "{}",
         ContractError::MaxCustomRecords
     );
 
+    state.track_custom_record_change(&msg.domain, &msg.key, ctx.sender, ctx.block_production_time);
     domain.mint_custom_record(msg.key.as_str(), msg.data.as_slice());
     state.domains.insert(msg.domain.clone(), domain);
 
@@ -211,10 +314,11 @@ pub fn execute_custom_record_update(
         ContractError::DomainExpired
     );
     assert!(
-        msg.data.len() < MAX_RECORD_DATA_LENGTH,
+        !state.has_fuses(&msg.domain, CANNOT_SET_RECORDS),
         "{}",
-        ContractError::RecordDataTooLong
+        ContractError::CannotSetRecords
     );
+    assert_record_data_length(state, &msg.domain, &msg.data);
 
     let mut domain = state.domains.get(&msg.domain).unwrap();
     assert!(
@@ -223,6 +327,7 @@ pub fn execute_custom_record_update(
         ContractError::NotFound
     );
 
+    state.track_custom_record_change(&msg.domain, &msg.key, ctx.sender, ctx.block_production_time);
     domain.update_custom_record_data(msg.key.as_str(), msg.data.as_slice());
     state.domains.insert(msg.domain.clone(), domain);
 
@@ -243,6 +348,11 @@ pub fn execute_custom_record_delete(
         "{}",
         ContractError::DomainExpired
     );
+    assert!(
+        !state.has_fuses(&msg.domain, CANNOT_SET_RECORDS),
+        "{}",
+        ContractError::CannotSetRecords
+    );
 
     let mut domain = state.domains.get(&msg.domain).unwrap();
     assert!(
@@ -251,14 +361,371 @@ pub fn execute_custom_record_delete(
         ContractError::NotFound
     );
 
+    state.track_custom_record_change(&msg.domain, &msg.key, ctx.sender, ctx.block_production_time);
     domain.delete_custom_record(&msg.key);
     state.domains.insert(msg.domain.clone(), domain);
 
     vec![]
 }
 
+/// Set or remove the address of a coin type for a domain
+/// Returns [`Vec<EventGroup>`] if operation was successful,
+/// otherwise panics with error message defined in [`ContractError`]
+pub fn execute_set_address(
+    ctx: &ContractContext,
+    state: &mut PartisiaNameSystemState,
+    msg: &PnsSetAddressMsg,
+) -> Vec<EventGroup> {
+    assert!(state.is_minted(&msg.domain), "{}", ContractError::NotFound);
+    assert!(
+        state.is_active(&msg.domain, ctx.block_production_time),
+        "{}",
+        ContractError::DomainExpired
+    );
+    assert!(
+        !state.has_fuses(&msg.domain, CANNOT_SET_RECORDS),
+        "{}",
+        ContractError::CannotSetRecords
+    );
+    if let Some(address) = &msg.address {
+        validate_address(msg.coin_type, address);
+    }
+
+    let mut domain = state.domains.get(&msg.domain).unwrap();
+    domain.set_address(msg.coin_type, msg.address.clone());
+    state.domains.insert(msg.domain.clone(), domain);
+
+    vec![]
+}
+
+/// Set or clear the content hash of a domain
+/// Returns [`Vec<EventGroup>`] if operation was successful,
+/// otherwise panics with error message defined in [`ContractError`]
+pub fn execute_set_content_hash(
+    ctx: &ContractContext,
+    state: &mut PartisiaNameSystemState,
+    msg: &PnsSetContentHashMsg,
+) -> Vec<EventGroup> {
+    assert!(state.is_minted(&msg.domain), "{}", ContractError::NotFound);
+    assert!(
+        state.is_active(&msg.domain, ctx.block_production_time),
+        "{}",
+        ContractError::DomainExpired
+    );
+    assert!(
+        !state.has_fuses(&msg.domain, CANNOT_SET_RECORDS),
+        "{}",
+        ContractError::CannotSetRecords
+    );
+    if let Some(content_hash) = &msg.content_hash {
+        assert_record_data_length(state, &msg.domain, content_hash);
+        validate_content_hash(content_hash);
+    }
+
+    let mut domain = state.domains.get(&msg.domain).unwrap();
+    domain.content_hash = msg.content_hash.clone();
+    state.domains.insert(msg.domain.clone(), domain);
+
+    vec![]
+}
+
+/// Replace the DNS record set of a domain
+/// Returns [`Vec<EventGroup>`] if operation was successful,
+/// otherwise panics with error message defined in [`ContractError`]
+pub fn execute_set_dns_records(
+    ctx: &ContractContext,
+    state: &mut PartisiaNameSystemState,
+    msg: &PnsSetDnsRecordsMsg,
+) -> Vec<EventGroup> {
+    assert!(state.is_minted(&msg.domain), "{}", ContractError::NotFound);
+    assert!(
+        state.is_active(&msg.domain, ctx.block_production_time),
+        "{}",
+        ContractError::DomainExpired
+    );
+    assert!(
+        !state.has_fuses(&msg.domain, CANNOT_SET_RECORDS),
+        "{}",
+        ContractError::CannotSetRecords
+    );
+    validate_dns_records(&msg.records);
+
+    let mut domain = state.domains.get(&msg.domain).unwrap();
+    domain.dns_records = msg.records.clone();
+    state.domains.insert(msg.domain.clone(), domain);
+
+    vec![]
+}
+
+/// Set or remove the alias of a domain
+/// The alias cannot point to the domain itself nor create a cycle
+/// Returns [`Vec<EventGroup>`] if operation was successful,
+/// otherwise panics with error message defined in [`ContractError`]
+pub fn execute_set_alias(
+    ctx: &ContractContext,
+    state: &mut PartisiaNameSystemState,
+    msg: &PnsSetAliasMsg,
+) -> Vec<EventGroup> {
+    assert!(state.is_minted(&msg.domain), "{}", ContractError::NotFound);
+    assert!(
+        state.is_active(&msg.domain, ctx.block_production_time),
+        "{}",
+        ContractError::DomainExpired
+    );
+    assert!(
+        !state.has_fuses(&msg.domain, CANNOT_SET_RECORDS),
+        "{}",
+        ContractError::CannotSetRecords
+    );
+    if let Some(alias) = &msg.alias {
+        assert!(
+            *alias != msg.domain && state.is_minted(alias),
+            "{}",
+            ContractError::InvalidAlias
+        );
+        assert!(
+            !state.get_alias_chain(alias).contains(&msg.domain),
+            "{}",
+            ContractError::AliasCycle
+        );
+    }
+
+    let mut domain = state.domains.get(&msg.domain).unwrap();
+    domain.alias = msg.alias.clone();
+    state.domains.insert(msg.domain.clone(), domain);
+
+    vec![]
+}
+
+/// Enable or disable the wildcard of a domain
+/// With the wildcard enabled the unminted subdomains resolve to the domain
+/// Returns [`Vec<EventGroup>`] if operation was successful,
+/// otherwise panics with error message defined in [`ContractError`]
+pub fn execute_set_wildcard(
+    ctx: &ContractContext,
+    state: &mut PartisiaNameSystemState,
+    msg: &PnsSetWildcardMsg,
+) -> Vec<EventGroup> {
+    assert!(state.is_minted(&msg.domain), "{}", ContractError::NotFound);
+    assert!(
+        state.is_active(&msg.domain, ctx.block_production_time),
+        "{}",
+        ContractError::DomainExpired
+    );
+    assert!(
+        !state.has_fuses(&msg.domain, CANNOT_SET_RECORDS),
+        "{}",
+        ContractError::CannotSetRecords
+    );
+
+    let mut domain = state.domains.get(&msg.domain).unwrap();
+    domain.wildcard = msg.wildcard;
+    state.domains.insert(msg.domain.clone(), domain);
+
+    vec![]
+}
+
+/// Set the premium tier of a domain, which sets its record limits
+/// Records above the new limits are kept but cannot be changed until they fit
+/// Returns [`Vec<EventGroup>`] if operation was successful,
+/// otherwise panics with error message defined in [`ContractError`]
+pub fn execute_set_premium_tier(
+    ctx: &ContractContext,
+    state: &mut PartisiaNameSystemState,
+    msg: &PnsSetPremiumTierMsg,
+) -> Vec<EventGroup> {
+    assert!(state.is_minted(&msg.domain), "{}", ContractError::NotFound);
+    if let Some(premium_tier) = msg.premium_tier {
+        assert!(
+            state.config.get_premium_tier(premium_tier).is_some(),
+            "{}",
+            ContractError::InvalidPremiumTier
+        );
+    }
+
+    let mut domain = state.domains.get(&msg.domain).unwrap();
+    domain.premium_tier = msg.premium_tier;
+    state.domains.insert(msg.domain.clone(), domain);
+
+    vec![]
+}
+
+/// Write a chunk of a large record of a domain
+/// The record is uncommitted until [`execute_commit_large_record`] is called
+/// Returns [`Vec<EventGroup>`] if operation was successful,
+/// otherwise panics with error message defined in [`ContractError`]
+pub fn execute_write_large_record_chunk(
+    ctx: &ContractContext,
+    state: &mut PartisiaNameSystemState,
+    msg: &PnsWriteLargeRecordChunkMsg,
+) -> Vec<EventGroup> {
+    assert!(state.is_minted(&msg.domain), "{}", ContractError::NotFound);
+    assert!(
+        state.is_active(&msg.domain, ctx.block_production_time),
+        "{}",
+        ContractError::DomainExpired
+    );
+    assert!(
+        !state.has_fuses(&msg.domain, CANNOT_SET_RECORDS),
+        "{}",
+        ContractError::CannotSetRecords
+    );
+    assert!(
+        !msg.key.is_empty() && msg.data.len() <= MAX_LARGE_RECORD_CHUNK_SIZE,
+        "{}",
+        ContractError::InvalidLargeRecordChunk
+    );
+
+    let mut large_records = state.get_large_records(&msg.domain);
+    let mut large_record = large_records.get(&msg.key).cloned().unwrap_or(LargeRecord {
+        chunks: vec![],
+        commit: None,
+    });
+
+    let index = msg.index as usize;
+    assert!(
+        index <= large_record.chunks.len(),
+        "{}",
+        ContractError::InvalidLargeRecordChunk
+    );
+    let replaced_size = large_record
+        .chunks
+        .get(index)
+        .map_or(0, |chunk| chunk.len() as u32);
+
+    let domain = state.get_domain(&msg.domain).unwrap();
+    let storage_used =
+        state.get_storage_used(&msg.domain) as u64 - replaced_size as u64 + msg.data.len() as u64;
+    assert!(
+        storage_used <= storage_quota_bytes(domain.storage_quota_kb),
+        "{}",
+        ContractError::StorageQuotaExceeded
+    );
+
+    if index == large_record.chunks.len() {
+        large_record.chunks.push(msg.data.clone());
+    } else {
+        large_record.chunks[index] = msg.data.clone();
+    }
+    large_record.commit = None;
+
+    large_records.insert(msg.key.clone(), large_record);
+    state
+        .large_records
+        .insert(msg.domain.clone(), large_records);
+
+    vec![]
+}
+
+/// Commit a large record of a domain with the length and hash of its value
+/// Returns [`Vec<EventGroup>`] if operation was successful,
+/// otherwise panics with error message defined in [`ContractError`]
+pub fn execute_commit_large_record(
+    ctx: &ContractContext,
+    state: &mut PartisiaNameSystemState,
+    msg: &PnsCommitLargeRecordMsg,
+) -> Vec<EventGroup> {
+    assert!(state.is_minted(&msg.domain), "{}", ContractError::NotFound);
+    assert!(
+        state.is_active(&msg.domain, ctx.block_production_time),
+        "{}",
+        ContractError::DomainExpired
+    );
+    assert!(
+        !state.has_fuses(&msg.domain, CANNOT_SET_RECORDS),
+        "{}",
+        ContractError::CannotSetRecords
+    );
+
+    let mut large_records = state.get_large_records(&msg.domain);
+    let large_record = large_records.get(&msg.key).cloned();
+    assert!(large_record.is_some(), "{}", ContractError::NotFound);
+
+    let mut large_record = large_record.unwrap();
+    assert!(
+        verify_large_record(&large_record.chunks, &msg.commit),
+        "{}",
+        ContractError::LargeRecordCommitMismatch
+    );
+
+    large_record.commit = Some(msg.commit.clone());
+    large_records.insert(msg.key.clone(), large_record);
+    state
+        .large_records
+        .insert(msg.domain.clone(), large_records);
+
+    vec![]
+}
+
+/// Delete a large record of a domain, releasing its storage
+/// Returns [`Vec<EventGroup>`] if operation was successful,
+/// otherwise panics with error message defined in [`ContractError`]
+pub fn execute_large_record_delete(
+    ctx: &ContractContext,
+    state: &mut PartisiaNameSystemState,
+    msg: &PnsLargeRecordDeleteMsg,
+) -> Vec<EventGroup> {
+    assert!(state.is_minted(&msg.domain), "{}", ContractError::NotFound);
+    assert!(
+        state.is_active(&msg.domain, ctx.block_production_time),
+        "{}",
+        ContractError::DomainExpired
+    );
+    assert!(
+        !state.has_fuses(&msg.domain, CANNOT_SET_RECORDS),
+        "{}",
+        ContractError::CannotSetRecords
+    );
+
+    let mut large_records = state.get_large_records(&msg.domain);
+    assert!(
+        large_records.remove_entry(&msg.key).is_some(),
+        "{}",
+        ContractError::NotFound
+    );
+    state
+        .large_records
+        .insert(msg.domain.clone(), large_records);
+
+    vec![]
+}
+
+/// Set the storage quota of the large records of a domain
+/// The quota cannot exceed the configured maximum nor go below the storage in use
+/// Returns [`Vec<EventGroup>`] if operation was successful,
+/// otherwise panics with error message defined in [`ContractError`]
+pub fn execute_set_storage_quota(
+    ctx: &ContractContext,
+    state: &mut PartisiaNameSystemState,
+    msg: &PnsSetStorageQuotaMsg,
+) -> Vec<EventGroup> {
+    assert!(state.is_minted(&msg.domain), "{}", ContractError::NotFound);
+    assert!(
+        state.is_active(&msg.domain, ctx.block_production_time),
+        "{}",
+        ContractError::DomainExpired
+    );
+    assert!(
+        msg.storage_quota_kb <= state.config.max_storage_quota_kb,
+        "{}",
+        ContractError::InvalidStorageQuota
+    );
+    assert!(
+        state.get_storage_used(&msg.domain) as u64 <= storage_quota_bytes(msg.storage_quota_kb),
+        "{}",
+        ContractError::StorageQuotaExceeded
+    );
+
+    let mut domain = state.domains.get(&msg.domain).unwrap();
+    domain.storage_quota_kb = msg.storage_quota_kb;
+    state.domains.insert(msg.domain.clone(), domain);
+
+    vec![]
+}
+
 ///## Description
 /// Update the expiration date for a domain
+/// A subdomain expiration is capped by the expiration of its parents when read
 pub fn execute_update_expiration(
     ctx: &ContractContext,
     state: &mut PartisiaNameSystemState,
@@ -273,17 +740,130 @@ pub fn execute_update_expiration(
     vec![]
 }
 
+///## Description
+/// Burn fuses of a domain, burned fuses cannot be restored
+pub fn execute_burn_fuses(
+    ctx: &ContractContext,
+    state: &mut PartisiaNameSystemState,
+    msg: &PnsBurnFusesMsg,
+) -> Vec<EventGroup> {
+    assert!(state.is_minted(&msg.domain), "{}", ContractError::NotFound);
+
+    let mut domain = state.domains.get(&msg.domain).unwrap();
+    domain.burn_fuses(msg.fuses);
+    state.domains.insert(msg.domain.clone(), domain);
+
+    vec![]
+}
+
+///## Description
+/// Publish or replace the subdomain sale policy of a domain
+/// The sold count is kept when the policy is replaced
+pub fn execute_set_subdomain_sale(
+    ctx: &ContractContext,
+    state: &mut PartisiaNameSystemState,
+    msg: &PnsSubdomainSaleMsg,
+) -> Vec<EventGroup> {
+    assert!(state.is_minted(&msg.domain), "{}", ContractError::NotFound);
+    assert!(
+        state.is_active(&msg.domain, ctx.block_production_time),
+        "{}",
+        ContractError::DomainExpired
+    );
+    assert!(
+        !matches!(msg.expiry_length, Some(length) if length <= 0),
+        "{}",
+        ContractError::InvalidSubdomainSale
+    );
+
+    let sold_count = state
+        .get_subdomain_sale(&msg.domain)
+        .map_or(0, |sale| sale.sold_count);
+
+    state.subdomain_sales.insert(
+        msg.domain.clone(),
+        SubdomainSale {
+            price: msg.price,
+            payment_coin_id: msg.payment_coin_id,
+            max_subdomains: msg.max_subdomains,
+            expiry_length: msg.expiry_length,
+            sold_count,
+        },
+    );
+
+    vec![]
+}
+
+///## Description
+/// Remove the subdomain sale policy of a domain
+pub fn execute_remove_subdomain_sale(
+    ctx: &ContractContext,
+    state: &mut PartisiaNameSystemState,
+    domain: &str,
+) -> Vec<EventGroup> {
+    assert!(
+        state.subdomain_sales.contains_key(&domain.to_string()),
+        "{}",
+        ContractError::SubdomainSaleNotFound
+    );
+
+    state.subdomain_sales.remove(&domain.to_string());
+
+    vec![]
+}
+
+///## Description
+/// Clear the subdomain sale policy of a domain changing owner, if any
+pub fn execute_clear_subdomain_sale(state: &mut PartisiaNameSystemState, domain: &str) {
+    state.subdomain_sales.remove(&domain.to_string());
+}
+
+///## Description
+/// Count a subdomain sold under the parent domain
+/// Returns the sale policy used for the subdomain
+pub fn execute_sell_subdomain(
+    ctx: &ContractContext,
+    state: &mut PartisiaNameSystemState,
+    parent_id: &str,
+) -> SubdomainSale {
+    let sale = state.get_subdomain_sale(parent_id);
+    assert!(sale.is_some(), "{}", ContractError::SubdomainSaleNotFound);
+
+    let mut sale = sale.unwrap();
+    assert!(
+        sale.is_available(),
+        "{}",
+        ContractError::SubdomainSaleSoldOut
+    );
+
+    sale.sold_count += 1;
+    state
+        .subdomain_sales
+        .insert(parent_id.to_string(), sale.clone());
+
+    sale
+}
+
 /// Validate the domain name
 /// Returns [`()`] if operation was successful,
 /// otherwise panics with error message defined in [`ContractError`]
-pub fn validate_domain(domain: &str) {
+pub fn validate_domain(config: &PnsConfig, domain: &str) {
     assert!(
-        domain.len() <= MAX_DOMAIN_LEN,
+        domain.len() <= config.max_domain_len as usize,
         "{}",
         ContractError::InvalidDomain
     )
 }
 
+/// Checks the record data against the record limits of the domain
+fn assert_record_data_length(state: &PartisiaNameSystemState, domain: &str, data: &[u8]) {
+    assert!(
+        data.len() < state.get_record_limits(domain).max_record_data_length as usize,
+        "{}",
+        ContractError::RecordDataTooLong
+    );
+}
+
 /// Validate the domain name with parent
 /// Returns [`()`] if operation was successful,
 /// otherwise panics with error message defined in [`ContractError`]
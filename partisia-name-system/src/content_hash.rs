@@ -0,0 +1,113 @@
+/// Multicodec codes of the supported content protocols
+pub const IPFS_NS: u64 = 0xe3;
+pub const SWARM_NS: u64 = 0xe4;
+pub const IPNS_NS: u64 = 0xe5;
+pub const ARWEAVE_NS: u64 = 0xb29910;
+
+const CID_V1: u64 = 0x01;
+const ARWEAVE_TX_ID_LEN: usize = 32;
+/// A varint of a u64 takes at most 10 bytes
+const MAX_VARINT_LEN: usize = 10;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum ContentProtocol {
+    Ipfs,
+    Ipns,
+    Swarm,
+    Arweave,
+}
+
+impl ContentProtocol {
+    /// Multicodec code of the protocol
+    pub fn code(&self) -> u64 {
+        match self {
+            ContentProtocol::Ipfs => IPFS_NS,
+            ContentProtocol::Ipns => IPNS_NS,
+            ContentProtocol::Swarm => SWARM_NS,
+            ContentProtocol::Arweave => ARWEAVE_NS,
+        }
+    }
+
+    pub fn from_code(code: u64) -> Option<ContentProtocol> {
+        match code {
+            IPFS_NS => Some(ContentProtocol::Ipfs),
+            IPNS_NS => Some(ContentProtocol::Ipns),
+            SWARM_NS => Some(ContentProtocol::Swarm),
+            ARWEAVE_NS => Some(ContentProtocol::Arweave),
+            _ => None,
+        }
+    }
+}
+
+/// Encodes the content identifier of the protocol as a content hash record
+/// The record is the varint multicodec of the protocol followed by the content identifier, as in ENSIP-7
+pub fn encode_content_hash(protocol: ContentProtocol, content_id: &[u8]) -> Vec<u8> {
+    let mut content_hash = encode_varint(protocol.code());
+    content_hash.extend_from_slice(content_id);
+    content_hash
+}
+
+/// Decodes a content hash record into its protocol and content identifier
+/// Returns `None` if the protocol is not supported
+pub fn decode_content_hash(content_hash: &[u8]) -> Option<(ContentProtocol, Vec<u8>)> {
+    let (code, content_id) = decode_varint(content_hash)?;
+    let protocol = ContentProtocol::from_code(code)?;
+
+    Some((protocol, content_id.to_vec()))
+}
+
+/// Checks the content identifier of the record
+/// IPFS, IPNS and Swarm identifiers are CIDv1, Arweave identifiers are transaction ids
+pub fn is_valid_content_hash(content_hash: &[u8]) -> bool {
+    match decode_content_hash(content_hash) {
+        Some((ContentProtocol::Arweave, content_id)) => content_id.len() == ARWEAVE_TX_ID_LEN,
+        Some((_, content_id)) => is_valid_cid_v1(&content_id),
+        None => false,
+    }
+}
+
+/// CIDv1: version, content codec and a multihash whose digest length matches
+fn is_valid_cid_v1(cid: &[u8]) -> bool {
+    let Some((CID_V1, rest)) = decode_varint(cid) else {
+        return false;
+    };
+    let Some((_codec, multihash)) = decode_varint(rest) else {
+        return false;
+    };
+    let Some((_hash_function, rest)) = decode_varint(multihash) else {
+        return false;
+    };
+
+    match decode_varint(rest) {
+        Some((digest_len, digest)) => digest_len > 0 && digest.len() as u64 == digest_len,
+        None => false,
+    }
+}
+
+/// Unsigned LEB128 varint as used by multiformats
+pub fn encode_varint(mut value: u64) -> Vec<u8> {
+    let mut bytes = vec![];
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return bytes;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// Decodes an unsigned LEB128 varint, returns the value and the remaining bytes
+pub fn decode_varint(bytes: &[u8]) -> Option<(u64, &[u8])> {
+    let mut value: u64 = 0;
+    for (index, byte) in bytes.iter().take(MAX_VARINT_LEN).enumerate() {
+        let bits = u64::from(byte & 0x7f);
+        value |= bits.checked_shl(7 * index as u32)?;
+        if byte & 0x80 == 0 {
+            return Some((value, &bytes[index + 1..]));
+        }
+    }
+
+    None
+}
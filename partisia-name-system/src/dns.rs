@@ -0,0 +1,101 @@
+use crate::state::{DnsRecord, DnsRecordData};
+
+/// Resource record types, see RFC 1035 and RFC 3596
+pub const TYPE_A: u16 = 1;
+pub const TYPE_CNAME: u16 = 5;
+pub const TYPE_MX: u16 = 15;
+pub const TYPE_TXT: u16 = 16;
+pub const TYPE_AAAA: u16 = 28;
+pub const TYPE_SRV: u16 = 33;
+pub const CLASS_IN: u16 = 1;
+
+const MAX_CHARACTER_STRING_LEN: usize = 255;
+
+impl DnsRecordData {
+    /// Resource record type of the data
+    pub fn record_type(&self) -> u16 {
+        match self {
+            DnsRecordData::A { .. } => TYPE_A,
+            DnsRecordData::Aaaa { .. } => TYPE_AAAA,
+            DnsRecordData::Cname { .. } => TYPE_CNAME,
+            DnsRecordData::Txt { .. } => TYPE_TXT,
+            DnsRecordData::Mx { .. } => TYPE_MX,
+            DnsRecordData::Srv { .. } => TYPE_SRV,
+        }
+    }
+}
+
+/// Serializes the records of a domain as RFC 1035 resource records, without name compression
+/// The domain is given as stored, e.g. `mpc.name`, and is written in DNS order, e.g. `name.mpc`
+pub fn to_wire_format(domain: &str, records: &[DnsRecord]) -> Vec<u8> {
+    let origin = to_dns_order(domain);
+    let mut wire = vec![];
+
+    for record in records {
+        let owner = match record.name.as_str() {
+            "" => origin.to_string(),
+            name => format!("{name}.{origin}"),
+        };
+        let rdata = encode_rdata(&record.data);
+
+        wire.extend(encode_name(&owner));
+        wire.extend(record.data.record_type().to_be_bytes());
+        wire.extend(CLASS_IN.to_be_bytes());
+        wire.extend(record.ttl.to_be_bytes());
+        wire.extend((rdata.len() as u16).to_be_bytes());
+        wire.extend(rdata);
+    }
+
+    wire
+}
+
+/// Reverses the labels of a stored domain name, e.g. `mpc.name` becomes `name.mpc`
+pub fn to_dns_order(domain: &str) -> String {
+    domain.split('.').rev().collect::<Vec<&str>>().join(".")
+}
+
+fn encode_rdata(data: &DnsRecordData) -> Vec<u8> {
+    match data {
+        DnsRecordData::A { address } | DnsRecordData::Aaaa { address } => address.clone(),
+        DnsRecordData::Cname { target } => encode_name(target),
+        DnsRecordData::Txt { text } => encode_character_strings(text.as_bytes()),
+        DnsRecordData::Mx {
+            preference,
+            exchange,
+        } => [preference.to_be_bytes().to_vec(), encode_name(exchange)].concat(),
+        DnsRecordData::Srv {
+            priority,
+            weight,
+            port,
+            target,
+        } => [
+            priority.to_be_bytes().to_vec(),
+            weight.to_be_bytes().to_vec(),
+            port.to_be_bytes().to_vec(),
+            encode_name(target),
+        ]
+        .concat(),
+    }
+}
+
+/// Length prefixed labels terminated by the root label
+pub fn encode_name(name: &str) -> Vec<u8> {
+    let mut encoded = vec![];
+    for label in name.split('.').filter(|label| !label.is_empty()) {
+        encoded.push(label.len() as u8);
+        encoded.extend(label.as_bytes());
+    }
+    encoded.push(0);
+    encoded
+}
+
+/// Text split into length prefixed character strings of at most 255 bytes
+fn encode_character_strings(text: &[u8]) -> Vec<u8> {
+    if text.is_empty() {
+        return vec![0];
+    }
+
+    text.chunks(MAX_CHARACTER_STRING_LEN)
+        .flat_map(|chunk| std::iter::once(chunk.len() as u8).chain(chunk.iter().copied()))
+        .collect()
+}
@@ -32,4 +32,76 @@ pub enum ContractError {
 
     #[error("The specified domain is expired")]
     DomainExpired,
+
+    #[error("The wallet record must be a hex encoded address")]
+    InvalidWalletRecord,
+
+    #[error("The email record must be a valid email address")]
+    InvalidEmailRecord,
+
+    #[error("The uri record must be a valid URI")]
+    InvalidUriRecord,
+
+    #[error("The twitter record must be a valid twitter handle")]
+    InvalidTwitterRecord,
+
+    #[error("The discord record must be a valid discord username")]
+    InvalidDiscordRecord,
+
+    #[error("The coin type is not supported")]
+    UnsupportedCoinType,
+
+    #[error("The address is not valid for the coin type")]
+    InvalidAddress,
+
+    #[error("The content hash is not valid")]
+    InvalidContentHash,
+
+    #[error("The DNS record is not valid")]
+    InvalidDnsRecord,
+
+    #[error("A name with a CNAME record cannot have other DNS records")]
+    DnsCnameConflict,
+
+    #[error("The maximum amount of DNS records has been reached")]
+    MaxDnsRecords,
+
+    #[error("The alias must point to another minted domain")]
+    InvalidAlias,
+
+    #[error("The alias creates a cycle")]
+    AliasCycle,
+
+    #[error("The given fuses are not valid")]
+    InvalidFuses,
+
+    #[error("The records of the domain cannot be set")]
+    CannotSetRecords,
+
+    #[error("The domain cannot create subdomains")]
+    CannotCreateSubdomain,
+
+    #[error("The specified domain has no subdomain sale")]
+    SubdomainSaleNotFound,
+
+    #[error("The subdomain sale is sold out")]
+    SubdomainSaleSoldOut,
+
+    #[error("The subdomain sale is not valid")]
+    InvalidSubdomainSale,
+
+    #[error("The premium tier is not valid")]
+    InvalidPremiumTier,
+
+    #[error("The large record chunk is not valid")]
+    InvalidLargeRecordChunk,
+
+    #[error("The large record does not match the commit")]
+    LargeRecordCommitMismatch,
+
+    #[error("The storage quota of the domain is exceeded")]
+    StorageQuotaExceeded,
+
+    #[error("The storage quota is not valid")]
+    InvalidStorageQuota,
 }
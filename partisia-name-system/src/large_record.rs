@@ -0,0 +1,31 @@
+use sha3::Digest;
+
+use crate::state::{LargeRecordCommit, MAX_LARGE_RECORD_CHUNK_SIZE};
+
+/// Keccak256 hash of the value of a large record
+pub fn large_record_hash(value: &[u8]) -> Vec<u8> {
+    sha3::Keccak256::digest(value).to_vec()
+}
+
+/// Builds the commit finalizing a large record with the given value
+pub fn large_record_commit(value: &[u8]) -> LargeRecordCommit {
+    LargeRecordCommit {
+        length: value.len() as u32,
+        hash: large_record_hash(value),
+    }
+}
+
+/// Splits a value into the chunks to write, in order
+pub fn split_into_chunks(value: &[u8]) -> Vec<Vec<u8>> {
+    value
+        .chunks(MAX_LARGE_RECORD_CHUNK_SIZE)
+        .map(|chunk| chunk.to_vec())
+        .collect()
+}
+
+/// Checks the chunks against the commit, so readers can verify the value they fetched
+pub fn verify_large_record(chunks: &[Vec<u8>], commit: &LargeRecordCommit) -> bool {
+    let value = chunks.concat();
+
+    value.len() as u32 == commit.length && large_record_hash(&value) == commit.hash
+}
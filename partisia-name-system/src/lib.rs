@@ -1,9 +1,13 @@
 #![allow(unused_variables)]
 
 pub mod actions;
+pub mod content_hash;
+pub mod dns;
 mod error;
+pub mod large_record;
 pub mod msg;
 pub mod state;
+pub mod validation;
 
 pub use crate::error::ContractError;
 
@@ -1,7 +1,8 @@
 use create_type_spec_derive::CreateTypeSpec;
+use pbc_contract_common::address::Address;
 use read_write_rpc_derive::ReadWriteRPC;
 
-use crate::state::RecordClass;
+use crate::state::{DnsRecord, LargeRecordCommit, RecordClass};
 
 /// This structure describes fields for PNS mint msg
 #[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
@@ -69,6 +70,8 @@ pub struct PnsCustomRecordDeleteMsg {
 #[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
 pub struct PnsRecordDeleteAllMsg {
     pub domain: String,
+    /// Recorded as the author of the deletion in the record history
+    pub updated_by: Address,
 }
 
 /// This structure describes fields for the Domain Update Expiration Msg
@@ -77,3 +80,104 @@ pub struct PnsDomainUpdateExpirationMsg {
     pub domain: String,
     pub expires_at: Option<i64>,
 }
+
+/// This structure describes fields for the Subdomain Sale Msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct PnsSubdomainSaleMsg {
+    pub domain: String,
+    pub price: u128,
+    /// BYOC token id
+    pub payment_coin_id: u64,
+    pub max_subdomains: Option<u32>,
+    /// Subdomain expiry length in milliseconds
+    pub expiry_length: Option<i64>,
+}
+
+/// This structure describes fields for the Burn Fuses Msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct PnsBurnFusesMsg {
+    pub domain: String,
+    /// Bitmask of the fuses to burn
+    pub fuses: u32,
+}
+
+/// This structure describes fields for the Set Address Msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct PnsSetAddressMsg {
+    pub domain: String,
+    /// SLIP-44 coin type
+    pub coin_type: u32,
+    /// `None` removes the address
+    pub address: Option<Vec<u8>>,
+}
+
+/// This structure describes fields for the Set Content Hash Msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct PnsSetContentHashMsg {
+    pub domain: String,
+    /// `None` clears the content hash
+    pub content_hash: Option<Vec<u8>>,
+}
+
+/// This structure describes fields for the Set DNS Records Msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct PnsSetDnsRecordsMsg {
+    pub domain: String,
+    /// Replaces the whole DNS record set, empty clears it
+    pub records: Vec<DnsRecord>,
+}
+
+/// This structure describes fields for the Set Alias Msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct PnsSetAliasMsg {
+    pub domain: String,
+    /// `None` removes the alias
+    pub alias: Option<String>,
+}
+
+/// This structure describes fields for the Set Wildcard Msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct PnsSetWildcardMsg {
+    pub domain: String,
+    pub wildcard: bool,
+}
+
+/// This structure describes fields for the Set Premium Tier Msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct PnsSetPremiumTierMsg {
+    pub domain: String,
+    /// `None` restores the default limits
+    pub premium_tier: Option<u32>,
+}
+
+/// This structure describes fields for the Write Large Record Chunk Msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct PnsWriteLargeRecordChunkMsg {
+    pub domain: String,
+    pub key: String,
+    /// Overwrites the chunk at the index, or appends it when the index is the chunks count
+    pub index: u32,
+    pub data: Vec<u8>,
+}
+
+/// This structure describes fields for the Commit Large Record Msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct PnsCommitLargeRecordMsg {
+    pub domain: String,
+    pub key: String,
+    pub commit: LargeRecordCommit,
+}
+
+/// This structure describes fields for the Large Record Delete Msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct PnsLargeRecordDeleteMsg {
+    pub domain: String,
+    pub key: String,
+}
+
+/// This structure describes fields for the Set Storage Quota Msg
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct PnsSetStorageQuotaMsg {
+    pub domain: String,
+    pub storage_quota_kb: u32,
+}
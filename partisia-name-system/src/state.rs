@@ -1,14 +1,49 @@
 use contract_version_base::state::ContractVersionBase;
 use create_type_spec_derive::CreateTypeSpec;
-use pbc_contract_common::{avl_tree_map::AvlTreeMap, sorted_vec_map::SortedVecMap};
+use pbc_contract_common::{
+    address::Address, avl_tree_map::AvlTreeMap, sorted_vec_map::SortedVecMap,
+};
 use read_write_rpc_derive::ReadWriteRPC;
 use read_write_state_derive::ReadWriteState;
+use utils::time::milliseconds_in_years;
 
 use crate::ContractError;
 
-pub const MAX_RECORD_DATA_LENGTH: usize = 64;
-pub const MAX_DOMAIN_LEN: usize = 32;
-pub const MAX_CUSTOM_RECORDS: usize = 10;
+/// Limits used unless configured otherwise, see [`PnsConfig`]
+pub const DEFAULT_MAX_RECORD_DATA_LENGTH: u32 = 64;
+pub const DEFAULT_MAX_DOMAIN_LEN: u32 = 32;
+pub const DEFAULT_MAX_CUSTOM_RECORDS: u32 = 10;
+
+/// Records removed on transfer even when the records are kept
+pub const TRANSFER_UNSAFE_RECORDS: [RecordClass; 1] = [RecordClass::Wallet {}];
+
+/// Fuses of a domain, once burned they cannot be restored
+pub const CANNOT_TRANSFER: u32 = 1;
+pub const CANNOT_SET_RECORDS: u32 = 1 << 1;
+pub const CANNOT_CREATE_SUBDOMAIN: u32 = 1 << 2;
+/// The parent owner cannot reclaim the subdomain nor burn its fuses
+pub const PARENT_CANNOT_CONTROL: u32 = 1 << 3;
+pub const ALL_FUSES: u32 =
+    CANNOT_TRANSFER | CANNOT_SET_RECORDS | CANNOT_CREATE_SUBDOMAIN | PARENT_CANNOT_CONTROL;
+
+/// SLIP-44 coin types of the supported address records
+pub const COIN_TYPE_BITCOIN: u32 = 0;
+pub const COIN_TYPE_ETHEREUM: u32 = 60;
+pub const COIN_TYPE_PARTISIA: u32 = 3757;
+
+/// Highest address type byte of a Wallet record, from an account (0x00) to a ZK contract (0x03)
+pub const MAX_ADDRESS_TYPE: u8 = 0x03;
+
+pub const MAX_DNS_RECORDS: usize = 16;
+/// Maximum amount of aliases followed when resolving a domain
+pub const MAX_ALIAS_HOPS: usize = 8;
+/// Maximum amount of domains resolved in a single batch
+pub const MAX_RESOLVE_BATCH_SIZE: usize = 500;
+/// Previous values kept in the history of each record unless configured otherwise
+pub const DEFAULT_RECORD_HISTORY_LIMIT: u32 = 5;
+/// Maximum size of a chunk of a large record, in bytes
+pub const MAX_LARGE_RECORD_CHUNK_SIZE: usize = 1024;
+pub const STORAGE_QUOTA_UNIT: u32 = 1024;
 
 /// This structure describes Partisia Name System state
 #[repr(C)]
@@ -16,6 +51,47 @@ pub const MAX_CUSTOM_RECORDS: usize = 10;
 pub struct PartisiaNameSystemState {
     pub version: ContractVersionBase,
     pub domains: AvlTreeMap<String, Domain>,
+    /// Direct subdomains of each parent domain, in mint order
+    pub children: AvlTreeMap<String, Vec<String>>,
+    pub subdomain_sales: AvlTreeMap<String, SubdomainSale>,
+    pub config: PnsConfig,
+    /// Large records of each domain by key, see [`crate::large_record`]
+    pub large_records: AvlTreeMap<String, SortedVecMap<String, LargeRecord>>,
+    /// Change trail of the records of each domain, cleared when the domain changes hands
+    pub record_histories: AvlTreeMap<String, RecordHistories>,
+}
+
+/// Limits of the name system, updated by the contract admins
+#[repr(C)]
+#[derive(ReadWriteState, ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct PnsConfig {
+    /// Previous values kept in the history of each record, 0 keeps none
+    pub record_history_limit: u32,
+    pub max_domain_len: u32,
+    /// Record limits of the domains without a premium tier
+    pub record_limits: RecordLimits,
+    /// The premium tier of a domain is its index in this list, tiers can only be appended
+    pub premium_tiers: Vec<PremiumTier>,
+    /// Yearly price of a KB of large record storage, scaled by the decimals of the payment fees
+    pub storage_price_per_kb: u128,
+    pub max_storage_quota_kb: u32,
+}
+
+#[repr(C)]
+#[derive(ReadWriteState, ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct RecordLimits {
+    /// Record data must be shorter than this length
+    pub max_record_data_length: u32,
+    pub max_custom_records: u32,
+}
+
+/// Larger record limits a domain can be upgraded to
+#[repr(C)]
+#[derive(ReadWriteState, ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct PremiumTier {
+    pub record_limits: RecordLimits,
+    /// Upgrade price, scaled by the decimals of the payment fees
+    pub price: u128,
 }
 
 #[repr(C)]
@@ -28,6 +104,130 @@ pub struct Domain {
     pub expires_at: Option<i64>,
     pub records: SortedVecMap<RecordClass, Vec<u8>>,
     pub custom_records: SortedVecMap<String, Vec<u8>>,
+    /// Address records keyed by SLIP-44 coin type
+    pub addresses: SortedVecMap<u32, Vec<u8>>,
+    /// Multicodec encoded content hash, see [`crate::content_hash`]
+    pub content_hash: Option<Vec<u8>>,
+    pub dns_records: Vec<DnsRecord>,
+    /// Domain whose records this domain resolves to
+    pub alias: Option<String>,
+    /// The unminted subdomains resolve to this domain
+    pub wildcard: bool,
+    /// Burned fuses
+    pub fuses: u32,
+    /// Index of the premium tier in the config, `None` for the default limits
+    pub premium_tier: Option<u32>,
+    /// Storage available to the large records, billed when set and on renewal
+    pub storage_quota_kb: u32,
+}
+
+/// Record value too large for a single record, written in numbered chunks
+/// The value is only served once committed
+#[repr(C)]
+#[derive(ReadWriteState, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct LargeRecord {
+    pub chunks: Vec<Vec<u8>>,
+    /// Cleared when a chunk is written
+    pub commit: Option<LargeRecordCommit>,
+}
+
+/// Length and keccak256 hash of the value of a large record
+#[repr(C)]
+#[derive(ReadWriteState, ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct LargeRecordCommit {
+    pub length: u32,
+    pub hash: Vec<u8>,
+}
+
+/// Change trails of the records and custom records of a domain
+#[repr(C)]
+#[derive(ReadWriteState, CreateTypeSpec, Clone, PartialEq, Eq, Default, Debug)]
+pub struct RecordHistories {
+    pub records: SortedVecMap<RecordClass, RecordHistory>,
+    pub custom_records: SortedVecMap<String, RecordHistory>,
+}
+
+/// Last change of a record along with its previous values
+#[repr(C)]
+#[derive(ReadWriteState, ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct RecordHistory {
+    /// Incremented on each change of the record
+    pub version: u32,
+    /// Unix millis timestamp
+    pub updated_at: i64,
+    pub updated_by: Address,
+    /// Previous values of the record, oldest first
+    pub revisions: Vec<RecordRevision>,
+}
+
+/// Value of a record before a change
+#[repr(C)]
+#[derive(ReadWriteState, ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct RecordRevision {
+    pub version: u32,
+    /// `None` when the record was deleted
+    pub data: Option<Vec<u8>>,
+    /// Unix millis timestamp
+    pub updated_at: i64,
+    pub updated_by: Address,
+}
+
+/// Sale policy published by a parent owner to sell subdomains to the public
+#[repr(C)]
+#[derive(ReadWriteState, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct SubdomainSale {
+    pub price: u128,
+    /// BYOC token id
+    pub payment_coin_id: u64,
+    pub max_subdomains: Option<u32>,
+    /// Subdomain expiry length in milliseconds, `None` means no expiry
+    pub expiry_length: Option<i64>,
+    pub sold_count: u32,
+}
+
+/// DNS resource record of the domain, served by off-chain gateways
+#[derive(ReadWriteState, ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct DnsRecord {
+    /// Owner name relative to the domain in DNS order, empty for the domain itself
+    pub name: String,
+    /// Time to live in seconds
+    pub ttl: u32,
+    pub data: DnsRecordData,
+}
+
+#[repr(u8)]
+#[derive(ReadWriteState, ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub enum DnsRecordData {
+    #[discriminant(0)]
+    A { address: Vec<u8> },
+    #[discriminant(1)]
+    Aaaa { address: Vec<u8> },
+    #[discriminant(2)]
+    Cname { target: String },
+    #[discriminant(3)]
+    Txt { text: String },
+    #[discriminant(4)]
+    Mx { preference: u16, exchange: String },
+    #[discriminant(5)]
+    Srv {
+        priority: u16,
+        weight: u16,
+        port: u16,
+        target: String,
+    },
+}
+
+/// Compact resolution of a domain, see [`PartisiaNameSystemState::resolve_batch`]
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct BatchResolvedDomain {
+    pub domain: String,
+    /// Token of the domain, or of the wildcard ancestor for unminted domains
+    pub token_id: Option<u128>,
+    /// Unix millis timestamp, capped by the expiration of the parents
+    pub expires_at: Option<i64>,
+    pub active: bool,
+    /// Data of the selected record classes, in the order of the selection
+    pub records: Vec<Option<Vec<u8>>>,
 }
 
 #[repr(u8)]
@@ -61,6 +261,50 @@ pub enum RecordClass {
     Custom5 {},
 }
 
+impl Default for PnsConfig {
+    fn default() -> Self {
+        PnsConfig {
+            record_history_limit: DEFAULT_RECORD_HISTORY_LIMIT,
+            max_domain_len: DEFAULT_MAX_DOMAIN_LEN,
+            record_limits: RecordLimits {
+                max_record_data_length: DEFAULT_MAX_RECORD_DATA_LENGTH,
+                max_custom_records: DEFAULT_MAX_CUSTOM_RECORDS,
+            },
+            premium_tiers: vec![],
+            storage_price_per_kb: 0,
+            max_storage_quota_kb: 0,
+        }
+    }
+}
+
+impl PnsConfig {
+    pub fn get_premium_tier(&self, tier: u32) -> Option<&PremiumTier> {
+        self.premium_tiers.get(tier as usize)
+    }
+
+    /// Whether the premium tiers of the previous config are kept, in the same order and limits
+    /// The domains reference their tier by index, only the price of a tier can change
+    pub fn keeps_premium_tiers(&self, previous: &PnsConfig) -> bool {
+        self.premium_tiers.len() >= previous.premium_tiers.len()
+            && previous
+                .premium_tiers
+                .iter()
+                .zip(self.premium_tiers.iter())
+                .all(|(previous, tier)| previous.record_limits == tier.record_limits)
+    }
+
+    /// Yearly price of the storage quota, scaled by the decimals of the payment fees
+    pub fn get_storage_price(&self, storage_quota_kb: u32) -> u128 {
+        storage_quota_kb as u128 * self.storage_price_per_kb
+    }
+
+    /// Price of the storage quota for the remaining time of a subscription, prorated from the yearly price
+    pub fn get_prorated_storage_price(&self, storage_quota_kb: u32, remaining_millis: i64) -> u128 {
+        self.get_storage_price(storage_quota_kb) * remaining_millis.max(0) as u128
+            / milliseconds_in_years(1) as u128
+    }
+}
+
 impl Domain {
     /// Get record given class
     pub fn get_record(&self, class: &RecordClass) -> Option<&Vec<u8>> {
@@ -82,6 +326,35 @@ impl Domain {
         self.custom_records.contains_key(key)
     }
 
+    /// Get address given SLIP-44 coin type
+    pub fn get_address(&self, coin_type: u32) -> Option<&Vec<u8>> {
+        self.addresses.get(&coin_type)
+    }
+
+    /// Sets the address of the coin type, `None` removes it
+    pub fn set_address(&mut self, coin_type: u32, address: Option<Vec<u8>>) {
+        match address {
+            Some(address) => {
+                self.addresses.insert(coin_type, address);
+            }
+            None => {
+                self.addresses.remove_entry(&coin_type);
+            }
+        }
+    }
+
+    /// Checks if all the given fuses are burned
+    pub fn has_fuses(&self, fuses: u32) -> bool {
+        self.fuses & fuses == fuses
+    }
+
+    /// Burns the given fuses, the already burned fuses are kept
+    pub fn burn_fuses(&mut self, fuses: u32) {
+        assert!(fuses & !ALL_FUSES == 0, "{}", ContractError::InvalidFuses);
+
+        self.fuses |= fuses;
+    }
+
     /// Checks if domain is active
     /// Opposite of expired
     pub fn is_active(&self, unix_millis_now: i64) -> bool {
@@ -166,6 +439,62 @@ impl Domain {
     }
 }
 
+impl RecordHistory {
+    /// Builds the history following a change, the current value is kept as a revision
+    /// and the oldest revisions beyond the limit are dropped
+    fn next(
+        history: Option<&RecordHistory>,
+        data: Option<&Vec<u8>>,
+        updated_by: Address,
+        updated_at: i64,
+        history_limit: u32,
+    ) -> RecordHistory {
+        match history {
+            Some(history) => {
+                let mut revisions = history.revisions.clone();
+                revisions.push(RecordRevision {
+                    version: history.version,
+                    data: data.cloned(),
+                    updated_at: history.updated_at,
+                    updated_by: history.updated_by,
+                });
+                let excess = revisions.len().saturating_sub(history_limit as usize);
+                revisions.drain(..excess);
+
+                RecordHistory {
+                    version: history.version + 1,
+                    updated_at,
+                    updated_by,
+                    revisions,
+                }
+            }
+            None => RecordHistory {
+                version: 1,
+                updated_at,
+                updated_by,
+                revisions: vec![],
+            },
+        }
+    }
+}
+
+impl LargeRecord {
+    /// Bytes stored by the chunks
+    pub fn size(&self) -> u32 {
+        self.chunks.iter().map(|chunk| chunk.len() as u32).sum()
+    }
+}
+
+impl SubdomainSale {
+    /// Checks if subdomains can still be sold
+    pub fn is_available(&self) -> bool {
+        match self.max_subdomains {
+            Some(max_subdomains) => self.sold_count < max_subdomains,
+            None => true,
+        }
+    }
+}
+
 impl PartisiaNameSystemState {
     /// Returns info given domain
     pub fn get_domain(&self, domain_name: &str) -> Option<Domain> {
@@ -173,19 +502,183 @@ impl PartisiaNameSystemState {
     }
 
     /// Returns if the domain is active
-    /// If the domain is a subdomain, it checks if the parent is active
+    /// If the domain is a subdomain, it checks if all the parents are active
     pub fn is_active(&self, domain_name: &str, unix_millis_now: i64) -> bool {
         match self.get_domain(domain_name) {
             Some(domain) => {
                 domain.is_active(unix_millis_now)
                     && self
-                        .get_root_parent(domain_name)
-                        .map_or(true, |parent| parent.is_active(unix_millis_now))
+                        .get_parents(domain_name)
+                        .iter()
+                        .all(|parent| parent.is_active(unix_millis_now))
             }
             None => false,
         }
     }
 
+    /// Resolves the domain following its aliases,
+    /// an unminted domain falls back to its nearest wildcard ancestor
+    /// Returns the name and info of the domain holding the records,
+    /// `None` if any domain of the chain is not active, on cycles or after [`MAX_ALIAS_HOPS`]
+    pub fn resolve(&self, domain_name: &str, unix_millis_now: i64) -> Option<(String, Domain)> {
+        let mut aliases: Vec<String> = vec![];
+        let mut current = domain_name.to_string();
+
+        loop {
+            if aliases.contains(&current) || aliases.len() > MAX_ALIAS_HOPS {
+                return None;
+            }
+            if !self.is_minted(&current) {
+                current = self.get_wildcard_ancestor(&current)?;
+            }
+            if !self.is_active(&current, unix_millis_now) {
+                return None;
+            }
+
+            let domain = self.get_domain(&current)?;
+            match domain.alias.clone() {
+                Some(target) => {
+                    aliases.push(current);
+                    current = target;
+                }
+                None => return Some((current, domain)),
+            }
+        }
+    }
+
+    /// Returns the minted domain whose owner and expiration apply to the domain
+    /// That is the domain itself, or its wildcard ancestor when it is not minted
+    pub fn get_status_domain(&self, domain_name: &str) -> Option<String> {
+        if self.is_minted(domain_name) {
+            Some(domain_name.to_string())
+        } else {
+            self.get_wildcard_ancestor(domain_name)
+        }
+    }
+
+    /// Resolves the selected record classes of many domains at once
+    /// Aliases and wildcards are followed, the records of inactive domains are not resolved
+    pub fn resolve_batch(
+        &self,
+        domain_names: &[String],
+        classes: &[RecordClass],
+        unix_millis_now: i64,
+    ) -> Vec<BatchResolvedDomain> {
+        domain_names
+            .iter()
+            .map(|domain_name| {
+                let status_domain = self.get_status_domain(domain_name);
+                let resolved = self.resolve(domain_name, unix_millis_now);
+
+                BatchResolvedDomain {
+                    domain: domain_name.clone(),
+                    token_id: status_domain
+                        .as_ref()
+                        .and_then(|status_domain| self.get_token_id(status_domain)),
+                    expires_at: status_domain
+                        .as_ref()
+                        .and_then(|status_domain| self.get_expires_at(status_domain)),
+                    active: status_domain.as_ref().is_some_and(|status_domain| {
+                        self.is_active(status_domain, unix_millis_now)
+                    }),
+                    records: classes
+                        .iter()
+                        .map(|class| {
+                            resolved
+                                .as_ref()
+                                .and_then(|(_, domain)| domain.get_record(class).cloned())
+                        })
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the nearest ancestor of the domain with the wildcard enabled
+    /// The domain does not need to be minted
+    pub fn get_wildcard_ancestor(&self, domain_name: &str) -> Option<String> {
+        let mut current = domain_name;
+
+        while let Some((parent, _)) = current.rsplit_once('.') {
+            if self
+                .get_domain(parent)
+                .is_some_and(|domain| domain.wildcard)
+            {
+                return Some(parent.to_string());
+            }
+            current = parent;
+        }
+
+        None
+    }
+
+    /// Returns the domain followed by the domains it points to through aliases
+    /// Stops on the first cycle or missing domain
+    pub fn get_alias_chain(&self, domain_name: &str) -> Vec<String> {
+        let mut chain: Vec<String> = vec![];
+        let mut current = Some(domain_name.to_string());
+
+        while let Some(name) = current {
+            if chain.contains(&name) {
+                break;
+            }
+
+            current = self.get_domain(&name).and_then(|domain| domain.alias);
+            chain.push(name);
+        }
+
+        chain
+    }
+
+    /// Returns the address of the coin type of the resolved domain
+    pub fn get_address(
+        &self,
+        domain_name: &str,
+        coin_type: u32,
+        unix_millis_now: i64,
+    ) -> Option<Vec<u8>> {
+        self.resolve(domain_name, unix_millis_now)
+            .and_then(|(_, domain)| domain.get_address(coin_type).cloned())
+    }
+
+    /// Checks if all the given fuses of the domain are burned
+    pub fn has_fuses(&self, domain_name: &str, fuses: u32) -> bool {
+        self.get_domain(domain_name)
+            .is_some_and(|domain| domain.has_fuses(fuses))
+    }
+
+    /// Returns the direct subdomains of the domain
+    pub fn get_children(&self, domain_name: &str) -> Vec<String> {
+        self.children
+            .get(&domain_name.to_owned())
+            .unwrap_or_default()
+    }
+
+    /// Returns a page of the direct subdomains of the domain
+    pub fn get_subdomains(&self, domain_name: &str, offset: u32, limit: u32) -> Vec<String> {
+        self.get_children(domain_name)
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    /// Returns the depth of the domain, a root domain has a depth of 0
+    pub fn get_depth(&self, domain_name: &str) -> u32 {
+        self.get_parents(domain_name).len() as u32
+    }
+
+    /// Returns the domain expiration capped by the expiration of all the parents
+    /// A subdomain without expiration inherits the expiration of its parents
+    pub fn get_expires_at(&self, domain_name: &str) -> Option<i64> {
+        let domain = self.get_domain(domain_name)?;
+
+        std::iter::once(domain)
+            .chain(self.get_parents(domain_name))
+            .filter_map(|domain| domain.expires_at)
+            .min()
+    }
+
     pub fn get_domain_by_token_id(&self, token_id: u128) -> Option<(String, Domain)> {
         self.domains
             .iter()
@@ -238,11 +731,123 @@ impl PartisiaNameSystemState {
         }
     }
 
+    /// Returns the subdomain sale policy of the domain
+    pub fn get_subdomain_sale(&self, domain_name: &str) -> Option<SubdomainSale> {
+        self.subdomain_sales.get(&domain_name.to_owned())
+    }
+
     /// Says is token id minted or not
     pub fn is_minted(&self, domain_name: &str) -> bool {
         self.domains.contains_key(&domain_name.to_owned())
     }
 
+    /// Returns the record limits of the domain given its premium tier
+    /// A domain whose tier is no longer configured gets the default limits
+    pub fn get_record_limits(&self, domain_name: &str) -> RecordLimits {
+        self.get_domain(domain_name)
+            .and_then(|domain| domain.premium_tier)
+            .and_then(|tier| self.config.get_premium_tier(tier))
+            .map(|tier| tier.record_limits.clone())
+            .unwrap_or_else(|| self.config.record_limits.clone())
+    }
+
+    /// Returns the large records of the domain by key
+    pub fn get_large_records(&self, domain_name: &str) -> SortedVecMap<String, LargeRecord> {
+        self.large_records
+            .get(&domain_name.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Returns the committed value of the large record of the domain
+    pub fn get_large_record_value(&self, domain_name: &str, key: &str) -> Option<Vec<u8>> {
+        self.get_large_records(domain_name)
+            .get(key)
+            .filter(|large_record| large_record.commit.is_some())
+            .map(|large_record| large_record.chunks.concat())
+    }
+
+    /// Returns the bytes used by the large records of the domain, committed or not
+    pub fn get_storage_used(&self, domain_name: &str) -> u32 {
+        self.get_large_records(domain_name)
+            .iter()
+            .map(|(_, large_record)| large_record.size())
+            .sum()
+    }
+
+    /// Returns the change trails of the records of the domain
+    pub fn get_record_histories(&self, domain_name: &str) -> RecordHistories {
+        self.record_histories
+            .get(&domain_name.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Returns the change trail of the record of the domain
+    pub fn get_record_history(
+        &self,
+        domain_name: &str,
+        class: &RecordClass,
+    ) -> Option<RecordHistory> {
+        self.get_record_histories(domain_name)
+            .records
+            .get(class)
+            .cloned()
+    }
+
+    /// Returns the change trail of the custom record of the domain
+    pub fn get_custom_record_history(&self, domain_name: &str, key: &str) -> Option<RecordHistory> {
+        self.get_record_histories(domain_name)
+            .custom_records
+            .get(key)
+            .cloned()
+    }
+
+    /// Tracks a change of the record, to be called before the record is changed
+    /// The current value is kept as a revision, the oldest revisions beyond the limit are dropped
+    pub fn track_record_change(
+        &mut self,
+        domain_name: &str,
+        class: &RecordClass,
+        updated_by: Address,
+        updated_at: i64,
+    ) {
+        let domain = self.get_domain(domain_name).unwrap();
+        let mut histories = self.get_record_histories(domain_name);
+        let history = RecordHistory::next(
+            histories.records.get(class),
+            domain.get_record(class),
+            updated_by,
+            updated_at,
+            self.config.record_history_limit,
+        );
+
+        histories.records.insert(*class, history);
+        self.record_histories
+            .insert(domain_name.to_string(), histories);
+    }
+
+    /// Tracks a change of the custom record, to be called before the custom record is changed
+    pub fn track_custom_record_change(
+        &mut self,
+        domain_name: &str,
+        key: &str,
+        updated_by: Address,
+        updated_at: i64,
+    ) {
+        let domain = self.get_domain(domain_name).unwrap();
+        let mut histories = self.get_record_histories(domain_name);
+        let history = RecordHistory::next(
+            histories.custom_records.get(key),
+            domain.get_custom_record(key),
+            updated_by,
+            updated_at,
+            self.config.record_history_limit,
+        );
+
+        histories.custom_records.insert(key.to_owned(), history);
+        self.record_histories
+            .insert(domain_name.to_string(), histories);
+    }
+
     /// This function returns token id for given domain
     pub fn get_token_id(&self, domain_name: &str) -> Option<u128> {
         self.domains
@@ -250,3 +855,8 @@ impl PartisiaNameSystemState {
             .map(|d| d.token_id)
     }
 }
+
+/// Converts a storage quota in KB to bytes
+pub fn storage_quota_bytes(storage_quota_kb: u32) -> u64 {
+    storage_quota_kb as u64 * STORAGE_QUOTA_UNIT as u64
+}
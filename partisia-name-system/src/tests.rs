@@ -1,21 +1,54 @@
 use crate::{
     actions::{
+        execute_burn_fuses, execute_clear_subdomain_sale, execute_commit_large_record,
         execute_custom_record_delete, execute_custom_record_mint, execute_custom_record_update,
-        execute_init, execute_mint, execute_record_delete, execute_record_delete_all,
-        execute_record_mint, execute_record_update, execute_update_expiration,
+        execute_init, execute_large_record_delete, execute_mint, execute_record_delete,
+        execute_record_delete_all, execute_record_delete_transfer_unsafe, execute_record_mint,
+        execute_record_update, execute_remove_subdomain_sale, execute_sell_subdomain,
+        execute_set_address, execute_set_alias, execute_set_content_hash, execute_set_dns_records,
+        execute_set_premium_tier, execute_set_storage_quota, execute_set_subdomain_sale,
+        execute_set_wildcard, execute_update_expiration, execute_write_large_record_chunk,
+        validate_domain,
     },
     msg::{
-        PnsCustomRecordDeleteMsg, PnsCustomRecordMintMsg, PnsCustomRecordUpdateMsg,
-        PnsDomainUpdateExpirationMsg, PnsMintMsg, PnsRecordDeleteAllMsg, PnsRecordDeleteMsg,
-        PnsRecordMintMsg, PnsRecordUpdateMsg,
+        PnsBurnFusesMsg, PnsCommitLargeRecordMsg, PnsCustomRecordDeleteMsg, PnsCustomRecordMintMsg,
+        PnsCustomRecordUpdateMsg, PnsDomainUpdateExpirationMsg, PnsLargeRecordDeleteMsg,
+        PnsMintMsg, PnsRecordDeleteAllMsg, PnsRecordDeleteMsg, PnsRecordMintMsg,
+        PnsRecordUpdateMsg, PnsSetAddressMsg, PnsSetAliasMsg, PnsSetContentHashMsg,
+        PnsSetDnsRecordsMsg, PnsSetPremiumTierMsg, PnsSetStorageQuotaMsg, PnsSetWildcardMsg,
+        PnsSubdomainSaleMsg, PnsWriteLargeRecordChunkMsg,
     },
-    state::{RecordClass, MAX_CUSTOM_RECORDS},
+    state::{
+        DnsRecord, DnsRecordData, PartisiaNameSystemState, PnsConfig, PremiumTier, RecordClass,
+        RecordHistories, RecordLimits, CANNOT_CREATE_SUBDOMAIN, CANNOT_SET_RECORDS,
+        CANNOT_TRANSFER, COIN_TYPE_BITCOIN, COIN_TYPE_ETHEREUM, COIN_TYPE_PARTISIA,
+        DEFAULT_MAX_CUSTOM_RECORDS, MAX_ALIAS_HOPS,
+    },
+};
+
+use crate::content_hash::{
+    decode_content_hash, decode_varint, encode_content_hash, encode_varint, is_valid_content_hash,
+    ContentProtocol,
+};
+use crate::dns::{encode_name, to_dns_order, to_wire_format};
+use crate::large_record::{large_record_commit, split_into_chunks, verify_large_record};
+use crate::validation::{
+    is_valid_dns_record, validate_address, validate_dns_records, validate_record,
 };
 
-use utils::tests::{
-    mock_contract_context, string_to_bytes, tomorrow_timestamp, yesterday_timestamp,
+use utils::{
+    tests::{mock_contract_context, string_to_bytes, tomorrow_timestamp, yesterday_timestamp},
+    time::milliseconds_in_years,
 };
 
+const WALLET_ADDRESS: &str = "00b2e734b5d8da089318d0d2b076c19f59c450855a";
+/// CIDv1 dag-pb sha2-256
+const IPFS_CID: [u8; 36] = [
+    0x01, 0x70, 0x12, 0x20, 0x29, 0xf2, 0xd1, 0x7b, 0xe6, 0x13, 0x90, 0x79, 0xdc, 0x48, 0x69, 0x6d,
+    0x1f, 0x58, 0x2a, 0x85, 0x30, 0xeb, 0x98, 0x05, 0xb5, 0x61, 0xed, 0xa5, 0x17, 0xe2, 0x2a, 0x89,
+    0x2c, 0x7e, 0x3f, 0x1f,
+];
+
 #[test]
 fn proper_mint() {
     let minter = 1u8;
@@ -289,7 +322,7 @@ fn record_already_minted_on_record_mint() {
     let record_mint = PnsRecordMintMsg {
         domain: "name".to_string(),
         class: RecordClass::Wallet {},
-        data: string_to_bytes("some data"),
+        data: string_to_bytes(WALLET_ADDRESS),
     };
 
     let _ = execute_record_mint(&mock_contract_context(alice), &mut state, &record_mint);
@@ -323,7 +356,7 @@ fn proper_record_update() {
     let record_update_msg = PnsRecordUpdateMsg {
         domain: domain.clone(),
         class: record_class,
-        data: string_to_bytes("new data"),
+        data: string_to_bytes("new_data"),
     };
 
     let _ = execute_record_update(
@@ -334,7 +367,7 @@ fn proper_record_update() {
 
     let domain = state.get_domain(domain).unwrap();
     let record = domain.get_record(&record_class).unwrap();
-    assert_eq!(*record, string_to_bytes("new data"));
+    assert_eq!(*record, string_to_bytes("new_data"));
 }
 
 #[test]
@@ -348,7 +381,7 @@ fn when_record_does_not_exist_record_update_fails() {
     let record_update_msg = PnsRecordUpdateMsg {
         domain: "name".to_string(),
         class: RecordClass::Twitter {},
-        data: string_to_bytes("new data"),
+        data: string_to_bytes("new_data"),
     };
 
     let _ = execute_record_update(
@@ -453,6 +486,7 @@ fn when_domain_is_expired_record_delete_all_action_works() {
 
     let record_delete_all = PnsRecordDeleteAllMsg {
         domain: "name".to_string(),
+        updated_by: mock_contract_context(minter).sender,
     };
 
     let _ = execute_record_delete_all(
@@ -564,7 +598,7 @@ fn when_too_many_custom_record_exists_mint_fails() {
 
     let _ = execute_mint(&mock_contract_context(minter), &mut state, &mint_msg);
 
-    for i in 0..(MAX_CUSTOM_RECORDS + 1) {
+    for i in 0..(DEFAULT_MAX_CUSTOM_RECORDS + 1) {
         let record_mint = PnsCustomRecordMintMsg {
             domain: "name".to_string(),
             key: format!("key-{}", i),
@@ -601,7 +635,7 @@ fn proper_custom_record_update() {
     let record_update_msg = PnsCustomRecordUpdateMsg {
         domain: domain.clone(),
         key: record_key.clone(),
-        data: string_to_bytes("new data"),
+        data: string_to_bytes("new_data"),
     };
 
     let _ = execute_custom_record_update(
@@ -612,7 +646,7 @@ fn proper_custom_record_update() {
 
     let domain = state.get_domain(domain).unwrap();
     let record = domain.get_custom_record(&record_key).unwrap();
-    assert_eq!(*record, string_to_bytes("new data"));
+    assert_eq!(*record, string_to_bytes("new_data"));
 }
 
 #[test]
@@ -626,7 +660,7 @@ fn when_custom_record_does_not_exist_record_update_fails() {
     let record_update_msg = PnsCustomRecordUpdateMsg {
         domain: "name".to_string(),
         key: "key".to_string(),
-        data: string_to_bytes("new data"),
+        data: string_to_bytes("new_data"),
     };
 
     let _ = execute_custom_record_update(
@@ -804,3 +838,1718 @@ fn when_record_does_not_exist_record_delete_fails() {
         &record_delete_msg,
     );
 }
+
+fn init_with_subdomain_sale(max_subdomains: Option<u32>) -> PartisiaNameSystemState {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+
+    execute_mint(
+        &ctx,
+        &mut state,
+        &PnsMintMsg {
+            token_id: 1,
+            domain: "mpc".to_string(),
+            parent_id: None,
+            expires_at: Some(tomorrow_timestamp()),
+        },
+    );
+    execute_set_subdomain_sale(
+        &ctx,
+        &mut state,
+        &PnsSubdomainSaleMsg {
+            domain: "mpc".to_string(),
+            price: 10,
+            payment_coin_id: 0,
+            max_subdomains,
+            expiry_length: Some(1000),
+        },
+    );
+
+    state
+}
+
+#[test]
+fn proper_set_subdomain_sale() {
+    let state = init_with_subdomain_sale(Some(2));
+
+    let sale = state.get_subdomain_sale("mpc").unwrap();
+    assert_eq!(sale.price, 10);
+    assert_eq!(sale.max_subdomains, Some(2));
+    assert_eq!(sale.expiry_length, Some(1000));
+    assert_eq!(sale.sold_count, 0);
+}
+
+#[test]
+fn proper_sell_subdomain() {
+    let ctx = mock_contract_context(1);
+    let mut state = init_with_subdomain_sale(Some(2));
+
+    execute_sell_subdomain(&ctx, &mut state, "mpc");
+    let sale = execute_sell_subdomain(&ctx, &mut state, "mpc");
+
+    assert_eq!(sale.sold_count, 2);
+    assert!(!sale.is_available());
+}
+
+#[test]
+fn proper_set_subdomain_sale_keeps_sold_count() {
+    let ctx = mock_contract_context(1);
+    let mut state = init_with_subdomain_sale(Some(2));
+    execute_sell_subdomain(&ctx, &mut state, "mpc");
+
+    execute_set_subdomain_sale(
+        &ctx,
+        &mut state,
+        &PnsSubdomainSaleMsg {
+            domain: "mpc".to_string(),
+            price: 20,
+            payment_coin_id: 0,
+            max_subdomains: None,
+            expiry_length: None,
+        },
+    );
+
+    let sale = state.get_subdomain_sale("mpc").unwrap();
+    assert_eq!(sale.price, 20);
+    assert_eq!(sale.sold_count, 1);
+    assert!(sale.is_available());
+}
+
+#[test]
+fn proper_clear_subdomain_sale() {
+    let mut state = init_with_subdomain_sale(Some(2));
+
+    execute_clear_subdomain_sale(&mut state, "mpc");
+    assert!(state.get_subdomain_sale("mpc").is_none());
+
+    // Clearing a domain without sale is a no-op
+    execute_clear_subdomain_sale(&mut state, "mpc");
+    assert!(state.get_subdomain_sale("mpc").is_none());
+}
+
+#[test]
+#[should_panic(expected = "The subdomain sale is sold out")]
+fn when_subdomain_sale_is_sold_out_sell_subdomain_fails() {
+    let ctx = mock_contract_context(1);
+    let mut state = init_with_subdomain_sale(Some(1));
+
+    execute_sell_subdomain(&ctx, &mut state, "mpc");
+    execute_sell_subdomain(&ctx, &mut state, "mpc");
+}
+
+#[test]
+#[should_panic(expected = "The specified domain has no subdomain sale")]
+fn when_subdomain_sale_is_removed_sell_subdomain_fails() {
+    let ctx = mock_contract_context(1);
+    let mut state = init_with_subdomain_sale(None);
+
+    execute_remove_subdomain_sale(&ctx, &mut state, "mpc");
+    execute_sell_subdomain(&ctx, &mut state, "mpc");
+}
+
+#[test]
+#[should_panic(expected = "Not found")]
+fn when_domain_is_not_minted_set_subdomain_sale_fails() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+
+    execute_set_subdomain_sale(
+        &ctx,
+        &mut state,
+        &PnsSubdomainSaleMsg {
+            domain: "mpc".to_string(),
+            price: 10,
+            payment_coin_id: 0,
+            max_subdomains: None,
+            expiry_length: None,
+        },
+    );
+}
+
+fn mint_domain(
+    state: &mut PartisiaNameSystemState,
+    domain: &str,
+    parent_id: Option<&str>,
+    expires_at: Option<i64>,
+) {
+    execute_mint(
+        &mock_contract_context(1),
+        state,
+        &PnsMintMsg {
+            token_id: domain.len() as u128,
+            domain: domain.to_string(),
+            parent_id: parent_id.map(|parent_id| parent_id.to_string()),
+            expires_at,
+        },
+    );
+}
+
+#[test]
+fn proper_mint_with_parent_inherits_expiration() {
+    let mut state = execute_init(&mock_contract_context(1));
+    let expires_at = tomorrow_timestamp();
+
+    mint_domain(&mut state, "mpc", None, Some(expires_at));
+    mint_domain(&mut state, "mpc.name", Some("mpc"), None);
+    mint_domain(
+        &mut state,
+        "mpc.name.sub",
+        Some("mpc.name"),
+        Some(expires_at + 1000),
+    );
+
+    let domain = state.get_domain("mpc.name").unwrap();
+    assert_eq!(domain.expires_at, None);
+    assert_eq!(state.get_expires_at("mpc.name"), Some(expires_at));
+    assert_eq!(state.get_expires_at("mpc.name.sub"), Some(expires_at));
+
+    // The renewals of the parent extend the subdomains
+    execute_update_expiration(
+        &mock_contract_context(1),
+        &mut state,
+        &PnsDomainUpdateExpirationMsg {
+            domain: "mpc".to_string(),
+            expires_at: Some(expires_at + 5000),
+        },
+    );
+
+    assert_eq!(state.get_expires_at("mpc.name"), Some(expires_at + 5000));
+    assert_eq!(
+        state.get_expires_at("mpc.name.sub"),
+        Some(expires_at + 1000)
+    );
+}
+
+#[test]
+fn proper_is_active_checks_all_parents() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+
+    mint_domain(&mut state, "mpc", None, None);
+    mint_domain(&mut state, "mpc.name", Some("mpc"), None);
+    mint_domain(&mut state, "mpc.name.sub", Some("mpc.name"), None);
+    assert!(state.is_active("mpc.name.sub", ctx.block_production_time));
+
+    let expires_at = yesterday_timestamp();
+    execute_update_expiration(
+        &ctx,
+        &mut state,
+        &PnsDomainUpdateExpirationMsg {
+            domain: "mpc.name".to_string(),
+            expires_at: Some(expires_at),
+        },
+    );
+
+    assert!(state.is_active("mpc", ctx.block_production_time));
+    assert!(!state.is_active("mpc.name.sub", ctx.block_production_time));
+    assert_eq!(state.get_expires_at("mpc.name.sub"), Some(expires_at));
+}
+
+#[test]
+fn proper_update_expiration_capped_by_parent() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    let expires_at = tomorrow_timestamp();
+
+    mint_domain(&mut state, "mpc", None, Some(expires_at));
+    mint_domain(&mut state, "mpc.name", Some("mpc"), None);
+
+    execute_update_expiration(
+        &ctx,
+        &mut state,
+        &PnsDomainUpdateExpirationMsg {
+            domain: "mpc.name".to_string(),
+            expires_at: Some(expires_at + 1),
+        },
+    );
+
+    let domain = state.get_domain("mpc.name").unwrap();
+    assert_eq!(domain.expires_at, Some(expires_at + 1));
+    assert_eq!(state.get_expires_at("mpc.name"), Some(expires_at));
+}
+
+#[test]
+fn proper_children_index() {
+    let mut state = execute_init(&mock_contract_context(1));
+
+    mint_domain(&mut state, "mpc", None, None);
+    mint_domain(&mut state, "mpc.a", Some("mpc"), None);
+    mint_domain(&mut state, "mpc.bb", Some("mpc"), None);
+    mint_domain(&mut state, "mpc.ccc", Some("mpc"), None);
+    mint_domain(&mut state, "mpc.a.deep", Some("mpc.a"), None);
+
+    assert_eq!(
+        state.get_children("mpc"),
+        vec!["mpc.a", "mpc.bb", "mpc.ccc"]
+    );
+    assert_eq!(state.get_children("mpc.a"), vec!["mpc.a.deep"]);
+    assert_eq!(state.get_children("mpc.bb"), Vec::<String>::new());
+
+    assert_eq!(
+        state.get_subdomains("mpc", 1, 10),
+        vec!["mpc.bb", "mpc.ccc"]
+    );
+    assert_eq!(state.get_subdomains("mpc", 0, 1), vec!["mpc.a"]);
+    assert_eq!(state.get_subdomains("mpc", 5, 1), Vec::<String>::new());
+
+    assert_eq!(state.get_depth("mpc"), 0);
+    assert_eq!(state.get_depth("mpc.a.deep"), 2);
+}
+
+#[test]
+fn proper_burn_fuses() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    mint_domain(&mut state, "mpc", None, None);
+
+    execute_burn_fuses(&ctx, &mut state, &burn_fuses_msg("mpc", CANNOT_TRANSFER));
+    execute_burn_fuses(&ctx, &mut state, &burn_fuses_msg("mpc", CANNOT_SET_RECORDS));
+
+    assert!(state.has_fuses("mpc", CANNOT_TRANSFER | CANNOT_SET_RECORDS));
+    assert!(!state.has_fuses("mpc", CANNOT_CREATE_SUBDOMAIN));
+}
+
+#[test]
+#[should_panic(expected = "The given fuses are not valid")]
+fn when_fuses_are_unknown_burn_fuses_fails() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    mint_domain(&mut state, "mpc", None, None);
+
+    execute_burn_fuses(&ctx, &mut state, &burn_fuses_msg("mpc", 1 << 10));
+}
+
+#[test]
+#[should_panic(expected = "The records of the domain cannot be set")]
+fn when_set_records_fuse_is_burned_record_mint_fails() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    mint_domain(&mut state, "mpc", None, None);
+    execute_burn_fuses(&ctx, &mut state, &burn_fuses_msg("mpc", CANNOT_SET_RECORDS));
+
+    execute_record_mint(
+        &ctx,
+        &mut state,
+        &PnsRecordMintMsg {
+            domain: "mpc".to_string(),
+            class: RecordClass::Bio {},
+            data: string_to_bytes("bio"),
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "The domain cannot create subdomains")]
+fn when_create_subdomain_fuse_is_burned_subdomain_mint_fails() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    mint_domain(&mut state, "mpc", None, None);
+    execute_burn_fuses(
+        &ctx,
+        &mut state,
+        &burn_fuses_msg("mpc", CANNOT_CREATE_SUBDOMAIN),
+    );
+
+    mint_domain(&mut state, "mpc.name", Some("mpc"), None);
+}
+
+fn burn_fuses_msg(domain: &str, fuses: u32) -> PnsBurnFusesMsg {
+    PnsBurnFusesMsg {
+        domain: domain.to_string(),
+        fuses,
+    }
+}
+
+#[test]
+fn proper_record_delete_transfer_unsafe() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    mint_domain(&mut state, "mpc", None, None);
+
+    for (class, data) in [
+        (RecordClass::Bio {}, "bio"),
+        (RecordClass::Wallet {}, WALLET_ADDRESS),
+    ] {
+        execute_record_mint(
+            &ctx,
+            &mut state,
+            &PnsRecordMintMsg {
+                domain: "mpc".to_string(),
+                class,
+                data: string_to_bytes(data),
+            },
+        );
+    }
+
+    execute_record_delete_transfer_unsafe(
+        &ctx,
+        &mut state,
+        &PnsRecordDeleteAllMsg {
+            domain: "mpc".to_string(),
+            updated_by: ctx.sender,
+        },
+    );
+
+    let domain = state.get_domain("mpc").unwrap();
+    assert!(domain.is_record_minted(&RecordClass::Bio {}));
+    assert!(!domain.is_record_minted(&RecordClass::Wallet {}));
+
+    let history = state
+        .get_record_history("mpc", &RecordClass::Bio {})
+        .unwrap();
+    assert_eq!(history.version, 1);
+    let history = state
+        .get_record_history("mpc", &RecordClass::Wallet {})
+        .unwrap();
+    assert_eq!(history.version, 2);
+    assert_eq!(
+        history.revisions[0].data,
+        Some(string_to_bytes(WALLET_ADDRESS))
+    );
+}
+
+#[test]
+fn proper_validate_record() {
+    let valid_records = [
+        (RecordClass::Wallet {}, WALLET_ADDRESS),
+        (
+            RecordClass::Wallet {},
+            "0x02b2e734b5d8da089318d0d2b076c19f59c450855a",
+        ),
+        (
+            RecordClass::Wallet {},
+            "03b2e734b5d8da089318d0d2b076c19f59c450855a",
+        ),
+        (RecordClass::Email {}, "john.doe+tag@mail.example.com"),
+        (
+            RecordClass::Uri {},
+            "https://metanames.app/domain?name=mpc%20name",
+        ),
+        (
+            RecordClass::Avatar {},
+            "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        (RecordClass::Twitter {}, "meta_names"),
+        (RecordClass::Discord {}, "meta.names_01"),
+        (RecordClass::Bio {}, "anything goes @ here"),
+    ];
+
+    for (class, data) in valid_records {
+        validate_record(&class, data.as_bytes());
+    }
+}
+
+#[test]
+fn proper_validate_record_invalid_data() {
+    let invalid_records = [
+        (RecordClass::Wallet {}, "some data"),
+        (
+            RecordClass::Wallet {},
+            "ffb2e734b5d8da089318d0d2b076c19f59c450855a",
+        ),
+        (
+            RecordClass::Wallet {},
+            "04b2e734b5d8da089318d0d2b076c19f59c450855a",
+        ),
+        (
+            RecordClass::Wallet {},
+            "00b2e734b5d8da089318d0d2b076c19f59c45085",
+        ),
+        (RecordClass::Email {}, "john.doe"),
+        (RecordClass::Email {}, "john..doe@mail.com"),
+        (RecordClass::Email {}, "john@-mail.com"),
+        (RecordClass::Email {}, "john@localhost"),
+        (RecordClass::Uri {}, "metanames.app"),
+        (RecordClass::Uri {}, "https://meta names.app"),
+        (RecordClass::Avatar {}, "1ttp://metanames.app"),
+        (RecordClass::Uri {}, "https://metanames.app/%zz"),
+        (RecordClass::Twitter {}, "@meta_names"),
+        (RecordClass::Twitter {}, "a_very_long_twitter_handle"),
+        (RecordClass::Discord {}, "Meta"),
+        (RecordClass::Discord {}, "meta..names"),
+        (RecordClass::Discord {}, "m"),
+    ];
+
+    for (class, data) in invalid_records {
+        let result = std::panic::catch_unwind(|| validate_record(&class, data.as_bytes()));
+        assert!(result.is_err(), "{data} should not be a valid record");
+    }
+}
+
+#[test]
+#[should_panic(expected = "The email record must be a valid email address")]
+fn when_email_is_invalid_record_mint_fails() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    mint_domain(&mut state, "mpc", None, None);
+
+    execute_record_mint(
+        &ctx,
+        &mut state,
+        &PnsRecordMintMsg {
+            domain: "mpc".to_string(),
+            class: RecordClass::Email {},
+            data: string_to_bytes("not an email"),
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "The wallet record must be a hex encoded address")]
+fn when_wallet_is_invalid_record_update_fails() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    mint_domain(&mut state, "mpc", None, None);
+
+    execute_record_mint(
+        &ctx,
+        &mut state,
+        &PnsRecordMintMsg {
+            domain: "mpc".to_string(),
+            class: RecordClass::Wallet {},
+            data: string_to_bytes(WALLET_ADDRESS),
+        },
+    );
+    execute_record_update(
+        &ctx,
+        &mut state,
+        &PnsRecordUpdateMsg {
+            domain: "mpc".to_string(),
+            class: RecordClass::Wallet {},
+            data: string_to_bytes("some data"),
+        },
+    );
+}
+
+#[test]
+fn proper_validate_address() {
+    let valid_addresses = [
+        (COIN_TYPE_PARTISIA, WALLET_ADDRESS),
+        (
+            COIN_TYPE_ETHEREUM,
+            "0x71C7656EC7ab88b098defB751B7401B5f6d8976F",
+        ),
+        (COIN_TYPE_BITCOIN, "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2"),
+        (COIN_TYPE_BITCOIN, "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy"),
+        (
+            COIN_TYPE_BITCOIN,
+            "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+        ),
+        (
+            COIN_TYPE_BITCOIN,
+            "BC1QAR0SRRR7XFKVY5L643LYDNW9RE59GTZZWF5MDQ",
+        ),
+    ];
+
+    for (coin_type, address) in valid_addresses {
+        validate_address(coin_type, address.as_bytes());
+    }
+}
+
+#[test]
+fn proper_validate_address_invalid_address() {
+    let invalid_addresses = [
+        (
+            COIN_TYPE_PARTISIA,
+            "0x71C7656EC7ab88b098defB751B7401B5f6d8976F",
+        ),
+        (
+            COIN_TYPE_ETHEREUM,
+            "71C7656EC7ab88b098defB751B7401B5f6d8976F",
+        ),
+        (
+            COIN_TYPE_ETHEREUM,
+            "0x71C7656EC7ab88b098defB751B7401B5f6d8976",
+        ),
+        (
+            COIN_TYPE_ETHEREUM,
+            "0x71C7656EC7ab88b098defB751B7401B5f6d8976G",
+        ),
+        (COIN_TYPE_BITCOIN, "2BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2"),
+        (COIN_TYPE_BITCOIN, "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN0"),
+        (
+            COIN_TYPE_BITCOIN,
+            "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdb",
+        ),
+        (
+            COIN_TYPE_BITCOIN,
+            "bc1QAR0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+        ),
+        (
+            COIN_TYPE_BITCOIN,
+            "tb1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+        ),
+    ];
+
+    for (coin_type, address) in invalid_addresses {
+        let result = std::panic::catch_unwind(|| validate_address(coin_type, address.as_bytes()));
+        assert!(result.is_err(), "{address} should not be a valid address");
+    }
+}
+
+#[test]
+#[should_panic(expected = "The coin type is not supported")]
+fn when_coin_type_is_not_supported_validate_address_fails() {
+    validate_address(2, b"LdP8Qox1VAhCzLJNqrr74YovaWYyNBUWvL");
+}
+
+#[test]
+fn proper_set_address() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    mint_domain(&mut state, "mpc", None, None);
+
+    for (coin_type, address) in [
+        (COIN_TYPE_PARTISIA, WALLET_ADDRESS),
+        (
+            COIN_TYPE_ETHEREUM,
+            "0x71C7656EC7ab88b098defB751B7401B5f6d8976F",
+        ),
+    ] {
+        execute_set_address(
+            &ctx,
+            &mut state,
+            &PnsSetAddressMsg {
+                domain: "mpc".to_string(),
+                coin_type,
+                address: Some(string_to_bytes(address)),
+            },
+        );
+    }
+
+    let now = ctx.block_production_time;
+    assert_eq!(
+        state.get_address("mpc", COIN_TYPE_PARTISIA, now),
+        Some(string_to_bytes(WALLET_ADDRESS))
+    );
+    assert_eq!(
+        state.get_address("mpc", COIN_TYPE_ETHEREUM, now),
+        Some(string_to_bytes(
+            "0x71C7656EC7ab88b098defB751B7401B5f6d8976F"
+        ))
+    );
+
+    execute_set_address(
+        &ctx,
+        &mut state,
+        &PnsSetAddressMsg {
+            domain: "mpc".to_string(),
+            coin_type: COIN_TYPE_ETHEREUM,
+            address: None,
+        },
+    );
+    assert_eq!(state.get_address("mpc", COIN_TYPE_ETHEREUM, now), None);
+    assert_eq!(state.get_domain("mpc").unwrap().addresses.len(), 1);
+
+    execute_record_delete_transfer_unsafe(
+        &ctx,
+        &mut state,
+        &PnsRecordDeleteAllMsg {
+            domain: "mpc".to_string(),
+            updated_by: ctx.sender,
+        },
+    );
+    assert_eq!(state.get_address("mpc", COIN_TYPE_PARTISIA, now), None);
+}
+
+#[test]
+#[should_panic(expected = "The address is not valid for the coin type")]
+fn when_address_is_invalid_set_address_fails() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    mint_domain(&mut state, "mpc", None, None);
+
+    execute_set_address(
+        &ctx,
+        &mut state,
+        &PnsSetAddressMsg {
+            domain: "mpc".to_string(),
+            coin_type: COIN_TYPE_ETHEREUM,
+            address: Some(string_to_bytes(WALLET_ADDRESS)),
+        },
+    );
+}
+
+#[test]
+fn proper_varint() {
+    for (value, bytes) in [
+        (0x01, vec![0x01]),
+        (0xe3, vec![0xe3, 0x01]),
+        (0xb29910, vec![0x90, 0xb2, 0xca, 0x05]),
+    ] {
+        assert_eq!(encode_varint(value), bytes);
+        assert_eq!(decode_varint(&bytes), Some((value, &[][..])));
+    }
+
+    assert_eq!(decode_varint(&[0x80]), None);
+    assert_eq!(decode_varint(&[]), None);
+}
+
+#[test]
+fn proper_content_hash() {
+    let content_hash = encode_content_hash(ContentProtocol::Ipfs, &IPFS_CID);
+
+    assert_eq!(content_hash[..2], [0xe3, 0x01]);
+    assert_eq!(
+        decode_content_hash(&content_hash),
+        Some((ContentProtocol::Ipfs, IPFS_CID.to_vec()))
+    );
+    assert!(is_valid_content_hash(&content_hash));
+
+    let arweave_hash = encode_content_hash(ContentProtocol::Arweave, &IPFS_CID[4..]);
+    assert!(is_valid_content_hash(&arweave_hash));
+}
+
+#[test]
+fn proper_content_hash_invalid() {
+    let invalid_content_hashes = [
+        // Unknown protocol
+        [vec![0xe6, 0x01], IPFS_CID.to_vec()].concat(),
+        // CIDv0 is not supported in the record
+        encode_content_hash(ContentProtocol::Ipfs, &IPFS_CID[2..]),
+        // Truncated digest
+        encode_content_hash(ContentProtocol::Ipfs, &IPFS_CID[..20]),
+        encode_content_hash(ContentProtocol::Arweave, &IPFS_CID),
+        vec![],
+    ];
+
+    for content_hash in invalid_content_hashes {
+        assert!(!is_valid_content_hash(&content_hash));
+    }
+}
+
+#[test]
+fn proper_set_content_hash() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    mint_domain(&mut state, "mpc", None, None);
+
+    let content_hash = encode_content_hash(ContentProtocol::Ipfs, &IPFS_CID);
+    execute_set_content_hash(
+        &ctx,
+        &mut state,
+        &PnsSetContentHashMsg {
+            domain: "mpc".to_string(),
+            content_hash: Some(content_hash.clone()),
+        },
+    );
+    assert_eq!(
+        state.get_domain("mpc").unwrap().content_hash,
+        Some(content_hash)
+    );
+
+    execute_set_content_hash(
+        &ctx,
+        &mut state,
+        &PnsSetContentHashMsg {
+            domain: "mpc".to_string(),
+            content_hash: None,
+        },
+    );
+    assert_eq!(state.get_domain("mpc").unwrap().content_hash, None);
+}
+
+#[test]
+#[should_panic(expected = "The content hash is not valid")]
+fn when_content_hash_is_invalid_set_content_hash_fails() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    mint_domain(&mut state, "mpc", None, None);
+
+    execute_set_content_hash(
+        &ctx,
+        &mut state,
+        &PnsSetContentHashMsg {
+            domain: "mpc".to_string(),
+            content_hash: Some(IPFS_CID.to_vec()),
+        },
+    );
+}
+
+fn dns_record(name: &str, data: DnsRecordData) -> DnsRecord {
+    DnsRecord {
+        name: name.to_string(),
+        ttl: 3600,
+        data,
+    }
+}
+
+#[test]
+fn proper_validate_dns_records() {
+    let records = vec![
+        dns_record(
+            "",
+            DnsRecordData::A {
+                address: vec![192, 0, 2, 1],
+            },
+        ),
+        dns_record(
+            "",
+            DnsRecordData::Aaaa {
+                address: vec![0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+            },
+        ),
+        dns_record(
+            "",
+            DnsRecordData::Txt {
+                text: "v=spf1 -all".to_string(),
+            },
+        ),
+        dns_record(
+            "",
+            DnsRecordData::Mx {
+                preference: 10,
+                exchange: "mail.example.com".to_string(),
+            },
+        ),
+        dns_record(
+            "www",
+            DnsRecordData::Cname {
+                target: "metanames.app".to_string(),
+            },
+        ),
+        dns_record(
+            "*.app",
+            DnsRecordData::A {
+                address: vec![192, 0, 2, 2],
+            },
+        ),
+        dns_record(
+            "_sip._tcp",
+            DnsRecordData::Srv {
+                priority: 10,
+                weight: 60,
+                port: 5060,
+                target: "sip.example.com".to_string(),
+            },
+        ),
+    ];
+
+    validate_dns_records(&records);
+}
+
+#[test]
+fn proper_validate_dns_records_invalid_record() {
+    let invalid_records = [
+        dns_record(
+            "",
+            DnsRecordData::A {
+                address: vec![192, 0, 2],
+            },
+        ),
+        dns_record(
+            "",
+            DnsRecordData::Aaaa {
+                address: vec![192, 0, 2, 1],
+            },
+        ),
+        dns_record(
+            "",
+            DnsRecordData::Cname {
+                target: "meta names.app".to_string(),
+            },
+        ),
+        dns_record(
+            "",
+            DnsRecordData::Mx {
+                preference: 10,
+                exchange: "".to_string(),
+            },
+        ),
+        dns_record(
+            "",
+            DnsRecordData::Srv {
+                priority: 0,
+                weight: 0,
+                port: 0,
+                target: "-sip.example.com".to_string(),
+            },
+        ),
+        dns_record(
+            "",
+            DnsRecordData::Txt {
+                text: "a".repeat(513),
+            },
+        ),
+        dns_record(
+            "www.",
+            DnsRecordData::A {
+                address: vec![192, 0, 2, 1],
+            },
+        ),
+        dns_record(
+            "app.*",
+            DnsRecordData::A {
+                address: vec![192, 0, 2, 1],
+            },
+        ),
+        DnsRecord {
+            name: "".to_string(),
+            ttl: u32::MAX,
+            data: DnsRecordData::A {
+                address: vec![192, 0, 2, 1],
+            },
+        },
+    ];
+
+    for record in invalid_records {
+        assert!(
+            !is_valid_dns_record(&record),
+            "{record:?} should not be valid"
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "A name with a CNAME record cannot have other DNS records")]
+fn when_cname_has_other_records_validate_dns_records_fails() {
+    validate_dns_records(&[
+        dns_record(
+            "www",
+            DnsRecordData::Cname {
+                target: "metanames.app".to_string(),
+            },
+        ),
+        dns_record(
+            "www",
+            DnsRecordData::Txt {
+                text: "text".to_string(),
+            },
+        ),
+    ]);
+}
+
+#[test]
+fn proper_dns_wire_format() {
+    assert_eq!(encode_name("name.mpc"), b"\x04name\x03mpc\x00".to_vec());
+
+    let records = vec![
+        dns_record(
+            "www",
+            DnsRecordData::A {
+                address: vec![192, 0, 2, 1],
+            },
+        ),
+        dns_record(
+            "",
+            DnsRecordData::Mx {
+                preference: 10,
+                exchange: "mx.name.mpc".to_string(),
+            },
+        ),
+    ];
+
+    let expected = [
+        b"\x03www\x04name\x03mpc\x00".to_vec(),
+        // Type A, class IN, TTL 3600
+        vec![0, 1, 0, 1, 0, 0, 0x0e, 0x10],
+        vec![0, 4, 192, 0, 2, 1],
+        b"\x04name\x03mpc\x00".to_vec(),
+        // Type MX, class IN, TTL 3600
+        vec![0, 15, 0, 1, 0, 0, 0x0e, 0x10],
+        vec![0, 15, 0, 10],
+        b"\x02mx\x04name\x03mpc\x00".to_vec(),
+    ]
+    .concat();
+
+    assert_eq!(to_wire_format("mpc.name", &records), expected);
+}
+
+#[test]
+fn proper_dns_order() {
+    assert_eq!(to_dns_order("mpc.name.sub"), "sub.name.mpc");
+    assert_eq!(to_dns_order("mpc"), "mpc");
+}
+
+#[test]
+fn proper_dns_wire_format_long_txt() {
+    let text = "a".repeat(300);
+    let wire = to_wire_format("mpc", &[dns_record("", DnsRecordData::Txt { text })]);
+
+    // Name, fixed header and RDLENGTH of two character strings
+    let rdata = &wire[5 + 8..];
+    assert_eq!(rdata[..2], 302u16.to_be_bytes());
+    assert_eq!(rdata[2], 255);
+    assert_eq!(rdata[2 + 256], 45);
+    assert_eq!(rdata.len(), 2 + 302);
+}
+
+#[test]
+fn proper_set_dns_records() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    mint_domain(&mut state, "mpc", None, None);
+
+    let records = vec![dns_record(
+        "",
+        DnsRecordData::A {
+            address: vec![192, 0, 2, 1],
+        },
+    )];
+    execute_set_dns_records(
+        &ctx,
+        &mut state,
+        &PnsSetDnsRecordsMsg {
+            domain: "mpc".to_string(),
+            records: records.clone(),
+        },
+    );
+    assert_eq!(state.get_domain("mpc").unwrap().dns_records, records);
+
+    execute_record_delete_all(
+        &ctx,
+        &mut state,
+        &PnsRecordDeleteAllMsg {
+            domain: "mpc".to_string(),
+            updated_by: ctx.sender,
+        },
+    );
+    assert_eq!(state.get_domain("mpc").unwrap().dns_records, vec![]);
+}
+
+#[test]
+#[should_panic(expected = "The DNS record is not valid")]
+fn when_dns_record_is_invalid_set_dns_records_fails() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    mint_domain(&mut state, "mpc", None, None);
+
+    execute_set_dns_records(
+        &ctx,
+        &mut state,
+        &PnsSetDnsRecordsMsg {
+            domain: "mpc".to_string(),
+            records: vec![dns_record("", DnsRecordData::A { address: vec![] })],
+        },
+    );
+}
+
+fn set_alias(state: &mut PartisiaNameSystemState, domain: &str, alias: &str) {
+    execute_set_alias(
+        &mock_contract_context(1),
+        state,
+        &PnsSetAliasMsg {
+            domain: domain.to_string(),
+            alias: Some(alias.to_string()),
+        },
+    );
+}
+
+#[test]
+fn proper_resolve() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    let now = ctx.block_production_time;
+    mint_domain(&mut state, "shop", None, None);
+    mint_domain(&mut state, "store", None, None);
+    mint_domain(&mut state, "market", None, None);
+
+    assert_eq!(state.resolve("shop", now).unwrap().0, "shop");
+
+    set_alias(&mut state, "shop", "store");
+    set_alias(&mut state, "store", "market");
+    assert_eq!(state.resolve("shop", now).unwrap().0, "market");
+    assert_eq!(state.resolve("store", now).unwrap().0, "market");
+    assert_eq!(state.resolve("unknown", now), None);
+}
+
+#[test]
+fn proper_resolve_with_expired_target() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    mint_domain(&mut state, "shop", None, None);
+    mint_domain(&mut state, "store", None, None);
+    set_alias(&mut state, "shop", "store");
+
+    execute_update_expiration(
+        &ctx,
+        &mut state,
+        &PnsDomainUpdateExpirationMsg {
+            domain: "store".to_string(),
+            expires_at: Some(yesterday_timestamp()),
+        },
+    );
+
+    assert_eq!(state.resolve("shop", ctx.block_production_time), None);
+}
+
+#[test]
+fn proper_resolve_with_cycle_and_max_hops() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    let now = ctx.block_production_time;
+    let names: Vec<String> = (0..=MAX_ALIAS_HOPS + 1)
+        .map(|index| format!("name{index}"))
+        .collect();
+    for name in &names {
+        mint_domain(&mut state, name, None, None);
+    }
+    for pair in names.windows(2) {
+        set_alias(&mut state, &pair[0], &pair[1]);
+    }
+
+    assert_eq!(
+        state.resolve(&names[1], now).unwrap().0,
+        names[MAX_ALIAS_HOPS + 1]
+    );
+    assert_eq!(state.resolve(&names[0], now), None);
+
+    // Cycles cannot be created by the alias actions
+    let mut domain = state.get_domain(&names[MAX_ALIAS_HOPS + 1]).unwrap();
+    domain.alias = Some(names[MAX_ALIAS_HOPS].clone());
+    state
+        .domains
+        .insert(names[MAX_ALIAS_HOPS + 1].clone(), domain);
+
+    assert_eq!(state.resolve(&names[MAX_ALIAS_HOPS], now), None);
+}
+
+#[test]
+#[should_panic(expected = "The alias creates a cycle")]
+fn when_alias_creates_cycle_set_alias_fails() {
+    let mut state = execute_init(&mock_contract_context(1));
+    mint_domain(&mut state, "shop", None, None);
+    mint_domain(&mut state, "store", None, None);
+    mint_domain(&mut state, "market", None, None);
+
+    set_alias(&mut state, "shop", "store");
+    set_alias(&mut state, "store", "market");
+    set_alias(&mut state, "market", "shop");
+}
+
+#[test]
+#[should_panic(expected = "The alias must point to another minted domain")]
+fn when_alias_is_not_minted_set_alias_fails() {
+    let mut state = execute_init(&mock_contract_context(1));
+    mint_domain(&mut state, "shop", None, None);
+
+    set_alias(&mut state, "shop", "store");
+}
+
+#[test]
+fn proper_resolve_with_wildcard() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    let now = ctx.block_production_time;
+    mint_domain(&mut state, "brand", None, None);
+    mint_domain(&mut state, "brand.shop", Some("brand"), None);
+    mint_domain(&mut state, "store", None, None);
+
+    assert_eq!(state.resolve("brand.item", now), None);
+
+    execute_set_wildcard(
+        &ctx,
+        &mut state,
+        &PnsSetWildcardMsg {
+            domain: "brand".to_string(),
+            wildcard: true,
+        },
+    );
+    assert_eq!(state.resolve("brand.item", now).unwrap().0, "brand");
+    assert_eq!(state.resolve("brand.shop.item", now).unwrap().0, "brand");
+    assert_eq!(state.resolve("brand.shop", now).unwrap().0, "brand.shop");
+    assert_eq!(state.resolve("brandy.item", now), None);
+
+    set_alias(&mut state, "brand", "store");
+    assert_eq!(state.resolve("brand.item", now).unwrap().0, "store");
+}
+
+#[test]
+fn proper_resolve_batch() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    let now = ctx.block_production_time;
+    let expires_at = tomorrow_timestamp();
+    mint_domain(&mut state, "shop", None, None);
+    mint_domain(&mut state, "store", None, Some(expires_at));
+    execute_record_mint(
+        &ctx,
+        &mut state,
+        &PnsRecordMintMsg {
+            domain: "store".to_string(),
+            class: RecordClass::Bio {},
+            data: string_to_bytes("store"),
+        },
+    );
+    set_alias(&mut state, "shop", "store");
+
+    let domains = vec![
+        "shop".to_string(),
+        "store".to_string(),
+        "unknown".to_string(),
+    ];
+    let classes = vec![RecordClass::Bio {}, RecordClass::Twitter {}];
+    let resolved = state.resolve_batch(&domains, &classes, now);
+
+    assert_eq!(resolved.len(), 3);
+    assert_eq!(resolved[0].domain, "shop");
+    assert_eq!(resolved[0].token_id, Some(4));
+    assert_eq!(resolved[0].expires_at, None);
+    assert!(resolved[0].active);
+    assert_eq!(
+        resolved[0].records,
+        vec![Some(string_to_bytes("store")), None]
+    );
+    assert_eq!(resolved[1].token_id, Some(5));
+    assert_eq!(resolved[1].expires_at, Some(expires_at));
+    assert_eq!(resolved[1].records, resolved[0].records);
+    assert_eq!(resolved[2].token_id, None);
+    assert!(!resolved[2].active);
+    assert_eq!(resolved[2].records, vec![None, None]);
+}
+
+#[test]
+fn proper_record_history() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    state.config.record_history_limit = 2;
+    mint_domain(&mut state, "name", None, None);
+
+    let class = RecordClass::Bio {};
+    execute_record_mint(
+        &ctx,
+        &mut state,
+        &PnsRecordMintMsg {
+            domain: "name".to_string(),
+            class,
+            data: string_to_bytes("v1"),
+        },
+    );
+    let history = state.get_record_history("name", &class).unwrap();
+    assert_eq!(history.version, 1);
+    assert_eq!(history.updated_by, ctx.sender);
+    assert_eq!(history.updated_at, ctx.block_production_time);
+    assert!(history.revisions.is_empty());
+
+    for data in ["v2", "v3"] {
+        execute_record_update(
+            &mock_contract_context(2),
+            &mut state,
+            &PnsRecordUpdateMsg {
+                domain: "name".to_string(),
+                class,
+                data: string_to_bytes(data),
+            },
+        );
+    }
+    execute_record_delete(
+        &mock_contract_context(2),
+        &mut state,
+        &PnsRecordDeleteMsg {
+            domain: "name".to_string(),
+            class,
+        },
+    );
+
+    let history = state.get_record_history("name", &class).unwrap();
+    assert_eq!(history.version, 4);
+    assert_eq!(history.updated_by, mock_contract_context(2).sender);
+    let revisions: Vec<(u32, Option<Vec<u8>>)> = history
+        .revisions
+        .into_iter()
+        .map(|revision| (revision.version, revision.data))
+        .collect();
+    assert_eq!(
+        revisions,
+        vec![
+            (2, Some(string_to_bytes("v2"))),
+            (3, Some(string_to_bytes("v3")))
+        ]
+    );
+    assert_eq!(state.get_record_history("name", &RecordClass::Uri {}), None);
+}
+
+#[test]
+fn proper_record_history_without_revisions() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    state.config.record_history_limit = 0;
+    mint_domain(&mut state, "name", None, None);
+
+    let class = RecordClass::Bio {};
+    execute_record_mint(
+        &ctx,
+        &mut state,
+        &PnsRecordMintMsg {
+            domain: "name".to_string(),
+            class,
+            data: string_to_bytes("v1"),
+        },
+    );
+    execute_record_delete(
+        &mock_contract_context(2),
+        &mut state,
+        &PnsRecordDeleteMsg {
+            domain: "name".to_string(),
+            class,
+        },
+    );
+
+    let history = state.get_record_history("name", &class).unwrap();
+    assert_eq!(history.version, 2);
+    assert_eq!(history.updated_by, mock_contract_context(2).sender);
+    assert!(history.revisions.is_empty());
+}
+
+#[test]
+fn proper_custom_record_history() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    mint_domain(&mut state, "name", None, None);
+
+    execute_custom_record_mint(
+        &ctx,
+        &mut state,
+        &PnsCustomRecordMintMsg {
+            domain: "name".to_string(),
+            key: "key".to_string(),
+            data: string_to_bytes("v1"),
+        },
+    );
+    execute_custom_record_update(
+        &ctx,
+        &mut state,
+        &PnsCustomRecordUpdateMsg {
+            domain: "name".to_string(),
+            key: "key".to_string(),
+            data: string_to_bytes("v2"),
+        },
+    );
+    execute_custom_record_delete(
+        &mock_contract_context(2),
+        &mut state,
+        &PnsCustomRecordDeleteMsg {
+            domain: "name".to_string(),
+            key: "key".to_string(),
+        },
+    );
+
+    let history = state.get_custom_record_history("name", "key").unwrap();
+    assert_eq!(history.version, 3);
+    assert_eq!(history.updated_by, mock_contract_context(2).sender);
+    let revisions: Vec<(u32, Option<Vec<u8>>)> = history
+        .revisions
+        .into_iter()
+        .map(|revision| (revision.version, revision.data))
+        .collect();
+    assert_eq!(
+        revisions,
+        vec![
+            (1, Some(string_to_bytes("v1"))),
+            (2, Some(string_to_bytes("v2")))
+        ]
+    );
+    assert_eq!(state.get_custom_record_history("name", "other"), None);
+}
+
+#[test]
+fn proper_record_delete_all_clears_history() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    mint_domain(&mut state, "name", None, None);
+
+    execute_record_mint(
+        &ctx,
+        &mut state,
+        &PnsRecordMintMsg {
+            domain: "name".to_string(),
+            class: RecordClass::Wallet {},
+            data: string_to_bytes(WALLET_ADDRESS),
+        },
+    );
+    execute_custom_record_mint(
+        &ctx,
+        &mut state,
+        &PnsCustomRecordMintMsg {
+            domain: "name".to_string(),
+            key: "key".to_string(),
+            data: string_to_bytes("v1"),
+        },
+    );
+    execute_record_delete_all(
+        &ctx,
+        &mut state,
+        &PnsRecordDeleteAllMsg {
+            domain: "name".to_string(),
+            updated_by: mock_contract_context(2).sender,
+        },
+    );
+
+    assert_eq!(
+        state.get_record_history("name", &RecordClass::Wallet {}),
+        None
+    );
+    assert_eq!(state.get_custom_record_history("name", "key"), None);
+    assert_eq!(
+        state.get_record_histories("name"),
+        RecordHistories::default()
+    );
+}
+
+fn set_premium_tier(state: &mut PartisiaNameSystemState, domain: &str, premium_tier: Option<u32>) {
+    execute_set_premium_tier(
+        &mock_contract_context(1),
+        state,
+        &PnsSetPremiumTierMsg {
+            domain: domain.to_string(),
+            premium_tier,
+        },
+    );
+}
+
+#[test]
+fn proper_record_limits_with_premium_tier() {
+    let mut state = execute_init(&mock_contract_context(1));
+    state.config.premium_tiers.push(PremiumTier {
+        record_limits: RecordLimits {
+            max_record_data_length: 256,
+            max_custom_records: 50,
+        },
+        price: 10,
+    });
+    mint_domain(&mut state, "name", None, None);
+
+    assert_eq!(state.get_record_limits("name"), state.config.record_limits);
+
+    set_premium_tier(&mut state, "name", Some(0));
+    assert_eq!(state.get_record_limits("name").max_record_data_length, 256);
+
+    // A tier removed from the config falls back to the default limits
+    state.config.premium_tiers = vec![];
+    assert_eq!(state.get_record_limits("name"), state.config.record_limits);
+}
+
+#[test]
+fn proper_pns_config_keeps_premium_tiers() {
+    let tier = |max_record_data_length: u32, price: u128| PremiumTier {
+        record_limits: RecordLimits {
+            max_record_data_length,
+            max_custom_records: 50,
+        },
+        price,
+    };
+    let previous = PnsConfig {
+        premium_tiers: vec![tier(128, 10), tier(256, 20)],
+        ..PnsConfig::default()
+    };
+
+    let with_tiers = |premium_tiers: Vec<PremiumTier>| PnsConfig {
+        premium_tiers,
+        ..PnsConfig::default()
+    };
+    assert!(with_tiers(vec![tier(128, 10), tier(256, 20)]).keeps_premium_tiers(&previous));
+    assert!(with_tiers(vec![tier(128, 5), tier(256, 40)]).keeps_premium_tiers(&previous));
+    assert!(
+        with_tiers(vec![tier(128, 10), tier(256, 20), tier(64, 1)]).keeps_premium_tiers(&previous)
+    );
+    assert!(!with_tiers(vec![tier(128, 10)]).keeps_premium_tiers(&previous));
+    assert!(!with_tiers(vec![tier(256, 20), tier(128, 10)]).keeps_premium_tiers(&previous));
+    assert!(!with_tiers(vec![tier(128, 10), tier(64, 20)]).keeps_premium_tiers(&previous));
+}
+
+#[test]
+fn proper_record_mint_with_premium_tier() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    state.config.premium_tiers.push(PremiumTier {
+        record_limits: RecordLimits {
+            max_record_data_length: 256,
+            max_custom_records: 50,
+        },
+        price: 10,
+    });
+    mint_domain(&mut state, "name", None, None);
+    set_premium_tier(&mut state, "name", Some(0));
+
+    let data = vec![b'a'; 128];
+    execute_record_mint(
+        &ctx,
+        &mut state,
+        &PnsRecordMintMsg {
+            domain: "name".to_string(),
+            class: RecordClass::Bio {},
+            data: data.clone(),
+        },
+    );
+
+    let domain = state.get_domain("name").unwrap();
+    assert_eq!(domain.get_record(&RecordClass::Bio {}), Some(&data));
+}
+
+#[test]
+#[should_panic(expected = "The record data is too long")]
+fn when_record_update_exceeds_limit_update_fails() {
+    let ctx = mock_contract_context(1);
+    let mut state = execute_init(&ctx);
+    state.config.record_limits.max_record_data_length = 8;
+    mint_domain(&mut state, "name", None, None);
+
+    execute_record_mint(
+        &ctx,
+        &mut state,
+        &PnsRecordMintMsg {
+            domain: "name".to_string(),
+            class: RecordClass::Bio {},
+            data: string_to_bytes("short"),
+        },
+    );
+    execute_record_update(
+        &ctx,
+        &mut state,
+        &PnsRecordUpdateMsg {
+            domain: "name".to_string(),
+            class: RecordClass::Bio {},
+            data: string_to_bytes("much longer"),
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "The premium tier is not valid")]
+fn when_premium_tier_is_not_configured_set_fails() {
+    let mut state = execute_init(&mock_contract_context(1));
+    mint_domain(&mut state, "name", None, None);
+
+    set_premium_tier(&mut state, "name", Some(0));
+}
+
+#[test]
+#[should_panic(expected = "The specified domain is not valid")]
+fn when_domain_exceeds_configured_length_validation_fails() {
+    let mut config = PnsConfig::default();
+    validate_domain(&config, "name.mpc");
+
+    config.max_domain_len = 4;
+    validate_domain(&config, "name.mpc");
+}
+
+fn set_storage_quota(state: &mut PartisiaNameSystemState, domain: &str, storage_quota_kb: u32) {
+    execute_set_storage_quota(
+        &mock_contract_context(1),
+        state,
+        &PnsSetStorageQuotaMsg {
+            domain: domain.to_string(),
+            storage_quota_kb,
+        },
+    );
+}
+
+fn write_large_record(state: &mut PartisiaNameSystemState, domain: &str, key: &str, value: &[u8]) {
+    for (index, data) in split_into_chunks(value).into_iter().enumerate() {
+        execute_write_large_record_chunk(
+            &mock_contract_context(1),
+            state,
+            &PnsWriteLargeRecordChunkMsg {
+                domain: domain.to_string(),
+                key: key.to_string(),
+                index: index as u32,
+                data,
+            },
+        );
+    }
+}
+
+fn commit_large_record(state: &mut PartisiaNameSystemState, domain: &str, key: &str, value: &[u8]) {
+    execute_commit_large_record(
+        &mock_contract_context(1),
+        state,
+        &PnsCommitLargeRecordMsg {
+            domain: domain.to_string(),
+            key: key.to_string(),
+            commit: large_record_commit(value),
+        },
+    );
+}
+
+#[test]
+fn proper_large_record() {
+    let mut state = execute_init(&mock_contract_context(1));
+    state.config.max_storage_quota_kb = 4;
+    mint_domain(&mut state, "name", None, None);
+    set_storage_quota(&mut state, "name", 4);
+
+    let value = vec![b'a'; 2500];
+    write_large_record(&mut state, "name", "avatar", &value);
+
+    let large_record = state
+        .get_large_records("name")
+        .get(&"avatar".to_string())
+        .cloned();
+    assert_eq!(large_record.unwrap().chunks.len(), 3);
+    assert_eq!(state.get_storage_used("name"), 2500);
+    // Uncommitted records are not resolved
+    assert_eq!(state.get_large_record_value("name", "avatar"), None);
+
+    commit_large_record(&mut state, "name", "avatar", &value);
+    assert_eq!(
+        state.get_large_record_value("name", "avatar"),
+        Some(value.clone())
+    );
+
+    let commit = large_record_commit(&value);
+    assert!(verify_large_record(&split_into_chunks(&value), &commit));
+    assert!(!verify_large_record(
+        &split_into_chunks(&value[1..]),
+        &commit
+    ));
+}
+
+#[test]
+fn proper_large_record_chunk_rewrite() {
+    let mut state = execute_init(&mock_contract_context(1));
+    state.config.max_storage_quota_kb = 1;
+    mint_domain(&mut state, "name", None, None);
+    set_storage_quota(&mut state, "name", 1);
+
+    write_large_record(&mut state, "name", "avatar", &[b'a'; 1024]);
+    commit_large_record(&mut state, "name", "avatar", &[b'a'; 1024]);
+
+    // Rewriting a chunk only counts its new size and uncommits the record
+    write_large_record(&mut state, "name", "avatar", &[b'b'; 512]);
+    assert_eq!(state.get_storage_used("name"), 512);
+    assert_eq!(state.get_large_record_value("name", "avatar"), None);
+}
+
+#[test]
+#[should_panic(expected = "The storage quota of the domain is exceeded")]
+fn when_large_record_exceeds_storage_quota_write_fails() {
+    let mut state = execute_init(&mock_contract_context(1));
+    state.config.max_storage_quota_kb = 1;
+    mint_domain(&mut state, "name", None, None);
+    set_storage_quota(&mut state, "name", 1);
+
+    write_large_record(&mut state, "name", "avatar", &[b'a'; 1025]);
+}
+
+#[test]
+#[should_panic(expected = "The large record chunk is not valid")]
+fn when_large_record_chunk_skips_index_write_fails() {
+    let mut state = execute_init(&mock_contract_context(1));
+    state.config.max_storage_quota_kb = 1;
+    mint_domain(&mut state, "name", None, None);
+    set_storage_quota(&mut state, "name", 1);
+
+    execute_write_large_record_chunk(
+        &mock_contract_context(1),
+        &mut state,
+        &PnsWriteLargeRecordChunkMsg {
+            domain: "name".to_string(),
+            key: "avatar".to_string(),
+            index: 1,
+            data: vec![b'a'; 16],
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "The large record does not match the commit")]
+fn when_commit_does_not_match_large_record_commit_fails() {
+    let mut state = execute_init(&mock_contract_context(1));
+    state.config.max_storage_quota_kb = 1;
+    mint_domain(&mut state, "name", None, None);
+    set_storage_quota(&mut state, "name", 1);
+
+    write_large_record(&mut state, "name", "avatar", b"value");
+    commit_large_record(&mut state, "name", "avatar", b"other");
+}
+
+#[test]
+#[should_panic(expected = "The storage quota of the domain is exceeded")]
+fn when_storage_quota_is_below_usage_set_fails() {
+    let mut state = execute_init(&mock_contract_context(1));
+    state.config.max_storage_quota_kb = 2;
+    mint_domain(&mut state, "name", None, None);
+    set_storage_quota(&mut state, "name", 2);
+    write_large_record(&mut state, "name", "avatar", &[b'a'; 1500]);
+
+    set_storage_quota(&mut state, "name", 1);
+}
+
+#[test]
+#[should_panic(expected = "The specified domain is expired")]
+fn when_domain_is_expired_set_storage_quota_fails() {
+    let mut state = execute_init(&mock_contract_context(1));
+    state.config.max_storage_quota_kb = 2;
+    mint_domain(&mut state, "name", None, Some(yesterday_timestamp()));
+
+    set_storage_quota(&mut state, "name", 1);
+}
+
+#[test]
+fn proper_prorated_storage_price() {
+    let config = PnsConfig {
+        storage_price_per_kb: 100,
+        ..PnsConfig::default()
+    };
+    let year = milliseconds_in_years(1);
+
+    assert_eq!(config.get_prorated_storage_price(2, year), 200);
+    assert_eq!(config.get_prorated_storage_price(2, year / 4), 50);
+    assert_eq!(config.get_prorated_storage_price(2, 3 * year), 600);
+    assert_eq!(config.get_prorated_storage_price(2, -year), 0);
+}
+
+#[test]
+#[should_panic(expected = "The storage quota is not valid")]
+fn when_storage_quota_exceeds_maximum_set_fails() {
+    let mut state = execute_init(&mock_contract_context(1));
+    state.config.max_storage_quota_kb = 2;
+    mint_domain(&mut state, "name", None, None);
+
+    set_storage_quota(&mut state, "name", 3);
+}
+
+#[test]
+fn proper_storage_quota_beyond_u32_bytes() {
+    let mut state = execute_init(&mock_contract_context(1));
+    state.config.max_storage_quota_kb = u32::MAX;
+    mint_domain(&mut state, "name", None, None);
+    set_storage_quota(&mut state, "name", u32::MAX);
+
+    write_large_record(&mut state, "name", "avatar", b"value");
+    assert_eq!(state.get_storage_used("name"), 5);
+}
+
+fn expire_domain(state: &mut PartisiaNameSystemState, domain: &str) {
+    execute_update_expiration(
+        &mock_contract_context(1),
+        state,
+        &PnsDomainUpdateExpirationMsg {
+            domain: domain.to_string(),
+            expires_at: Some(yesterday_timestamp()),
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "The specified domain is expired")]
+fn when_domain_is_expired_large_record_commit_fails() {
+    let mut state = execute_init(&mock_contract_context(1));
+    state.config.max_storage_quota_kb = 1;
+    mint_domain(&mut state, "name", None, None);
+    set_storage_quota(&mut state, "name", 1);
+    write_large_record(&mut state, "name", "avatar", b"value");
+    expire_domain(&mut state, "name");
+
+    commit_large_record(&mut state, "name", "avatar", b"value");
+}
+
+#[test]
+#[should_panic(expected = "The specified domain is expired")]
+fn when_domain_is_expired_large_record_delete_fails() {
+    let mut state = execute_init(&mock_contract_context(1));
+    state.config.max_storage_quota_kb = 1;
+    mint_domain(&mut state, "name", None, None);
+    set_storage_quota(&mut state, "name", 1);
+    write_large_record(&mut state, "name", "avatar", b"value");
+    expire_domain(&mut state, "name");
+
+    execute_large_record_delete(
+        &mock_contract_context(1),
+        &mut state,
+        &PnsLargeRecordDeleteMsg {
+            domain: "name".to_string(),
+            key: "avatar".to_string(),
+        },
+    );
+}
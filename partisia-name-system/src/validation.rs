@@ -0,0 +1,279 @@
+use crate::{
+    content_hash::is_valid_content_hash,
+    state::{
+        DnsRecord, DnsRecordData, RecordClass, COIN_TYPE_BITCOIN, COIN_TYPE_ETHEREUM,
+        COIN_TYPE_PARTISIA, MAX_ADDRESS_TYPE, MAX_DNS_RECORDS,
+    },
+    ContractError,
+};
+
+const ADDRESS_HEX_LEN: usize = 42;
+const ETHEREUM_ADDRESS_HEX_LEN: usize = 40;
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const MIN_BITCOIN_BASE58_LEN: usize = 26;
+const MAX_BITCOIN_BASE58_LEN: usize = 35;
+const MIN_BITCOIN_BECH32_LEN: usize = 14;
+const MAX_BITCOIN_BECH32_LEN: usize = 74;
+const MAX_EMAIL_LOCAL_LEN: usize = 64;
+const EMAIL_LOCAL_SPECIAL_CHARS: &str = "!#$%&'*+/=?^_`{|}~-";
+const URI_SPECIAL_CHARS: &str = "-._~:/?#[]@!$&'()*+,;=%";
+const MAX_TWITTER_HANDLE_LEN: usize = 15;
+const MIN_DISCORD_HANDLE_LEN: usize = 2;
+const MAX_DISCORD_HANDLE_LEN: usize = 32;
+const IPV4_LEN: usize = 4;
+const IPV6_LEN: usize = 16;
+const MAX_DNS_LABEL_LEN: usize = 63;
+const MAX_DNS_NAME_LEN: usize = 253;
+/// TTLs are positive signed 32 bits values, see RFC 2181
+const MAX_DNS_TTL: u32 = i32::MAX as u32;
+const MAX_DNS_TXT_LEN: usize = 512;
+
+/// Validate the record data given its class
+/// Returns [`()`] if the data is valid,
+/// otherwise panics with error message defined in [`ContractError`]
+pub fn validate_record(class: &RecordClass, data: &[u8]) {
+    match class {
+        RecordClass::Wallet {} => assert!(
+            is_valid_address(data),
+            "{}",
+            ContractError::InvalidWalletRecord
+        ),
+        RecordClass::Email {} => assert!(
+            is_valid_email(data),
+            "{}",
+            ContractError::InvalidEmailRecord
+        ),
+        RecordClass::Uri {} | RecordClass::Avatar {} => {
+            assert!(is_valid_uri(data), "{}", ContractError::InvalidUriRecord)
+        }
+        RecordClass::Twitter {} => assert!(
+            is_valid_twitter_handle(data),
+            "{}",
+            ContractError::InvalidTwitterRecord
+        ),
+        RecordClass::Discord {} => assert!(
+            is_valid_discord_handle(data),
+            "{}",
+            ContractError::InvalidDiscordRecord
+        ),
+        _ => {}
+    }
+}
+
+/// Validate the address format given its SLIP-44 coin type
+/// Returns [`()`] if the address is valid,
+/// otherwise panics with error message defined in [`ContractError`]
+pub fn validate_address(coin_type: u32, address: &[u8]) {
+    let is_valid = match coin_type {
+        COIN_TYPE_PARTISIA => is_valid_address(address),
+        COIN_TYPE_ETHEREUM => is_valid_ethereum_address(address),
+        COIN_TYPE_BITCOIN => is_valid_bitcoin_address(address),
+        _ => panic!("{}", ContractError::UnsupportedCoinType),
+    };
+
+    assert!(is_valid, "{}", ContractError::InvalidAddress);
+}
+
+/// Validate the content hash record
+/// Returns [`()`] if the content hash is valid,
+/// otherwise panics with error message defined in [`ContractError`]
+pub fn validate_content_hash(content_hash: &[u8]) {
+    assert!(
+        is_valid_content_hash(content_hash),
+        "{}",
+        ContractError::InvalidContentHash
+    );
+}
+
+/// Validate the DNS record set of a domain
+/// A name with a CNAME record cannot have other records, see RFC 1034
+/// Returns [`()`] if the records are valid,
+/// otherwise panics with error message defined in [`ContractError`]
+pub fn validate_dns_records(records: &[DnsRecord]) {
+    assert!(
+        records.len() <= MAX_DNS_RECORDS,
+        "{}",
+        ContractError::MaxDnsRecords
+    );
+
+    for record in records {
+        assert!(
+            is_valid_dns_record(record),
+            "{}",
+            ContractError::InvalidDnsRecord
+        );
+    }
+
+    for record in records {
+        if matches!(record.data, DnsRecordData::Cname { .. }) {
+            assert!(
+                records
+                    .iter()
+                    .filter(|other| other.name == record.name)
+                    .count()
+                    == 1,
+                "{}",
+                ContractError::DnsCnameConflict
+            );
+        }
+    }
+}
+
+/// Checks the owner name, the TTL and the data of the DNS record given its type
+pub fn is_valid_dns_record(record: &DnsRecord) -> bool {
+    let is_valid_name = record.name.is_empty() || is_valid_dns_name(&record.name, true);
+
+    let is_valid_data = match &record.data {
+        DnsRecordData::A { address } => address.len() == IPV4_LEN,
+        DnsRecordData::Aaaa { address } => address.len() == IPV6_LEN,
+        DnsRecordData::Cname { target } => is_valid_dns_name(target, false),
+        DnsRecordData::Txt { text } => text.len() <= MAX_DNS_TXT_LEN,
+        DnsRecordData::Mx { exchange, .. } => is_valid_dns_name(exchange, false),
+        DnsRecordData::Srv { target, .. } => is_valid_dns_name(target, false),
+    };
+
+    is_valid_name && record.ttl <= MAX_DNS_TTL && is_valid_data
+}
+
+/// Dot separated DNS labels of letters, digits, hyphens and underscores
+/// The wildcard label `*` is only allowed as the first label of an owner name
+pub fn is_valid_dns_name(name: &str, allow_wildcard: bool) -> bool {
+    name.len() <= MAX_DNS_NAME_LEN
+        && name.split('.').enumerate().all(|(index, label)| {
+            (allow_wildcard && index == 0 && label == "*")
+                || ((1..=MAX_DNS_LABEL_LEN).contains(&label.len())
+                    && !label.starts_with('-')
+                    && !label.ends_with('-')
+                    && label
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        })
+}
+
+/// Hex encoded Partisia address, the first byte being the address type
+pub fn is_valid_address(data: &[u8]) -> bool {
+    let address = data.strip_prefix(b"0x").unwrap_or(data);
+
+    address.len() == ADDRESS_HEX_LEN
+        && address.iter().all(u8::is_ascii_hexdigit)
+        && std::str::from_utf8(&address[..2])
+            .ok()
+            .and_then(|address_type| u8::from_str_radix(address_type, 16).ok())
+            .is_some_and(|address_type| address_type <= MAX_ADDRESS_TYPE)
+}
+
+/// `0x` prefixed hex encoded Ethereum address, the EIP-55 checksum is not verified
+pub fn is_valid_ethereum_address(data: &[u8]) -> bool {
+    data.strip_prefix(b"0x").is_some_and(|address| {
+        address.len() == ETHEREUM_ADDRESS_HEX_LEN && address.iter().all(u8::is_ascii_hexdigit)
+    })
+}
+
+/// Mainnet Bitcoin address, either base58 (P2PKH, P2SH) or bech32 (segwit)
+/// Only the format is checked, not the checksum
+pub fn is_valid_bitcoin_address(data: &[u8]) -> bool {
+    let Ok(address) = std::str::from_utf8(data) else {
+        return false;
+    };
+
+    let is_base58 = (address.starts_with('1') || address.starts_with('3'))
+        && (MIN_BITCOIN_BASE58_LEN..=MAX_BITCOIN_BASE58_LEN).contains(&address.len())
+        && address.chars().all(|c| BASE58_ALPHABET.contains(c));
+
+    // bech32 is case insensitive but must not be mixed case
+    let lowercase = address.to_ascii_lowercase();
+    let is_bech32 = (address == lowercase || address == address.to_ascii_uppercase())
+        && (MIN_BITCOIN_BECH32_LEN..=MAX_BITCOIN_BECH32_LEN).contains(&address.len())
+        && lowercase
+            .strip_prefix("bc1")
+            .is_some_and(|data| data.chars().all(|c| BECH32_CHARSET.contains(c)));
+
+    is_base58 || is_bech32
+}
+
+/// Subset of the RFC 5322 addr-spec: a dot-atom local part and a dot separated host name
+pub fn is_valid_email(data: &[u8]) -> bool {
+    let Ok(email) = std::str::from_utf8(data) else {
+        return false;
+    };
+    let Some((local, host)) = email.rsplit_once('@') else {
+        return false;
+    };
+
+    let is_valid_local = local.len() <= MAX_EMAIL_LOCAL_LEN
+        && is_dot_atom(local, |c| {
+            c.is_ascii_alphanumeric() || EMAIL_LOCAL_SPECIAL_CHARS.contains(c)
+        });
+
+    let labels: Vec<&str> = host.split('.').collect();
+    let is_valid_host = labels.len() > 1
+        && labels.iter().all(|label| is_valid_host_label(label))
+        && labels
+            .last()
+            .is_some_and(|tld| tld.len() > 1 && tld.chars().all(|c| c.is_ascii_alphabetic()));
+
+    is_valid_local && is_valid_host
+}
+
+/// RFC 3986 URI with a scheme and a non empty hierarchical part
+pub fn is_valid_uri(data: &[u8]) -> bool {
+    let Ok(uri) = std::str::from_utf8(data) else {
+        return false;
+    };
+    let Some((scheme, rest)) = uri.split_once(':') else {
+        return false;
+    };
+
+    let is_valid_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+
+    let is_valid_rest = !rest.is_empty()
+        && rest
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || URI_SPECIAL_CHARS.contains(c))
+        && is_valid_percent_encoding(rest);
+
+    is_valid_scheme && is_valid_rest
+}
+
+/// Twitter handle without the leading `@`
+pub fn is_valid_twitter_handle(data: &[u8]) -> bool {
+    (1..=MAX_TWITTER_HANDLE_LEN).contains(&data.len())
+        && data.iter().all(|c| c.is_ascii_alphanumeric() || *c == b'_')
+}
+
+/// Discord username, lowercase without consecutive dots
+pub fn is_valid_discord_handle(data: &[u8]) -> bool {
+    let Ok(handle) = std::str::from_utf8(data) else {
+        return false;
+    };
+
+    (MIN_DISCORD_HANDLE_LEN..=MAX_DISCORD_HANDLE_LEN).contains(&handle.len())
+        && handle
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '.')
+        && !handle.contains("..")
+}
+
+fn is_dot_atom(value: &str, is_atom_char: impl Fn(char) -> bool) -> bool {
+    value
+        .split('.')
+        .all(|atom| !atom.is_empty() && atom.chars().all(&is_atom_char))
+}
+
+fn is_valid_host_label(label: &str) -> bool {
+    !label.is_empty()
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+        && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn is_valid_percent_encoding(value: &str) -> bool {
+    value
+        .split('%')
+        .skip(1)
+        .all(|encoded| encoded.len() >= 2 && encoded.chars().take(2).all(|c| c.is_ascii_hexdigit()))
+}
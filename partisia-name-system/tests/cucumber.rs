@@ -146,9 +146,13 @@ fn domain_record_delete(world: &mut PartisiaNameSystemWorld, class: String, doma
 
 #[when(regex = ".+ deletes all records for the '(.+)' domain")]
 fn domain_record_delete_all(world: &mut PartisiaNameSystemWorld, domain: String) {
-    let msg = PnsRecordDeleteAllMsg { domain };
+    let ctx = mock_contract_context(1);
+    let msg = PnsRecordDeleteAllMsg {
+        domain,
+        updated_by: ctx.sender,
+    };
 
-    execute_record_delete_all(&mock_contract_context(1), &mut world.state, &msg);
+    execute_record_delete_all(&ctx, &mut world.state, &msg);
 }
 
 #[then(regex = "'(.+)' domain (is|is not) minted")]
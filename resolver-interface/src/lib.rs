@@ -0,0 +1,83 @@
+#![doc = include_str!("../README.md")]
+
+use create_type_spec_derive::CreateTypeSpec;
+use partisia_name_system::state::{RecordClass, MAX_ADDRESS_TYPE};
+use pbc_contract_common::{
+    address::{Address, AddressType, Shortname},
+    events::EventGroupBuilder,
+};
+use read_write_rpc_derive::ReadWriteRPC;
+use rpc_msg_derive::IntoShortnameRPCEvent;
+use utils::events::IntoShortnameRPCEvent;
+
+const ADDRESS_LEN: usize = 21;
+
+/// Request sent to the Meta Names contract, answered by calling the sender back
+#[derive(ReadWriteRPC, CreateTypeSpec, IntoShortnameRPCEvent, Clone, PartialEq, Eq, Debug)]
+#[rpc_msg(action = 0x52)]
+pub struct ResolveRequest {
+    pub domain: String,
+    /// Record resolved along with the owner
+    pub class: RecordClass,
+    /// Shortname of the sender action receiving the [`ResolveResponse`]
+    pub callback_shortname: u32,
+    /// Echoed in the response to match the request
+    pub request_id: u64,
+}
+
+/// Response sent by the Meta Names contract to the callback shortname of the request
+#[derive(ReadWriteRPC, CreateTypeSpec, Clone, PartialEq, Eq, Debug)]
+pub struct ResolveResponse {
+    pub request_id: u64,
+    pub domain: String,
+    pub owner: Option<Address>,
+    pub active: bool,
+    /// Record data, `None` if the domain does not resolve or the record is not set
+    pub data: Option<Vec<u8>>,
+}
+
+impl ResolveResponse {
+    /// Calls the callback action of the requesting contract with the response
+    pub fn as_callback_interaction(
+        &self,
+        builder: &mut EventGroupBuilder,
+        dest: &Address,
+        callback_shortname: u32,
+    ) {
+        builder
+            .call(*dest, Shortname::from_u32(callback_shortname))
+            .argument(self.clone())
+            .done();
+    }
+
+    /// Returns the address of a resolved Wallet record
+    /// `None` if the domain is not active or the data is not a valid address
+    /// Address types up to [`MAX_ADDRESS_TYPE`] are accepted, as in the record validation
+    pub fn wallet_address(&self) -> Option<Address> {
+        if !self.active {
+            return None;
+        }
+
+        let data = self.data.as_ref()?;
+        let bytes = hex::decode(data.strip_prefix(b"0x").unwrap_or(data)).ok()?;
+        if bytes.len() != ADDRESS_LEN || bytes[0] > MAX_ADDRESS_TYPE {
+            return None;
+        }
+
+        let address_type = match bytes[0] {
+            0x00 => AddressType::Account,
+            0x01 => AddressType::SystemContract,
+            0x02 => AddressType::PublicContract,
+            0x03 => AddressType::ZkContract,
+            _ => return None,
+        };
+
+        Some(Address {
+            address_type,
+            identifier: bytes[1..].try_into().ok()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
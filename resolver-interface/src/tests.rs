@@ -0,0 +1,71 @@
+use utils::{events::IntoShortnameRPCEvent, tests::mock_address};
+
+use crate::{ResolveRequest, ResolveResponse};
+use partisia_name_system::state::{RecordClass, MAX_ADDRESS_TYPE};
+
+fn wallet_response(data: &str, active: bool) -> ResolveResponse {
+    ResolveResponse {
+        request_id: 1,
+        domain: "alice".to_string(),
+        owner: Some(mock_address(1)),
+        active,
+        data: Some(data.as_bytes().to_vec()),
+    }
+}
+
+#[test]
+fn proper_resolve_request_shortname() {
+    let request = ResolveRequest {
+        domain: "alice".to_string(),
+        class: RecordClass::Wallet {},
+        callback_shortname: 0x02,
+        request_id: 1,
+    };
+
+    assert_eq!(request.action_shortname(), 0x52);
+}
+
+#[test]
+fn proper_wallet_address() {
+    let response = wallet_response("0x000700000000000000000000000000000000000000", true);
+
+    assert_eq!(response.wallet_address(), Some(mock_address(7)));
+}
+
+#[test]
+fn proper_wallet_address_not_resolved() {
+    let invalid_responses = [
+        wallet_response("000700000000000000000000000000000000000000", false),
+        wallet_response("0007000000000000000000000000000000000000", true),
+        wallet_response("090700000000000000000000000000000000000000", true),
+        wallet_response(
+            &format!(
+                "{:02x}0700000000000000000000000000000000000000",
+                MAX_ADDRESS_TYPE + 1
+            ),
+            true,
+        ),
+        wallet_response("not an address", true),
+        ResolveResponse {
+            data: None,
+            ..wallet_response("", true)
+        },
+    ];
+
+    for response in invalid_responses {
+        assert_eq!(response.wallet_address(), None);
+    }
+}
+
+#[test]
+fn proper_wallet_address_types() {
+    for address_type in 0..=MAX_ADDRESS_TYPE {
+        let response = wallet_response(
+            &format!("{address_type:02x}0700000000000000000000000000000000000000"),
+            true,
+        );
+        let address = response.wallet_address().unwrap();
+
+        assert_eq!(address.address_type as u8, address_type);
+    }
+}
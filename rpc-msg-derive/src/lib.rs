@@ -80,7 +80,7 @@ fn build_arguments(data: Data) -> TokenStream2 {
             let fields = named.iter().map(|f| &f.ident);
             let ftypes = named.iter().map(|f| &f.ty);
 
-            for (field, _) in fields.into_iter().zip(ftypes.into_iter()) {
+            for (field, _) in fields.into_iter().zip(ftypes) {
                 arguments_stream.extend::<TokenStream2>(quote! {
                     interaction = interaction.argument(self.#field.clone());
                 })
@@ -135,10 +135,10 @@ impl Ord for DecimalRatio {
     }
 }
 
-impl ToString for DecimalRatio {
-    fn to_string(&self) -> String {
+impl std::fmt::Display for DecimalRatio {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let d: Decimal = (*self).into();
-        d.to_string()
+        std::fmt::Display::fmt(&d, f)
     }
 }
 
@@ -68,10 +68,15 @@ pub fn build_msg_callback_with_cost<T>(
 /// * **callback_ctx** is an object of type [`CallbackContext`]
 #[inline]
 pub fn assert_callback_success(callback_ctx: &CallbackContext) {
-    assert!(
-        callback_ctx.success && callback_ctx.results.iter().all(|res| res.succeeded),
-        "Callback has errors"
-    );
+    assert!(is_callback_success(callback_ctx), "Callback has errors");
+}
+
+/// Checks that all spawned events from original action was executed successfully
+/// ## Params
+/// * **callback_ctx** is an object of type [`CallbackContext`]
+#[inline]
+pub fn is_callback_success(callback_ctx: &CallbackContext) -> bool {
+    callback_ctx.success && callback_ctx.results.iter().all(|res| res.succeeded)
 }
 
 #[cfg(test)]